@@ -19,16 +19,104 @@ impl AudioBlockData {
     }
 }
 
+/// Number of 32-bit bitmap words needed to cover `POOL_SIZE` slots.
+const POOL_WORDS: usize = POOL_SIZE.div_ceil(32);
+
+// Slot indices are handed out as `u8`
+const _: () = assert!(POOL_SIZE <= 256, "POOL_SIZE must fit slot indices in a u8");
+
+/// Lock-free allocation bitmap over `WORDS * 32` slots, of which the first
+/// `len` are usable.
+///
+/// Each word is claimed with its own compare-exchange, so allocation stays
+/// lock-free at any pool size; a racing allocator only forces a retry of
+/// the word it lost.
+struct SlotBitmap<const WORDS: usize> {
+    /// Bit `n % 32` of word `n / 32` = 1 means slot `n` is allocated.
+    words: [AtomicU32; WORDS],
+    /// Number of usable slots.
+    len: usize,
+}
+
+impl<const WORDS: usize> SlotBitmap<WORDS> {
+    const fn new(len: usize) -> Self {
+        SlotBitmap {
+            words: [const { AtomicU32::new(0) }; WORDS],
+            len,
+        }
+    }
+
+    /// Bits of word `w` that correspond to usable slots.
+    fn valid_mask(&self, w: usize) -> u32 {
+        let remaining = self.len.saturating_sub(w * 32);
+        if remaining >= 32 {
+            u32::MAX
+        } else {
+            (1u32 << remaining) - 1
+        }
+    }
+
+    /// Claim the lowest free slot, or `None` if all are taken.
+    fn claim(&self) -> Option<usize> {
+        for (w, word) in self.words.iter().enumerate() {
+            let mask = self.valid_mask(w);
+            let mut bits = word.load(Ordering::Acquire);
+            loop {
+                let free = !bits & mask;
+                if free == 0 {
+                    break; // this word is full, try the next
+                }
+                let bit = 1u32 << free.trailing_zeros();
+                match word.compare_exchange_weak(
+                    bits,
+                    bits | bit,
+                    Ordering::AcqRel,
+                    Ordering::Acquire,
+                ) {
+                    Ok(_) => return Some(w * 32 + free.trailing_zeros() as usize),
+                    Err(current) => bits = current, // raced, retry this word
+                }
+            }
+        }
+        None
+    }
+
+    /// Mark `slot` free.
+    fn release(&self, slot: usize) {
+        self.words[slot / 32].fetch_and(!(1u32 << (slot % 32)), Ordering::Release);
+    }
+
+    /// Number of allocated slots.
+    fn count(&self) -> u32 {
+        self.words
+            .iter()
+            .map(|w| w.load(Ordering::Acquire).count_ones())
+            .sum()
+    }
+
+    /// Free every slot.
+    #[cfg(test)]
+    fn clear(&self) {
+        for word in &self.words {
+            word.store(0, Ordering::Release);
+        }
+    }
+}
+
 /// Global lock-free pool allocator for audio blocks.
 ///
-/// Uses an atomic bitmap to track which slots are allocated, and per-slot
-/// atomic reference counts for shared ownership. All operations are lock-free
-/// and ISR-safe.
+/// Uses an atomic bitmap (one `AtomicU32` per 32 slots) to track which
+/// slots are allocated, and per-slot atomic reference counts for shared
+/// ownership. All operations are lock-free and ISR-safe.
 pub struct AudioBlockPool {
-    /// Bitmap: bit N = 1 means slot N is allocated.
-    bitmap: AtomicU32,
+    /// Allocation bitmap, one bit per slot.
+    bitmap: SlotBitmap<POOL_WORDS>,
     /// Per-slot reference counts.
     refcounts: [AtomicU8; POOL_SIZE],
+    /// Most blocks allocated at once since the last reset.
+    high_water: AtomicU32,
+    /// Number of `alloc()` calls that found the pool exhausted.
+    alloc_failures: AtomicU32,
     /// Block storage.
     storage: UnsafeCell<[MaybeUninit<AudioBlockData>; POOL_SIZE]>,
 }
@@ -44,7 +132,9 @@ impl AudioBlockPool {
     const fn new() -> Self {
         const ZERO_REFCOUNT: AtomicU8 = AtomicU8::new(0);
         AudioBlockPool {
-            bitmap: AtomicU32::new(0),
+            bitmap: SlotBitmap::new(POOL_SIZE),
+            high_water: AtomicU32::new(0),
+            alloc_failures: AtomicU32::new(0),
             refcounts: [ZERO_REFCOUNT; POOL_SIZE],
             storage: UnsafeCell::new(unsafe {
                 MaybeUninit::<[MaybeUninit<AudioBlockData>; POOL_SIZE]>::zeroed().assume_init()
@@ -54,42 +144,24 @@ impl AudioBlockPool {
 
     /// Allocate a block from the pool. Returns the slot index, or `None` if full.
     ///
-    /// The returned slot has refcount = 1 and its data is zeroed.
+    /// The returned slot has refcount = 1 and its data is zeroed. Each
+    /// failure is counted (see [`alloc_failures()`](Self::alloc_failures)).
     pub fn alloc(&self) -> Option<u8> {
-        loop {
-            let bitmap = self.bitmap.load(Ordering::Acquire);
-            let free = !bitmap;
-            if free == 0 {
-                return None; // all slots allocated
-            }
-            let slot = free.trailing_zeros();
-            if slot >= POOL_SIZE as u32 {
-                return None;
-            }
-            let bit = 1u32 << slot;
-            // Try to claim this slot
-            match self.bitmap.compare_exchange_weak(
-                bitmap,
-                bitmap | bit,
-                Ordering::AcqRel,
-                Ordering::Acquire,
-            ) {
-                Ok(_) => {
-                    // Slot claimed — initialize it
-                    self.refcounts[slot as usize].store(1, Ordering::Release);
-                    // Zero the block data
-                    let storage = self.storage.get();
-                    // SAFETY: We just exclusively claimed this slot via the bitmap CAS.
-                    unsafe {
-                        let block_ptr =
-                            (*storage)[slot as usize].as_mut_ptr();
-                        (*block_ptr) = AudioBlockData::zeroed();
-                    }
-                    return Some(slot as u8);
-                }
-                Err(_) => continue, // another core/ISR raced us, retry
-            }
+        let Some(slot) = self.bitmap.claim() else {
+            self.alloc_failures.fetch_add(1, Ordering::Relaxed);
+            return None;
+        };
+        self.high_water.fetch_max(self.bitmap.count(), Ordering::Relaxed);
+        // Slot claimed — initialize it
+        self.refcounts[slot].store(1, Ordering::Release);
+        // Zero the block data
+        let storage = self.storage.get();
+        // SAFETY: We just exclusively claimed this slot via the bitmap CAS.
+        unsafe {
+            let block_ptr = (*storage)[slot].as_mut_ptr();
+            (*block_ptr) = AudioBlockData::zeroed();
         }
+        Some(slot as u8)
     }
 
     /// Increment the reference count for a slot (used by `AudioBlockRef::clone`).
@@ -111,8 +183,7 @@ impl AudioBlockPool {
         debug_assert!(old > 0, "dec_ref on slot with refcount 0");
         if old == 1 {
             // Refcount went from 1 to 0 — deallocate
-            let bit = 1u32 << (slot as u32);
-            self.bitmap.fetch_and(!bit, Ordering::Release);
+            self.bitmap.release(slot as usize);
         }
     }
 
@@ -132,13 +203,67 @@ impl AudioBlockPool {
 
     /// Return the number of currently allocated blocks.
     pub fn allocated_count(&self) -> u32 {
-        self.bitmap.load(Ordering::Acquire).count_ones()
+        self.bitmap.count()
+    }
+
+    /// Return the largest number of blocks allocated at once since the pool
+    /// (or the mark) was last reset.
+    ///
+    /// Run a graph through its busiest passages, then compare this against
+    /// `POOL_SIZE` to size the pool.
+    pub fn high_water_mark(&self) -> u32 {
+        self.high_water.load(Ordering::Relaxed)
+    }
+
+    /// Restart high-water tracking from the current allocation count.
+    pub fn reset_high_water_mark(&self) {
+        self.high_water.store(self.allocated_count(), Ordering::Relaxed);
+    }
+
+    /// Return how many allocations have failed because the pool was
+    /// exhausted since the counter was last reset.
+    ///
+    /// Each failure usually means some node skipped a block, so a count
+    /// that rises alongside audible drop-outs points at pool sizing.
+    pub fn alloc_failures(&self) -> u32 {
+        self.alloc_failures.load(Ordering::Relaxed)
+    }
+
+    /// Reset the allocation failure counter to zero.
+    pub fn reset_alloc_failures(&self) {
+        self.alloc_failures.store(0, Ordering::Relaxed);
+    }
+
+    /// Write the indices of currently allocated slots into `out`, lowest
+    /// first, and return how many slots are live.
+    ///
+    /// For leak debugging: when an accounting check fails, this names the
+    /// slots still held so their contents can be inspected via
+    /// [`data_ptr()`](Self::data_ptr). If `out` is shorter than the live
+    /// count, only the first `out.len()` indices are written. Available in
+    /// tests and debug builds only.
+    #[cfg(any(test, debug_assertions))]
+    pub fn live_slots(&self, out: &mut [u8]) -> usize {
+        let mut live = 0;
+        for (w, word) in self.bitmap.words.iter().enumerate() {
+            let mut bits = word.load(Ordering::Acquire);
+            while bits != 0 {
+                if let Some(dst) = out.get_mut(live) {
+                    *dst = (w * 32 + bits.trailing_zeros() as usize) as u8;
+                }
+                live += 1;
+                bits &= bits - 1;
+            }
+        }
+        live
     }
 
     /// Reset the pool to its initial state. For testing only.
     #[cfg(test)]
     pub fn reset(&self) {
-        self.bitmap.store(0, Ordering::Release);
+        self.bitmap.clear();
+        self.high_water.store(0, Ordering::Release);
+        self.alloc_failures.store(0, Ordering::Release);
         for rc in &self.refcounts {
             rc.store(0, Ordering::Release);
         }
@@ -229,4 +354,124 @@ mod tests {
         POOL.dec_ref(slot);
         assert_eq!(POOL.allocated_count(), 0); // now freed
     }
+
+    #[test]
+    fn live_slots_reports_allocated_indices() {
+        reset_pool();
+        let a = POOL.alloc().unwrap();
+        let b = POOL.alloc().unwrap();
+        let c = POOL.alloc().unwrap();
+        POOL.dec_ref(b);
+
+        let mut live = [0xFFu8; POOL_SIZE];
+        assert_eq!(POOL.live_slots(&mut live), 2);
+        assert_eq!(&live[..2], &[a.min(c), a.max(c)]);
+        assert_eq!(live[2], 0xFF, "nothing written past the live count");
+
+        // A short buffer is filled as far as it goes; the count is still exact
+        let mut one = [0u8; 1];
+        assert_eq!(POOL.live_slots(&mut one), 2);
+        assert_eq!(one[0], a.min(c));
+
+        POOL.dec_ref(a);
+        POOL.dec_ref(c);
+        assert_eq!(POOL.live_slots(&mut live), 0);
+    }
+
+    #[test]
+    fn bitmap_64_slots_exhaust_and_free() {
+        let bitmap = SlotBitmap::<2>::new(64);
+        for expected in 0..64 {
+            assert_eq!(bitmap.claim(), Some(expected));
+        }
+        assert_eq!(bitmap.claim(), None);
+        assert_eq!(bitmap.count(), 64);
+
+        // Free one slot in each word; both come back, lowest first
+        bitmap.release(40);
+        bitmap.release(7);
+        assert_eq!(bitmap.count(), 62);
+        assert_eq!(bitmap.claim(), Some(7));
+        assert_eq!(bitmap.claim(), Some(40));
+        assert_eq!(bitmap.claim(), None);
+
+        for slot in 0..64 {
+            bitmap.release(slot);
+        }
+        assert_eq!(bitmap.count(), 0);
+        assert_eq!(bitmap.claim(), Some(0));
+    }
+
+    #[test]
+    fn bitmap_partial_word_stops_at_len() {
+        // 40 slots across two words: the upper 24 bits of word 1 are unusable
+        let bitmap = SlotBitmap::<2>::new(40);
+        for _ in 0..40 {
+            assert!(bitmap.claim().is_some());
+        }
+        assert_eq!(bitmap.claim(), None);
+        assert_eq!(bitmap.count(), 40);
+    }
+
+    #[test]
+    fn pool_exhaust_and_free_all() {
+        reset_pool();
+        let mut slots = [0u8; POOL_SIZE];
+        for s in slots.iter_mut() {
+            *s = POOL.alloc().unwrap();
+        }
+        assert!(POOL.alloc().is_none());
+        assert_eq!(POOL.allocated_count() as usize, POOL_SIZE);
+
+        for &s in slots.iter() {
+            POOL.dec_ref(s);
+        }
+        assert_eq!(POOL.allocated_count(), 0);
+        let mut live = [0u8; 1];
+        assert_eq!(POOL.live_slots(&mut live), 0);
+    }
+
+    #[test]
+    fn high_water_mark_tracks_peak() {
+        reset_pool();
+        assert_eq!(POOL.high_water_mark(), 0);
+
+        let mut slots = [0u8; 5];
+        for s in slots.iter_mut() {
+            *s = POOL.alloc().unwrap();
+        }
+        for &s in slots.iter() {
+            POOL.dec_ref(s);
+        }
+        for s in slots[..3].iter_mut() {
+            *s = POOL.alloc().unwrap();
+        }
+        assert_eq!(POOL.allocated_count(), 3);
+        assert_eq!(POOL.high_water_mark(), 5);
+
+        // Restarting tracking begins from what is live now
+        POOL.reset_high_water_mark();
+        assert_eq!(POOL.high_water_mark(), 3);
+        POOL.dec_ref(slots[0]);
+        let _ = POOL.alloc().unwrap();
+        assert_eq!(POOL.high_water_mark(), 3);
+    }
+
+    #[test]
+    fn alloc_failures_counted() {
+        reset_pool();
+        for _ in 0..POOL_SIZE {
+            POOL.alloc().unwrap();
+        }
+        assert_eq!(POOL.alloc_failures(), 0);
+        for _ in 0..4 {
+            assert!(POOL.alloc().is_none());
+        }
+        assert_eq!(POOL.alloc_failures(), 4);
+
+        POOL.reset_alloc_failures();
+        assert_eq!(POOL.alloc_failures(), 0);
+        assert!(POOL.alloc().is_none());
+        assert_eq!(POOL.alloc_failures(), 1);
+    }
 }
@@ -37,11 +37,41 @@ impl AudioBlockMut {
         self.slot
     }
 
+    /// Borrow the samples as a fixed-size array.
+    ///
+    /// Same as deref, but spelled out for bulk copies and array methods
+    /// where the known length lets the optimizer unroll or vectorize.
+    pub fn as_array(&self) -> &[i16; AUDIO_BLOCK_SAMPLES] {
+        self
+    }
+
+    /// Mutably borrow the samples as a fixed-size array.
+    ///
+    /// Exclusive access is guaranteed by this handle being unique.
+    pub fn as_mut_array(&mut self) -> &mut [i16; AUDIO_BLOCK_SAMPLES] {
+        self
+    }
+
     /// Allocate a new audio block from the global pool.
-    /// Returns `None` if the pool is exhausted.
+    /// Returns `None` if the pool is exhausted; each such failure is counted
+    /// by [`alloc_failures()`](Self::alloc_failures).
     pub fn alloc() -> Option<Self> {
         POOL.alloc().map(AudioBlockMut::new)
     }
+
+    /// Number of block allocations that failed because the global pool was
+    /// exhausted, since the last [`reset_alloc_failures()`](Self::reset_alloc_failures).
+    ///
+    /// Poll from the control loop to correlate glitches with pool
+    /// exhaustion.
+    pub fn alloc_failures() -> u32 {
+        POOL.alloc_failures()
+    }
+
+    /// Reset the global pool's allocation failure counter.
+    pub fn reset_alloc_failures() {
+        POOL.reset_alloc_failures();
+    }
 }
 
 impl Deref for AudioBlockMut {
@@ -82,21 +112,49 @@ impl AudioBlockRef {
         self.slot
     }
 
+    /// Borrow the samples as a fixed-size array.
+    ///
+    /// Shared blocks may have other readers, so only `&` access is given;
+    /// use [`into_mut()`](Self::into_mut) to write.
+    pub fn as_array(&self) -> &[i16; AUDIO_BLOCK_SAMPLES] {
+        self
+    }
+
     /// Try to convert back to an exclusive mutable reference.
     ///
     /// - If this is the only reference (refcount == 1), converts in place (no copy).
     /// - If there are other references, allocates a new block, copies the data,
     ///   and returns the new exclusive block. Returns `None` if the pool is exhausted.
     pub fn into_mut(self) -> Option<AudioBlockMut> {
+        self.into_mut_or_clone().ok()
+    }
+
+    /// Copy-on-write conversion to an exclusive block, for effects that
+    /// process their input in place.
+    ///
+    /// Like [`into_mut()`](Self::into_mut): a sole reference is reused
+    /// without allocating, a fanned-out one is copied into a fresh block.
+    /// If that copy can't be allocated the original block is handed back in
+    /// `Err`, so the node can still pass its input through unprocessed.
+    ///
+    /// ```ignore
+    /// let block = match input.into_mut_or_clone() {
+    ///     Ok(mut block) => { process(&mut block); block.into_shared() }
+    ///     Err(unprocessed) => unprocessed,
+    /// };
+    /// ```
+    pub fn into_mut_or_clone(self) -> Result<AudioBlockMut, AudioBlockRef> {
         let refcount = POOL.refcount(self.slot);
         if refcount == 1 {
             // We're the sole owner — convert in place
             let slot = self.slot;
             core::mem::forget(self);
-            Some(AudioBlockMut::new(slot))
+            Ok(AudioBlockMut::new(slot))
         } else {
             // Clone-on-write: allocate a new block and copy
-            let new_slot = POOL.alloc()?;
+            let Some(new_slot) = POOL.alloc() else {
+                return Err(self);
+            };
             unsafe {
                 let src = &(*POOL.data_ptr(self.slot)).samples;
                 let dst = &mut (*POOL.data_ptr(new_slot)).samples;
@@ -104,9 +162,22 @@ impl AudioBlockRef {
             }
             // Drop self (decrements refcount on old slot)
             drop(self);
-            Some(AudioBlockMut::new(new_slot))
+            Ok(AudioBlockMut::new(new_slot))
         }
     }
+
+    /// Returns `true` if every sample in the block is zero.
+    ///
+    /// Lets effect nodes skip processing for silent input. Scans the block
+    /// as 64 `u32` words rather than 128 `i16` samples.
+    pub fn is_silent(&self) -> bool {
+        // SAFETY: `AudioBlockData` is `repr(C, align(4))` and holds exactly
+        // `AUDIO_BLOCK_SAMPLES` i16s, so it is valid to view as u32 words.
+        let words = unsafe {
+            &*(POOL.data_ptr(self.slot) as *const [u32; AUDIO_BLOCK_SAMPLES / 2])
+        };
+        words.iter().all(|&w| w == 0)
+    }
 }
 
 impl Deref for AudioBlockRef {
@@ -231,4 +302,104 @@ mod tests {
         assert_eq!(shared2[0], 55);
         assert_eq!(POOL.refcount(slot), 1); // old slot refcount decremented
     }
+
+    #[test]
+    fn is_silent_zeroed_block() {
+        reset_pool();
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block.fill(0);
+        assert!(block.into_shared().is_silent());
+    }
+
+    #[test]
+    fn is_silent_detects_single_sample() {
+        reset_pool();
+        // Both halves of the first and last words.
+        for &index in &[0usize, 1, 64, 126, 127] {
+            let mut block = AudioBlockMut::alloc().unwrap();
+            block.fill(0);
+            block[index] = -1;
+            assert!(!block.into_shared().is_silent(), "missed sample {}", index);
+        }
+    }
+
+    #[test]
+    fn alloc_failures_seen_through_block_alloc() {
+        reset_pool();
+        let mut _held = [const { None }; crate::constants::POOL_SIZE];
+        for slot in _held.iter_mut() {
+            *slot = AudioBlockMut::alloc();
+        }
+        assert!(AudioBlockMut::alloc().is_none());
+        assert!(AudioBlockMut::alloc().is_none());
+        assert_eq!(AudioBlockMut::alloc_failures(), 2);
+        AudioBlockMut::reset_alloc_failures();
+        assert_eq!(AudioBlockMut::alloc_failures(), 0);
+    }
+
+    #[test]
+    fn array_accessors_copy_between_blocks() {
+        reset_pool();
+        let mut src = AudioBlockMut::alloc().unwrap();
+        for (i, s) in src.as_mut_array().iter_mut().enumerate() {
+            *s = i as i16 * 3 - 100;
+        }
+        let src = src.into_shared();
+        let alias = src.clone();
+
+        let mut dst = AudioBlockMut::alloc().unwrap();
+        *dst.as_mut_array() = *src.as_array();
+        assert_eq!(dst.as_array(), alias.as_array());
+        assert_eq!(dst[127], 127 * 3 - 100);
+        assert_eq!(POOL.refcount(src.slot()), 2, "borrowing doesn't touch refcounts");
+    }
+
+    #[test]
+    fn into_mut_or_clone_sole_owner_reuses_slot() {
+        reset_pool();
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block[3] = 1234;
+        let slot = block.slot();
+        let shared = block.into_shared();
+
+        let mut exclusive = shared.into_mut_or_clone().unwrap();
+        assert_eq!(exclusive.slot(), slot);
+        assert_eq!(POOL.allocated_count(), 1, "no allocation for a sole owner");
+        exclusive[3] += 1;
+        assert_eq!(exclusive[3], 1235);
+    }
+
+    #[test]
+    fn into_mut_or_clone_shared_copies() {
+        reset_pool();
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block.fill(500);
+        let shared = block.into_shared();
+        let other = shared.clone();
+
+        let mut exclusive = shared.into_mut_or_clone().unwrap();
+        assert_ne!(exclusive.slot(), other.slot());
+        assert_eq!(POOL.allocated_count(), 2);
+        assert_eq!(POOL.refcount(other.slot()), 1);
+        exclusive.fill(-500);
+        assert!(other.iter().all(|&s| s == 500), "original left intact");
+    }
+
+    #[test]
+    fn into_mut_or_clone_exhausted_returns_original() {
+        reset_pool();
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block[0] = 42;
+        let shared = block.into_shared();
+        let other = shared.clone();
+        let mut _held = [const { None }; crate::constants::POOL_SIZE - 1];
+        for slot in _held.iter_mut() {
+            *slot = AudioBlockMut::alloc();
+        }
+
+        let back = shared.into_mut_or_clone().unwrap_err();
+        assert_eq!(back.slot(), other.slot());
+        assert_eq!(back[0], 42);
+        assert_eq!(POOL.refcount(other.slot()), 2, "no reference lost");
+    }
 }
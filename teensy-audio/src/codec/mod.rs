@@ -1,13 +1,31 @@
-//! SGTL5000 audio codec driver module.
+//! Audio codec driver module.
 //!
-//! Provides a full-featured driver for the NXP SGTL5000 codec found on the
-//! Teensy Audio Shield. Ported from the C++ `AudioControlSGTL5000` class.
+//! Provides register-level I2C drivers for the codecs used with Teensy:
 //!
-//! # Feature gate
+//! - `Sgtl5000` — the NXP SGTL5000 found on the Teensy Audio Shield.
+//!   Ported from the C++ `AudioControlSGTL5000` class.
+//! - `Wm8731` — the Cirrus Logic (Wolfson) WM8731. Ported from the C++
+//!   `AudioControlWM8731` class.
 //!
-//! This module is available when the `sgtl5000` feature is enabled (on by default).
+//! Both implement [`AudioControl`](crate::control::AudioControl), so code
+//! that only needs enable/disable/volume can be generic over the codec.
+//!
+//! # Feature gates
+//!
+//! Each driver has its own feature: `sgtl5000` (on by default) and `wm8731`.
 
+#[cfg(feature = "sgtl5000")]
 pub(crate) mod registers;
+#[cfg(feature = "sgtl5000")]
 mod sgtl5000;
+#[cfg(feature = "wm8731")]
+pub(crate) mod wm8731_registers;
+#[cfg(feature = "wm8731")]
+mod wm8731;
 
-pub use sgtl5000::{EqMode, HeadphoneSource, Input, Sgtl5000};
+#[cfg(feature = "sgtl5000")]
+pub use sgtl5000::{
+    EqMode, Headphone, HeadphoneSource, Input, LineOut, Sgtl5000, Sgtl5000Error, Sgtl5000Result,
+};
+#[cfg(feature = "wm8731")]
+pub use wm8731::{Wm8731, Wm8731Input};
@@ -4,7 +4,7 @@
 //! NXP SGTL5000 datasheet. Register addresses are 16-bit; all registers hold
 //! 16-bit values. I2C protocol uses big-endian byte order.
 
-// Some registers are defined for completeness (AVC, status, test, etc.)
+// Some registers are defined for completeness (status, test, etc.)
 // but are not yet used by the driver.
 #![allow(dead_code)]
 
@@ -23,6 +23,9 @@ pub const I2C_ADDR_CS_HIGH: u8 = 0x2A;
 /// - Bits  7:0 — REVID
 pub const CHIP_ID: u16 = 0x0000;
 
+/// PARTID value read from [`CHIP_ID`] bits 15:8 on an SGTL5000.
+pub const CHIP_ID_PARTID: u8 = 0xA0;
+
 // ── Digital power ──────────────────────────────────────────────────────────
 
 /// Digital block power control.
@@ -200,6 +203,9 @@ pub const CHIP_SHORT_CTRL: u16 = 0x003C;
 
 // ── Digital Audio Processor (DAP) ──────────────────────────────────────────
 
+// The DAP has no peak-meter or level registers: signal levels cannot be read
+// back from the chip, only the control registers below.
+
 /// DAP master enable.
 pub const DAP_CONTROL: u16 = 0x0100;
 
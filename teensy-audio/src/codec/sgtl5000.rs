@@ -54,8 +54,39 @@ pub enum EqMode {
     GraphicEq = 3,
 }
 
+// ── Errors ─────────────────────────────────────────────────────────────────
+
+/// Errors returned by the higher-level SGTL5000 driver methods.
+///
+/// Wraps the underlying I2C bus error and adds driver-level failures: a
+/// missing chip or out-of-range arguments.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Sgtl5000Error<E> {
+    /// The I2C bus transaction failed.
+    I2c(E),
+    /// The chip answered, but its `CHIP_ID` is not an SGTL5000's.
+    NotPresent,
+    /// An argument was outside its documented range.
+    InvalidParameter,
+}
+
+/// Result of a driver method that can fail with a [`Sgtl5000Error`].
+///
+/// Bus errors convert into it with `?`, so application code can use this
+/// as its own return type around both kinds of driver method.
+pub type Sgtl5000Result<T, E> = Result<T, Sgtl5000Error<E>>;
+
+impl<E> From<E> for Sgtl5000Error<E> {
+    fn from(err: E) -> Self {
+        Sgtl5000Error::I2c(err)
+    }
+}
+
 // ── Driver struct ──────────────────────────────────────────────────────────
 
+/// CHIP_ANA_POWER value in PLL mode: normal power-up plus PLL and VCO amp.
+const ANA_POWER_PLL: u16 = 0x40FF | (1 << 10) | (1 << 8);
+
 /// SGTL5000 audio codec driver.
 ///
 /// Generic over I2C bus and delay provider. The delay is used only during
@@ -66,6 +97,12 @@ pub struct Sgtl5000<I2C, D> {
     address: u8,
     /// Cached CHIP_ANA_CTRL value for fast mute/select operations.
     ana_ctrl: u16,
+    /// Cached CHIP_ANA_HP_CTRL value, restored by [`reinit()`](Self::reinit).
+    hp_ctrl: u16,
+    /// Whether a power-on sequence has completed.
+    enabled: bool,
+    /// `(ext_mclk, pll_freq)` if enabled in PLL master mode.
+    pll: Option<(u32, u32)>,
     /// Whether headphone output is currently muted.
     muted: bool,
     /// Whether the driver auto-configures DAP/EQ modes.
@@ -90,6 +127,9 @@ where
             delay,
             address: Self::DEFAULT_ADDRESS,
             ana_ctrl: 0,
+            hp_ctrl: 0,
+            enabled: false,
+            pll: None,
             muted: true,
             semi_automated: false,
         }
@@ -102,6 +142,9 @@ where
             delay,
             address,
             ana_ctrl: 0,
+            hp_ctrl: 0,
+            enabled: false,
+            pll: None,
             muted: true,
             semi_automated: false,
         }
@@ -114,6 +157,8 @@ where
         // Cache writes to ANA_CTRL for fast read-modify-write
         if register == reg::CHIP_ANA_CTRL {
             self.ana_ctrl = value;
+        } else if register == reg::CHIP_ANA_HP_CTRL {
+            self.hp_ctrl = value;
         }
         let buf = [
             (register >> 8) as u8,
@@ -140,6 +185,26 @@ where
         Ok(new_val)
     }
 
+    // ── Chip identification ────────────────────────────────────────────
+
+    /// Read the `CHIP_ID` register, returning `(part_id, rev_id)`.
+    ///
+    /// An SGTL5000 reports a part ID of `0xA0`.
+    pub fn chip_id(&mut self) -> Result<(u8, u8), I2C::Error> {
+        let id = self.read_register(reg::CHIP_ID)?;
+        Ok(((id >> 8) as u8, id as u8))
+    }
+
+    /// Check that an SGTL5000 answers at the configured address.
+    ///
+    /// Cheap enough to call before [`enable()`](Self::enable) to fail fast
+    /// instead of waiting out the power-up ramp. A missing chip usually
+    /// NAKs, which surfaces as the bus error.
+    pub fn is_present(&mut self) -> Result<bool, I2C::Error> {
+        let (part_id, _) = self.chip_id()?;
+        Ok(part_id == reg::CHIP_ID_PARTID)
+    }
+
     // ── Power-on sequence ──────────────────────────────────────────────
 
     /// Full power-on sequence for I2S slave mode at 44.1 kHz.
@@ -152,8 +217,20 @@ where
     /// - Headphone volume at minimum (call [`volume()`](Self::volume) to unmute)
     ///
     /// Includes a 400 ms delay for the analog power ramp.
-    pub fn enable(&mut self) -> Result<(), I2C::Error> {
+    ///
+    /// Returns [`Sgtl5000Error::NotPresent`] without writing anything if
+    /// the chip ID doesn't match (see [`is_present()`](Self::is_present)).
+    pub fn enable(&mut self) -> Result<(), Sgtl5000Error<I2C::Error>> {
         self.delay.delay_ms(5);
+        if !self.is_present()? {
+            return Err(Sgtl5000Error::NotPresent);
+        }
+        self.power_up()?;
+        Ok(())
+    }
+
+    /// Full slave-mode power-on sequence used by [`enable()`](Self::enable).
+    fn power_up(&mut self) -> Result<(), I2C::Error> {
         self.muted = true;
 
         // VDDD is externally driven with 1.8V
@@ -195,6 +272,8 @@ where
         self.write_register(reg::CHIP_ANA_CTRL, 0x0036)?;
 
         self.semi_automated = true;
+        self.enabled = true;
+        self.pll = None;
         Ok(())
     }
 
@@ -204,21 +283,34 @@ where
     ///
     /// * `ext_mclk` — External MCLK frequency in Hz.
     /// * `pll_freq` — Desired PLL output frequency, typically `4096 × Fs`.
+    ///
+    /// Returns [`Sgtl5000Error::NotPresent`] like [`enable()`](Self::enable).
     pub fn enable_with_pll(
         &mut self,
         ext_mclk: u32,
         pll_freq: u32,
-    ) -> Result<(), I2C::Error> {
+    ) -> Result<(), Sgtl5000Error<I2C::Error>> {
         self.delay.delay_ms(5);
+        if !self.is_present()? {
+            return Err(Sgtl5000Error::NotPresent);
+        }
 
         // Check if already initialized (recovery from Teensy reset)
         let i2s_ctrl = self.read_register(reg::CHIP_I2S_CTRL)?;
         if i2s_ctrl == (0x0030 | (1 << 7)) {
             self.muted = false;
             self.semi_automated = true;
+            self.enabled = true;
+            self.pll = Some((ext_mclk, pll_freq));
             return Ok(());
         }
 
+        self.power_up_with_pll(ext_mclk, pll_freq)?;
+        Ok(())
+    }
+
+    /// Full PLL power-on sequence used by [`enable_with_pll()`](Self::enable_with_pll).
+    fn power_up_with_pll(&mut self, ext_mclk: u32, pll_freq: u32) -> Result<(), I2C::Error> {
         self.muted = true;
 
         self.write_register(reg::CHIP_ANA_POWER, 0x4060)?;
@@ -228,24 +320,10 @@ where
         self.write_register(reg::CHIP_SHORT_CTRL, 0x4446)?;
         self.write_register(reg::CHIP_ANA_CTRL, 0x0137)?;
 
-        // Divide MCLK by 2 if above 17 MHz
-        if ext_mclk > 17_000_000 {
-            self.write_register(reg::CHIP_CLK_TOP_CTRL, 1)?;
-        } else {
-            self.write_register(reg::CHIP_CLK_TOP_CTRL, 0)?;
-        }
-
-        // Configure PLL dividers
-        let int_divisor = (pll_freq / ext_mclk) & 0x1F;
-        let frac_part = (pll_freq as f32 / ext_mclk as f32) - int_divisor as f32;
-        let frac_divisor = (frac_part * 2048.0) as u32 & 0x7FF;
-        self.write_register(
-            reg::CHIP_PLL_CTRL,
-            ((int_divisor << 11) | frac_divisor) as u16,
-        )?;
+        self.configure_pll(ext_mclk, pll_freq)?;
 
         // Power up with PLL and VCO amp enabled
-        self.write_register(reg::CHIP_ANA_POWER, 0x40FF | (1 << 10) | (1 << 8))?;
+        self.write_register(reg::CHIP_ANA_POWER, ANA_POWER_PLL)?;
         self.write_register(reg::CHIP_DIG_POWER, 0x0073)?;
 
         self.delay.delay_ms(400);
@@ -263,9 +341,94 @@ where
         self.write_register(reg::CHIP_ANA_CTRL, 0x0036)?;
 
         self.semi_automated = true;
+        self.enabled = true;
+        self.pll = Some((ext_mclk, pll_freq));
         Ok(())
     }
 
+    /// Write the MCLK divider and PLL dividers for PLL master mode.
+    fn configure_pll(&mut self, ext_mclk: u32, pll_freq: u32) -> Result<(), I2C::Error> {
+        // Divide MCLK by 2 if above 17 MHz
+        if ext_mclk > 17_000_000 {
+            self.write_register(reg::CHIP_CLK_TOP_CTRL, 1)?;
+        } else {
+            self.write_register(reg::CHIP_CLK_TOP_CTRL, 0)?;
+        }
+
+        // Configure PLL dividers
+        let int_divisor = (pll_freq / ext_mclk) & 0x1F;
+        let frac_part = (pll_freq as f32 / ext_mclk as f32) - int_divisor as f32;
+        let frac_divisor = (frac_part * 2048.0) as u32 & 0x7FF;
+        self.write_register(
+            reg::CHIP_PLL_CTRL,
+            ((int_divisor << 11) | frac_divisor) as u16,
+        )
+    }
+
+    // ── Recovery ───────────────────────────────────────────────────────
+
+    /// Restore the codec configuration after a brownout or bus glitch.
+    ///
+    /// Reads back `CHIP_I2S_CTRL` and `CHIP_DIG_POWER`. If both still hold
+    /// the values written at power-on, nothing is written and `Ok(false)` is
+    /// returned. Otherwise the configuration is re-applied and `Ok(true)` is
+    /// returned:
+    ///
+    /// - If `CHIP_ANA_POWER` shows the analog supplies still up, only the
+    ///   clocking, I2S format, routing and volume registers are rewritten,
+    ///   skipping the 400 ms analog ramp.
+    /// - Otherwise the full power-on sequence for the mode originally used
+    ///   ([`enable()`](Self::enable) or [`enable_with_pll()`](Self::enable_with_pll))
+    ///   runs again.
+    ///
+    /// In both cases headphone volume and the mute/input selection are
+    /// restored from the driver's cached values. Line-out level, DAC volume
+    /// and DAP settings return to their power-on defaults.
+    ///
+    /// Does nothing if the codec has not been enabled.
+    pub fn reinit(&mut self) -> Result<bool, I2C::Error> {
+        if !self.enabled {
+            return Ok(false);
+        }
+
+        let master = self.pll.is_some();
+        let i2s_expected = if master { 0x0030 | (1 << 7) } else { 0x0030 };
+        let i2s_ctrl = self.read_register(reg::CHIP_I2S_CTRL)?;
+        let dig_power = self.read_register(reg::CHIP_DIG_POWER)?;
+        if i2s_ctrl == i2s_expected && dig_power == 0x0073 {
+            return Ok(false);
+        }
+
+        let (hp_ctrl, ana_ctrl, muted) = (self.hp_ctrl, self.ana_ctrl, self.muted);
+        let ana_expected = if master { ANA_POWER_PLL } else { 0x40FF };
+        let ana_power = self.read_register(reg::CHIP_ANA_POWER)?;
+
+        if ana_power != ana_expected {
+            // Analog side lost power too: full sequence including the ramp
+            match self.pll {
+                Some((ext_mclk, pll_freq)) => self.power_up_with_pll(ext_mclk, pll_freq)?,
+                None => self.power_up()?,
+            }
+        } else {
+            if let Some((ext_mclk, pll_freq)) = self.pll {
+                self.configure_pll(ext_mclk, pll_freq)?;
+            }
+            self.write_register(reg::CHIP_DIG_POWER, 0x0073)?;
+            self.write_register(reg::CHIP_LINE_OUT_VOL, 0x1D1D)?;
+            let clk_ctrl = if master { 0x0004 | 0x03 } else { 0x0004 };
+            self.write_register(reg::CHIP_CLK_CTRL, clk_ctrl)?;
+            self.write_register(reg::CHIP_I2S_CTRL, i2s_expected)?;
+            self.write_register(reg::CHIP_SSS_CTRL, 0x0010)?;
+            self.write_register(reg::CHIP_ADCDAC_CTRL, 0x0000)?;
+            self.write_register(reg::CHIP_DAC_VOL, 0x3C3C)?;
+        }
+
+        self.write_register(reg::CHIP_ANA_HP_CTRL, hp_ctrl)?;
+        self.write_register(reg::CHIP_ANA_CTRL, ana_ctrl)?;
+        self.muted = muted;
+        Ok(true)
+    }
+
     /// Disable the codec (no-op, matching C++ behaviour).
     pub fn disable(&mut self) -> Result<(), I2C::Error> {
         Ok(())
@@ -474,6 +637,10 @@ where
 
     // ── Digital Audio Processor (DAP) ──────────────────────────────────
 
+    // The SGTL5000 exposes no peak or level readback (the DAP and ADC
+    // blocks have control registers only). For metering, put an
+    // `AudioAnalyzePeak` or `AudioAnalyzeRms` node in the graph.
+
     /// Enable audio pre-processing (analog input → DAP → Teensy).
     pub fn audio_pre_processor_enable(&mut self) -> Result<(), I2C::Error> {
         self.write_register(reg::DAP_CONTROL, 1)?;
@@ -535,6 +702,37 @@ where
         self.dap_audio_eq_band(4, treble)
     }
 
+    /// Set all 5 graphic EQ bands from an array, validating each level.
+    ///
+    /// Bands are ordered as in [`eq_band()`](Self::eq_band). Every level must
+    /// lie within −1.0 to +1.0; otherwise nothing is written and
+    /// [`Sgtl5000Error::InvalidParameter`] is returned.
+    pub fn set_graphic_eq(
+        &mut self,
+        bands: &[f32; 5],
+    ) -> Result<(), Sgtl5000Error<I2C::Error>> {
+        if bands.iter().any(|b| !(-1.0..=1.0).contains(b)) {
+            return Err(Sgtl5000Error::InvalidParameter);
+        }
+        self.eq_bands_5(bands[0], bands[1], bands[2], bands[3], bands[4])?;
+        Ok(())
+    }
+
+    /// Read back the 5 graphic EQ band levels (each −1.0 to +1.0).
+    ///
+    /// Levels are reconstructed from the band registers, so they match the
+    /// values passed to [`set_graphic_eq()`](Self::set_graphic_eq) to within
+    /// one register step (1/48).
+    pub fn read_graphic_eq(&mut self) -> Result<[f32; 5], Sgtl5000Error<I2C::Error>> {
+        let mut levels = [0.0f32; 5];
+        for (band, level) in levels.iter_mut().enumerate() {
+            let addr = reg::DAP_AUDIO_EQ_BASS_BAND0 + (band as u16) * 2;
+            let n = self.read_register(addr)? & 127;
+            *level = (n as f32 - 47.0) / 48.0;
+        }
+        Ok(levels)
+    }
+
     /// Set bass and treble (2-band tone control, each −1.0 to +1.0).
     pub fn eq_bands_2(&mut self, bass: f32, treble: f32) -> Result<(), I2C::Error> {
         if self.semi_automated {
@@ -639,6 +837,32 @@ where
         self.enhance_bass(lr_level, bass_level)
     }
 
+    /// Read back the bass enhancement levels as `(lr_level, bass_level)`,
+    /// each 0.0–1.0 as passed to [`enhance_bass()`](Self::enhance_bass).
+    pub fn read_enhance_bass(&mut self) -> Result<(f32, f32), I2C::Error> {
+        let ctrl = self.read_register(reg::DAP_BASS_ENHANCE_CTRL)?;
+        let lr = 0x3F - ((ctrl >> 8) & 0x3F);
+        let bass = 0x7F - (ctrl & 0x7F);
+        Ok((lr as f32 / 0x3F as f32, bass as f32 / 0x7F as f32))
+    }
+
+    /// Boost bass by `db` decibels and enable bass enhancement.
+    ///
+    /// Sets the harmonic bass level, which the chip encodes as +6.0 dB at
+    /// 0x00 falling in 0.5 dB steps to −57.5 dB at 0x7F; `db` is rounded to
+    /// the nearest step. The left/right mix level is left unchanged. Levels
+    /// outside −57.5 to +6.0 dB return [`Sgtl5000Error::InvalidParameter`]
+    /// without touching the chip.
+    pub fn bass_boost_db(&mut self, db: f32) -> Result<(), Sgtl5000Error<I2C::Error>> {
+        if !(-57.5..=6.0).contains(&db) {
+            return Err(Sgtl5000Error::InvalidParameter);
+        }
+        let bass = libm::roundf((6.0 - db) * 2.0) as u16;
+        self.modify(reg::DAP_BASS_ENHANCE_CTRL, bass, 0x7F)?;
+        self.enhance_bass_enable()?;
+        Ok(())
+    }
+
     /// Enable bass enhancement.
     pub fn enhance_bass_enable(&mut self) -> Result<(), I2C::Error> {
         self.modify(reg::DAP_BASS_ENHANCE, 1, 1)?;
@@ -651,6 +875,95 @@ where
         Ok(())
     }
 
+    // ── Auto volume control (AVC) ──────────────────────────────────────
+
+    /// Configure the DAP auto volume control (compressor/limiter).
+    ///
+    /// - `max_gain`: 0 = 0 dB, 1 = +6 dB, 2 = +12 dB (clamped to 2)
+    /// - `lbi_response`: integrator response, 0 = 0 ms … 3 = 400 ms
+    /// - `hard_limit`: 1 = hard limiter, 0 = soft knee
+    /// - `threshold_db`: target level in dBFS (e.g. −18.0)
+    /// - `attack_db_s`, `decay_db_s`: gain change rates in dB/s
+    ///
+    /// AVC runs in the DAP, so audio must be routed through it with
+    /// [`audio_post_processor_enable()`](Self::audio_post_processor_enable).
+    /// While the driver manages the DAP (see
+    /// [`kill_automation()`](Self::kill_automation)) that routing is set up
+    /// here if the DAP is off. Call
+    /// [`auto_volume_enable()`](Self::auto_volume_enable) to start it.
+    ///
+    /// The register values use the C++ `autoVolumeControl()` formulas but
+    /// are not truncated to 8 bits as the C++ `uint8_t` locals are.
+    pub fn auto_volume(
+        &mut self,
+        max_gain: u8,
+        lbi_response: u8,
+        hard_limit: u8,
+        threshold_db: f32,
+        attack_db_s: f32,
+        decay_db_s: f32,
+    ) -> Result<(), I2C::Error> {
+        if self.semi_automated && self.read_register(reg::DAP_CONTROL)? & 1 == 0 {
+            self.audio_post_processor_enable()?;
+        }
+        let max_gain = max_gain.min(2) as u16;
+        let lbi_response = (lbi_response & 3) as u16;
+        let hard_limit = (hard_limit & 1) as u16;
+
+        let thresh = libm::powf(10.0, threshold_db / 20.0) * 0.636 * 32768.0;
+        let attack = Self::avc_rate(attack_db_s) * (1 << 19) as f32;
+        let decay = Self::avc_rate(decay_db_s) * (1 << 23) as f32;
+        self.write_register(reg::DAP_AVC_THRESHOLD, thresh as u16)?;
+        self.write_register(reg::DAP_AVC_ATTACK, attack as u16)?;
+        self.write_register(reg::DAP_AVC_DECAY, decay as u16)?;
+        self.modify(
+            reg::DAP_AVC_CTRL,
+            (max_gain << 12) | (lbi_response << 8) | (hard_limit << 5),
+            (3 << 12) | (3 << 8) | (1 << 5),
+        )?;
+        Ok(())
+    }
+
+    /// Enable auto volume control.
+    pub fn auto_volume_enable(&mut self) -> Result<(), I2C::Error> {
+        self.modify(reg::DAP_AVC_CTRL, 1, 1)?;
+        Ok(())
+    }
+
+    /// Disable auto volume control.
+    pub fn auto_volume_disable(&mut self) -> Result<(), I2C::Error> {
+        self.modify(reg::DAP_AVC_CTRL, 0, 1)?;
+        Ok(())
+    }
+
+    /// Per-sample gain step for a rate in dB/s: `1 − 10^(−rate / (20·Fs))`.
+    ///
+    /// Computed with `expm1` because the step is tiny (~1e−6) and the
+    /// direct form loses it to `f32` rounding.
+    fn avc_rate(db_per_s: f32) -> f32 {
+        -libm::expm1f(-db_per_s / (20.0 * 44100.0) * core::f32::consts::LN_10)
+    }
+
+    // ── Per-output handles ─────────────────────────────────────────────
+
+    /// Volume and mute controls for the headphone output.
+    ///
+    /// ```ignore
+    /// codec.headphone().volume(0.5)?;
+    /// ```
+    pub fn headphone(&mut self) -> Headphone<'_, I2C, D> {
+        Headphone { codec: self }
+    }
+
+    /// Level and mute controls for the line output.
+    ///
+    /// ```ignore
+    /// codec.line_out().mute()?;
+    /// ```
+    pub fn line_out(&mut self) -> LineOut<'_, I2C, D> {
+        LineOut { codec: self }
+    }
+
     // ── Automation control ─────────────────────────────────────────────
 
     /// Stop automatic DAP/EQ mode management.
@@ -727,6 +1040,74 @@ where
     }
 }
 
+// ── Per-output handles ─────────────────────────────────────────────────────
+
+/// Headphone output controls, borrowed from [`Sgtl5000::headphone()`].
+///
+/// Each method forwards to the matching flat driver method.
+pub struct Headphone<'a, I2C, D> {
+    codec: &'a mut Sgtl5000<I2C, D>,
+}
+
+impl<I2C, D> Headphone<'_, I2C, D>
+where
+    I2C: I2c,
+    D: DelayNs,
+{
+    /// Set volume (0.0 = muted, 1.0 = +12 dB). See [`Sgtl5000::volume()`].
+    pub fn volume(&mut self, level: f32) -> Result<(), I2C::Error> {
+        self.codec.volume(level)
+    }
+
+    /// Set left/right volume independently. See [`Sgtl5000::volume_lr()`].
+    pub fn volume_lr(&mut self, left: f32, right: f32) -> Result<(), I2C::Error> {
+        self.codec.volume_lr(left, right)
+    }
+
+    /// Mute the headphone amplifier.
+    pub fn mute(&mut self) -> Result<(), I2C::Error> {
+        self.codec.mute_headphone()
+    }
+
+    /// Unmute the headphone amplifier.
+    pub fn unmute(&mut self) -> Result<(), I2C::Error> {
+        self.codec.unmute_headphone()
+    }
+
+    /// Select what feeds the headphones. See [`Sgtl5000::headphone_select()`].
+    pub fn select(&mut self, source: HeadphoneSource) -> Result<(), I2C::Error> {
+        self.codec.headphone_select(source)
+    }
+}
+
+/// Line output controls, borrowed from [`Sgtl5000::line_out()`].
+///
+/// Each method forwards to the matching flat driver method.
+pub struct LineOut<'a, I2C, D> {
+    codec: &'a mut Sgtl5000<I2C, D>,
+}
+
+impl<I2C, D> LineOut<'_, I2C, D>
+where
+    I2C: I2c,
+    D: DelayNs,
+{
+    /// Set output level (13–31 per channel). See [`Sgtl5000::line_out_level()`].
+    pub fn level(&mut self, left: u8, right: u8) -> Result<(), I2C::Error> {
+        self.codec.line_out_level(left, right)
+    }
+
+    /// Mute the line output.
+    pub fn mute(&mut self) -> Result<(), I2C::Error> {
+        self.codec.mute_lineout()
+    }
+
+    /// Unmute the line output.
+    pub fn unmute(&mut self) -> Result<(), I2C::Error> {
+        self.codec.unmute_lineout()
+    }
+}
+
 // ── AudioControl trait implementation ──────────────────────────────────────
 
 impl<I2C, D> AudioControl for Sgtl5000<I2C, D>
@@ -734,7 +1115,7 @@ where
     I2C: I2c,
     D: DelayNs,
 {
-    type Error = I2C::Error;
+    type Error = Sgtl5000Error<I2C::Error>;
 
     fn enable(&mut self) -> Result<(), Self::Error> {
         // Delegate to the inherent method
@@ -742,11 +1123,11 @@ where
     }
 
     fn disable(&mut self) -> Result<(), Self::Error> {
-        Sgtl5000::disable(self)
+        Ok(Sgtl5000::disable(self)?)
     }
 
     fn volume(&mut self, level: f32) -> Result<(), Self::Error> {
-        Sgtl5000::volume(self, level)
+        Ok(Sgtl5000::volume(self, level)?)
     }
 }
 
@@ -780,10 +1161,17 @@ mod tests {
     }
 
     impl MockI2c {
+        /// A mock whose `CHIP_ID` reads as an SGTL5000 (part 0xA0, rev 0x11).
         fn new() -> Self {
+            Self::with_chip_id(0xA011)
+        }
+
+        fn with_chip_id(chip_id: u16) -> Self {
+            let mut regs = [(0, 0); 128];
+            regs[0] = (reg::CHIP_ID, chip_id);
             Self {
-                regs: [(0, 0); 128],
-                reg_count: 0,
+                regs,
+                reg_count: 1,
                 log: [(0, 0); 128],
                 log_count: 0,
             }
@@ -913,6 +1301,40 @@ mod tests {
         assert!(codec.semi_automated);
     }
 
+    // ── Chip identification tests ─────────────────────────────────────
+
+    #[test]
+    fn chip_id_splits_part_and_revision() {
+        let mut codec = Sgtl5000::new(MockI2c::with_chip_id(0xA011), MockDelay);
+        assert_eq!(codec.chip_id().unwrap(), (0xA0, 0x11));
+        assert!(codec.is_present().unwrap());
+    }
+
+    #[test]
+    fn wrong_part_id_is_not_present() {
+        let mut codec = Sgtl5000::new(MockI2c::with_chip_id(0x5511), MockDelay);
+        assert!(!codec.is_present().unwrap());
+    }
+
+    #[test]
+    fn enable_wrong_chip_id_is_not_present() {
+        let mut codec = Sgtl5000::new(MockI2c::with_chip_id(0x0000), MockDelay);
+        assert!(matches!(codec.enable(), Err(Sgtl5000Error::NotPresent)));
+        assert!(matches!(
+            codec.enable_with_pll(12_288_000, 4096 * 44_100),
+            Err(Sgtl5000Error::NotPresent)
+        ));
+        assert!(!codec.enabled);
+        assert!(matches!(
+            AudioControl::enable(&mut codec),
+            Err(Sgtl5000Error::NotPresent)
+        ));
+
+        // Nothing is written to a chip that isn't there
+        let (i2c, _) = codec.release();
+        assert_eq!(i2c.log_count, 0);
+    }
+
     // ── Volume tests ──────────────────────────────────────────────────
 
     #[test]
@@ -996,6 +1418,41 @@ mod tests {
         assert_eq!(codec.ana_ctrl & (1 << 8), 0);
     }
 
+    #[test]
+    fn output_handles_route_to_their_registers() {
+        let mut codec = enabled_codec();
+        let start = codec.i2c.log_count;
+
+        codec.headphone().volume(0.5).unwrap();
+        // 0.5 → n = 64 → register 0x80 - 64 = 0x40 per channel; unmutes first
+        let last = codec.i2c.log_count - 1;
+        assert_eq!(codec.i2c.write_at(last), (reg::CHIP_ANA_HP_CTRL, 0x4040));
+        assert_eq!(codec.i2c.write_at(last - 1), (reg::CHIP_ANA_CTRL, 0x0026));
+
+        codec.line_out().mute().unwrap();
+        let last = codec.i2c.log_count - 1;
+        assert_eq!(codec.i2c.write_at(last), (reg::CHIP_ANA_CTRL, 0x0126));
+
+        codec.line_out().level(20, 25).unwrap();
+        assert_eq!(codec.i2c.read_reg(reg::CHIP_LINE_OUT_VOL) & 0x1F1F, (25 << 8) | 20);
+
+        codec.headphone().mute().unwrap();
+        assert_eq!(codec.ana_ctrl, 0x0136);
+        codec.line_out().unmute().unwrap();
+        codec.headphone().unmute().unwrap();
+        assert_eq!(codec.ana_ctrl, 0x0026);
+
+        // Nothing outside ANA_CTRL / HP_CTRL / LINE_OUT_VOL was touched
+        for i in start..codec.i2c.log_count {
+            let (r, _) = codec.i2c.write_at(i);
+            assert!(
+                r == reg::CHIP_ANA_CTRL || r == reg::CHIP_ANA_HP_CTRL || r == reg::CHIP_LINE_OUT_VOL,
+                "unexpected write to {:#06x}",
+                r
+            );
+        }
+    }
+
     // ── Input selection tests ─────────────────────────────────────────
 
     #[test]
@@ -1163,6 +1620,47 @@ mod tests {
         assert_eq!(i2c.read_reg(reg::CHIP_SSS_CTRL), 0x0010);
     }
 
+    // ── Auto volume control tests ─────────────────────────────────────
+
+    #[test]
+    fn auto_volume_register_encoding() {
+        let mut codec = enabled_codec();
+        // The C++ autoVolumeControl() formulas, kept at full register width:
+        // the C++ stores them in uint8_t, which would truncate 2623 to 63
+        codec.auto_volume(1, 1, 0, -18.0, 32.0, 0.5).unwrap();
+        assert_eq!(codec.i2c.read_reg(reg::DAP_AVC_THRESHOLD), 2623);
+        assert_eq!(codec.i2c.read_reg(reg::DAP_AVC_ATTACK), 43);
+        assert_eq!(codec.i2c.read_reg(reg::DAP_AVC_DECAY), 10);
+        assert_eq!(codec.i2c.read_reg(reg::DAP_AVC_CTRL), 0x1100);
+
+        // −12 dBFS is the chip's reset threshold (~0x1473)
+        codec.auto_volume(5, 7, 1, -12.0, 10.0, 4.0).unwrap();
+        assert_eq!(codec.i2c.read_reg(reg::DAP_AVC_THRESHOLD), 5234);
+        assert_eq!(codec.i2c.read_reg(reg::DAP_AVC_ATTACK), 13);
+        assert_eq!(codec.i2c.read_reg(reg::DAP_AVC_DECAY), 87);
+        // max_gain clamped to 2, lbi_response masked to 3
+        assert_eq!(codec.i2c.read_reg(reg::DAP_AVC_CTRL), 0x2320);
+    }
+
+    #[test]
+    fn auto_volume_routes_through_dap_and_toggles() {
+        let mut codec = enabled_codec();
+        codec.auto_volume(1, 1, 0, -18.0, 32.0, 0.5).unwrap();
+        assert_eq!(codec.i2c.read_reg(reg::DAP_CONTROL), 1);
+        assert_eq!(codec.i2c.read_reg(reg::CHIP_SSS_CTRL), 0x0070);
+
+        codec.auto_volume_enable().unwrap();
+        assert_eq!(codec.i2c.read_reg(reg::DAP_AVC_CTRL), 0x1101);
+        codec.auto_volume_disable().unwrap();
+        assert_eq!(codec.i2c.read_reg(reg::DAP_AVC_CTRL), 0x1100);
+
+        // Pre-processor routing is left alone
+        let mut codec = enabled_codec();
+        codec.audio_pre_processor_enable().unwrap();
+        codec.auto_volume(0, 0, 0, -6.0, 8.0, 1.0).unwrap();
+        assert_eq!(codec.i2c.read_reg(reg::CHIP_SSS_CTRL), 0x0013);
+    }
+
     // ── calc_vol helper test ──────────────────────────────────────────
 
     #[test]
@@ -1197,6 +1695,131 @@ mod tests {
         assert_eq!(codec.address, Sgtl5000::<MockI2c, MockDelay>::ALT_ADDRESS);
     }
 
+    // ── Graphic EQ tests ──────────────────────────────────────────────
+
+    #[test]
+    fn graphic_eq_round_trip() {
+        let mut codec = enabled_codec();
+        let bands = [-1.0, -0.5, 0.0, 0.25, 1.0];
+        codec.set_graphic_eq(&bands).unwrap();
+
+        let read = codec.read_graphic_eq().unwrap();
+        for (set, got) in bands.iter().zip(read.iter()) {
+            assert!(
+                (set - got).abs() <= 1.0 / 48.0 + 1e-6,
+                "band set to {} read back as {}",
+                set,
+                got
+            );
+        }
+
+        let (i2c, _) = codec.release();
+        // Graphic EQ mode auto-selected
+        assert_eq!(i2c.read_reg(reg::DAP_AUDIO_EQ) & 3, EqMode::GraphicEq as u16);
+    }
+
+    #[test]
+    fn graphic_eq_rejects_out_of_range() {
+        let mut codec = enabled_codec();
+        let result = codec.set_graphic_eq(&[0.0, 0.0, 1.5, 0.0, 0.0]);
+        assert!(matches!(result, Err(Sgtl5000Error::InvalidParameter)));
+        let result = codec.set_graphic_eq(&[0.0, f32::NAN, 0.0, 0.0, 0.0]);
+        assert!(matches!(result, Err(Sgtl5000Error::InvalidParameter)));
+
+        // Nothing was written to the band registers
+        let (i2c, _) = codec.release();
+        assert_eq!(i2c.read_reg(reg::DAP_AUDIO_EQ_BASS_BAND0), 0);
+    }
+
+    // ── Bass enhance tests ────────────────────────────────────────────
+
+    #[test]
+    fn bass_boost_db_encodes_level_and_enables() {
+        let mut codec = enabled_codec();
+        codec.enhance_bass(0.5, 0.0).unwrap();
+        let lr_bits = codec.i2c.read_reg(reg::DAP_BASS_ENHANCE_CTRL) & (0x3F << 8);
+
+        codec.bass_boost_db(3.0).unwrap();
+        // (6.0 - 3.0) / 0.5 dB per step = 6
+        assert_eq!(codec.i2c.read_reg(reg::DAP_BASS_ENHANCE_CTRL), lr_bits | 0x06);
+        assert_eq!(codec.i2c.read_reg(reg::DAP_BASS_ENHANCE) & 1, 1);
+
+        codec.bass_boost_db(-57.5).unwrap();
+        assert_eq!(codec.i2c.read_reg(reg::DAP_BASS_ENHANCE_CTRL) & 0x7F, 0x7F);
+        codec.bass_boost_db(6.0).unwrap();
+        assert_eq!(codec.i2c.read_reg(reg::DAP_BASS_ENHANCE_CTRL) & 0x7F, 0x00);
+
+        let writes = codec.i2c.log_count;
+        assert!(matches!(codec.bass_boost_db(7.0), Err(Sgtl5000Error::InvalidParameter)));
+        assert!(matches!(codec.bass_boost_db(f32::NAN), Err(Sgtl5000Error::InvalidParameter)));
+        assert_eq!(codec.i2c.log_count, writes);
+    }
+
+    #[test]
+    fn enhance_bass_round_trip() {
+        let mut codec = enabled_codec();
+        codec.enhance_bass(0.25, 0.75).unwrap();
+        let (lr, bass) = codec.read_enhance_bass().unwrap();
+        assert!((lr - 0.25).abs() <= 1.0 / 0x3F as f32, "lr {}", lr);
+        assert!((bass - 0.75).abs() <= 1.0 / 0x7F as f32, "bass {}", bass);
+    }
+
+    // ── Recovery tests ────────────────────────────────────────────────
+
+    #[test]
+    fn reinit_noop_when_configured() {
+        let mut codec = enabled_codec();
+        let writes = codec.i2c.log_count;
+        assert!(!codec.reinit().unwrap());
+        assert_eq!(codec.i2c.log_count, writes);
+    }
+
+    #[test]
+    fn reinit_restores_clobbered_i2s_without_ramp() {
+        let mut codec = enabled_codec();
+        codec.volume(0.5).unwrap();
+        let hp = codec.i2c.read_reg(reg::CHIP_ANA_HP_CTRL);
+        let ana = codec.i2c.read_reg(reg::CHIP_ANA_CTRL);
+
+        // Simulate a glitch that reset the digital side only
+        codec.i2c.set_reg(reg::CHIP_I2S_CTRL, 0x0010);
+        codec.i2c.set_reg(reg::CHIP_ANA_HP_CTRL, 0x1818);
+        let writes = codec.i2c.log_count;
+
+        assert!(codec.reinit().unwrap());
+        let (i2c, _) = codec.release();
+        assert_eq!(i2c.read_reg(reg::CHIP_I2S_CTRL), 0x0030);
+        assert_eq!(i2c.read_reg(reg::CHIP_CLK_CTRL), 0x0004);
+        assert_eq!(i2c.read_reg(reg::CHIP_SSS_CTRL), 0x0010);
+        assert_eq!(i2c.read_reg(reg::CHIP_ANA_HP_CTRL), hp);
+        assert_eq!(i2c.read_reg(reg::CHIP_ANA_CTRL), ana);
+
+        // Analog power was still up, so it is not rewritten
+        for idx in writes..i2c.log_count {
+            assert_ne!(i2c.write_at(idx).0, reg::CHIP_ANA_POWER);
+        }
+    }
+
+    #[test]
+    fn reinit_full_sequence_when_analog_down() {
+        let mut codec = enabled_codec();
+        codec.i2c.set_reg(reg::CHIP_I2S_CTRL, 0x0010);
+        codec.i2c.set_reg(reg::CHIP_ANA_POWER, 0x7060);
+        let writes = codec.i2c.log_count;
+
+        assert!(codec.reinit().unwrap());
+        assert_eq!(codec.i2c.write_at(writes), (reg::CHIP_ANA_POWER, 0x4060));
+        assert_eq!(codec.i2c.read_reg(reg::CHIP_ANA_POWER), 0x40FF);
+        assert_eq!(codec.i2c.read_reg(reg::CHIP_I2S_CTRL), 0x0030);
+    }
+
+    #[test]
+    fn reinit_before_enable_is_noop() {
+        let mut codec = make_codec();
+        assert!(!codec.reinit().unwrap());
+        assert_eq!(codec.i2c.log_count, 0);
+    }
+
     // ── Release test ──────────────────────────────────────────────────
 
     #[test]
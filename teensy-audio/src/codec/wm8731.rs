@@ -0,0 +1,374 @@
+//! WM8731 audio codec driver.
+//!
+//! Driver for the Cirrus Logic (Wolfson) WM8731 stereo codec, as used on
+//! the MikroElektronika Audio Codec Proto board, ported from the C++
+//! `AudioControlWM8731` class. The public API mirrors
+//! [`Sgtl5000`](super::Sgtl5000) where the chips overlap, and both
+//! implement [`AudioControl`], so graph setup code can be generic over the
+//! codec.
+//!
+//! The WM8731's registers are write-only: the driver never reads back.
+//!
+//! # Example
+//!
+//! ```ignore
+//! let mut codec = Wm8731::new(i2c, delay);
+//! codec.enable()?;           // Reset, I2S slave setup, ~100 ms power-up
+//! codec.volume(0.6)?;        // Set headphone volume
+//! codec.input_select(Wm8731Input::LineIn)?;
+//! ```
+
+use embedded_hal::delay::DelayNs;
+use embedded_hal::i2c::I2c;
+
+use super::wm8731_registers as reg;
+use crate::control::AudioControl;
+
+/// ADC input selection.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Wm8731Input {
+    /// Stereo line-in input.
+    LineIn,
+    /// Microphone input (+20 dB boost).
+    Mic,
+}
+
+/// WM8731 audio codec driver.
+///
+/// Generic over I2C bus and delay provider. The delay is used only during
+/// the power-on sequence.
+pub struct Wm8731<I2C, D> {
+    i2c: I2C,
+    delay: D,
+    address: u8,
+}
+
+impl<I2C, D> Wm8731<I2C, D>
+where
+    I2C: I2c,
+    D: DelayNs,
+{
+    /// Default I2C address (CSB pin low).
+    pub const DEFAULT_ADDRESS: u8 = reg::I2C_ADDR_CSB_LOW;
+
+    /// Alternate I2C address (CSB pin high).
+    pub const ALT_ADDRESS: u8 = reg::I2C_ADDR_CSB_HIGH;
+
+    /// Create a new driver with the default I2C address (0x1A).
+    pub fn new(i2c: I2C, delay: D) -> Self {
+        Self::new_with_address(i2c, delay, Self::DEFAULT_ADDRESS)
+    }
+
+    /// Create a new driver with a specific I2C address.
+    pub fn new_with_address(i2c: I2C, delay: D, address: u8) -> Self {
+        Self {
+            i2c,
+            delay,
+            address,
+        }
+    }
+
+    // ── Low-level I2C helpers ──────────────────────────────────────────
+
+    /// Write a 9-bit value to a 7-bit register.
+    ///
+    /// The register address and the value's top bit share the first byte.
+    pub fn write_register(&mut self, register: u8, value: u16) -> Result<(), I2C::Error> {
+        let buf = [(register << 1) | ((value >> 8) & 1) as u8, value as u8];
+        self.i2c.write(self.address, &buf)
+    }
+
+    // ── Power-on sequence ──────────────────────────────────────────────
+
+    /// Full power-on sequence for I2S slave mode at 44.1 kHz.
+    ///
+    /// Resets the chip, selects 16-bit I2S with 256×Fs MCLK, powers up
+    /// with the DAC soft-muted and headphones at minimum to avoid pops,
+    /// then activates the interface and routes the DAC to the outputs.
+    /// Call [`volume()`](Self::volume) to make the headphones audible.
+    pub fn enable(&mut self) -> Result<(), I2C::Error> {
+        self.delay.delay_ms(5);
+        self.write_register(reg::RESET, 0)?;
+        // I2S, 16-bit, slave
+        self.write_register(reg::INTERFACE, 0x02)?;
+        // 256×Fs, 44.1 kHz, MCLK/1
+        self.write_register(reg::SAMPLING, 0x20)?;
+        // DAC soft mute while powering up
+        self.write_register(reg::DIGITAL, 0x08)?;
+        // Disconnect everything from the outputs
+        self.write_register(reg::ANALOG, 0x00)?;
+        // Power up all blocks
+        self.write_register(reg::POWERDOWN, 0x00)?;
+        // Headphones muted (volume code below the audible range)
+        self.write_register(reg::LHEADOUT, reg::HEADOUT_ZCEN)?;
+        self.write_register(reg::RHEADOUT, reg::HEADOUT_ZCEN)?;
+
+        self.delay.delay_ms(100);
+        self.write_register(reg::ACTIVE, 1)?;
+        self.delay.delay_ms(5);
+
+        // DAC unmuted and selected
+        self.write_register(reg::DIGITAL, 0x00)?;
+        self.write_register(reg::ANALOG, 0x10)
+    }
+
+    /// Disable the codec by deactivating its digital audio interface.
+    pub fn disable(&mut self) -> Result<(), I2C::Error> {
+        self.write_register(reg::ACTIVE, 0)
+    }
+
+    // ── Headphone volume ───────────────────────────────────────────────
+
+    /// Set headphone volume (0.0 = muted, 1.0 = maximum +6 dB).
+    pub fn volume(&mut self, level: f32) -> Result<(), I2C::Error> {
+        let code = Self::headphone_code(level);
+        self.write_register(reg::LHEADOUT, code | reg::HEADOUT_BOTH | reg::HEADOUT_ZCEN)
+    }
+
+    /// Set headphone volume independently for left and right channels
+    /// (0.0 = muted, 1.0 = maximum).
+    pub fn volume_lr(&mut self, left: f32, right: f32) -> Result<(), I2C::Error> {
+        let l = Self::headphone_code(left);
+        let r = Self::headphone_code(right);
+        self.write_register(reg::LHEADOUT, l | reg::HEADOUT_ZCEN)?;
+        self.write_register(reg::RHEADOUT, r | reg::HEADOUT_ZCEN)
+    }
+
+    /// Map 0.0–1.0 to a HEADOUT volume code: 0.0 lands below
+    /// [`reg::HEADOUT_MIN`] (mute), anything above spans −73 … +6 dB.
+    fn headphone_code(level: f32) -> u16 {
+        let level = level.clamp(0.0, 1.0);
+        let n = (level * 80.0 + 47.499) as u16;
+        n.min(reg::HEADOUT_MAX)
+    }
+
+    // ── Input selection and level ──────────────────────────────────────
+
+    /// Select the ADC input source. Keeps the DAC routed to the outputs.
+    pub fn input_select(&mut self, input: Wm8731Input) -> Result<(), I2C::Error> {
+        let analog = match input {
+            Wm8731Input::LineIn => reg::ANALOG_LINEIN,
+            Wm8731Input::Mic => reg::ANALOG_MIC,
+        };
+        self.write_register(reg::ANALOG, analog)
+    }
+
+    /// Set line-in input level (0–31 per channel, 1.5 dB steps,
+    /// 23 = 0 dB). Values above 31 are clamped.
+    pub fn line_in_level(&mut self, left: u8, right: u8) -> Result<(), I2C::Error> {
+        let l = left.min(reg::LINEIN_MAX);
+        let r = right.min(reg::LINEIN_MAX);
+        self.write_register(reg::LLINEIN, l as u16)?;
+        self.write_register(reg::RLINEIN, r as u16)
+    }
+
+    /// Release the I2C bus and delay provider.
+    pub fn release(self) -> (I2C, D) {
+        (self.i2c, self.delay)
+    }
+}
+
+// ── AudioControl trait implementation ──────────────────────────────────────
+
+impl<I2C, D> AudioControl for Wm8731<I2C, D>
+where
+    I2C: I2c,
+    D: DelayNs,
+{
+    type Error = I2C::Error;
+
+    fn enable(&mut self) -> Result<(), Self::Error> {
+        Wm8731::enable(self)
+    }
+
+    fn disable(&mut self) -> Result<(), Self::Error> {
+        Wm8731::disable(self)
+    }
+
+    fn volume(&mut self, level: f32) -> Result<(), Self::Error> {
+        Wm8731::volume(self, level)
+    }
+}
+
+// ── Tests ──────────────────────────────────────────────────────────────────
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use embedded_hal::delay::DelayNs;
+    use embedded_hal::i2c::{self, ErrorType, I2c, Operation};
+
+    // ── Mock I2C write log ────────────────────────────────────────────
+
+    #[derive(Debug)]
+    struct MockError;
+
+    impl i2c::Error for MockError {
+        fn kind(&self) -> i2c::ErrorKind {
+            i2c::ErrorKind::Other
+        }
+    }
+
+    /// Mock I2C that records decoded (register, value) writes.
+    struct MockI2c {
+        log: [(u8, u16); 32],
+        log_count: usize,
+        last_addr: u8,
+    }
+
+    impl MockI2c {
+        fn new() -> Self {
+            Self {
+                log: [(0, 0); 32],
+                log_count: 0,
+                last_addr: 0,
+            }
+        }
+
+        fn writes(&self) -> &[(u8, u16)] {
+            &self.log[..self.log_count]
+        }
+    }
+
+    impl ErrorType for MockI2c {
+        type Error = MockError;
+    }
+
+    impl I2c for MockI2c {
+        fn read(&mut self, _addr: u8, _buf: &mut [u8]) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn write(&mut self, addr: u8, bytes: &[u8]) -> Result<(), Self::Error> {
+            assert_eq!(bytes.len(), 2, "WM8731 writes are two bytes");
+            let reg = bytes[0] >> 1;
+            let val = (((bytes[0] & 1) as u16) << 8) | bytes[1] as u16;
+            self.log[self.log_count] = (reg, val);
+            self.log_count += 1;
+            self.last_addr = addr;
+            Ok(())
+        }
+
+        fn write_read(&mut self, _addr: u8, _wr: &[u8], _rd: &mut [u8]) -> Result<(), Self::Error> {
+            panic!("WM8731 registers are write-only");
+        }
+
+        fn transaction(
+            &mut self,
+            _addr: u8,
+            _ops: &mut [Operation<'_>],
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    // ── Mock delay that totals the requested time ─────────────────────
+
+    struct MockDelay {
+        total_ns: u64,
+    }
+
+    impl DelayNs for MockDelay {
+        fn delay_ns(&mut self, ns: u32) {
+            self.total_ns += ns as u64;
+        }
+    }
+
+    fn make_codec() -> Wm8731<MockI2c, MockDelay> {
+        Wm8731::new(MockI2c::new(), MockDelay { total_ns: 0 })
+    }
+
+    #[test]
+    fn enable_writes_power_up_sequence() {
+        let mut codec = make_codec();
+        codec.enable().unwrap();
+        let (i2c, delay) = codec.release();
+
+        assert_eq!(
+            i2c.writes(),
+            &[
+                (reg::RESET, 0x000),
+                (reg::INTERFACE, 0x002),
+                (reg::SAMPLING, 0x020),
+                (reg::DIGITAL, 0x008),
+                (reg::ANALOG, 0x000),
+                (reg::POWERDOWN, 0x000),
+                (reg::LHEADOUT, 0x080),
+                (reg::RHEADOUT, 0x080),
+                (reg::ACTIVE, 0x001),
+                (reg::DIGITAL, 0x000),
+                (reg::ANALOG, 0x010),
+            ]
+        );
+        assert_eq!(i2c.last_addr, 0x1A);
+        assert!(delay.total_ns >= 100_000_000, "power-up settle delay");
+    }
+
+    #[test]
+    fn volume_encodes_nine_bit_headout() {
+        let mut codec = make_codec();
+        codec.volume(1.0).unwrap();
+        codec.volume(0.0).unwrap();
+        codec.volume(0.5).unwrap();
+        let (i2c, _) = codec.release();
+
+        // LRHPBOTH (bit 8) must survive the 7-bit address packing
+        assert_eq!(i2c.writes()[0], (reg::LHEADOUT, 0x1FF));
+        assert_eq!(i2c.writes()[1], (reg::LHEADOUT, 0x1AF), "0.0 is below HEADOUT_MIN");
+        assert_eq!(i2c.writes()[2], (reg::LHEADOUT, 0x180 | 87));
+    }
+
+    #[test]
+    fn volume_lr_writes_each_channel() {
+        let mut codec = make_codec();
+        codec.volume_lr(1.0, 0.0).unwrap();
+        let (i2c, _) = codec.release();
+        assert_eq!(i2c.writes(), &[(reg::LHEADOUT, 0x0FF), (reg::RHEADOUT, 0x0AF)]);
+    }
+
+    #[test]
+    fn input_select_and_line_level() {
+        let mut codec = make_codec();
+        codec.input_select(Wm8731Input::Mic).unwrap();
+        codec.input_select(Wm8731Input::LineIn).unwrap();
+        codec.line_in_level(23, 40).unwrap();
+        let (i2c, _) = codec.release();
+        assert_eq!(
+            i2c.writes(),
+            &[
+                (reg::ANALOG, 0x15),
+                (reg::ANALOG, 0x12),
+                (reg::LLINEIN, 23),
+                (reg::RLINEIN, 0x1F),
+            ]
+        );
+    }
+
+    #[test]
+    fn audio_control_trait_delegation() {
+        fn bring_up<C: AudioControl>(codec: &mut C) -> Result<(), C::Error> {
+            codec.enable()?;
+            codec.volume(1.0)?;
+            codec.disable()
+        }
+
+        let mut codec = make_codec();
+        bring_up(&mut codec).unwrap();
+        let (i2c, _) = codec.release();
+        let writes = i2c.writes();
+        assert_eq!(writes[writes.len() - 2], (reg::LHEADOUT, 0x1FF));
+        assert_eq!(writes[writes.len() - 1], (reg::ACTIVE, 0));
+    }
+
+    #[test]
+    fn custom_address() {
+        let mut codec = Wm8731::new_with_address(
+            MockI2c::new(),
+            MockDelay { total_ns: 0 },
+            Wm8731::<MockI2c, MockDelay>::ALT_ADDRESS,
+        );
+        codec.disable().unwrap();
+        let (i2c, _) = codec.release();
+        assert_eq!(i2c.last_addr, 0x1B);
+    }
+}
@@ -0,0 +1,102 @@
+//! WM8731 register addresses and bitfield definitions.
+//!
+//! Ported from the C++ `control_wm8731.cpp` register definitions and the
+//! Cirrus Logic (Wolfson) WM8731 datasheet. Registers have 7-bit addresses
+//! and hold 9-bit values; they are write-only, so the driver never reads
+//! them back.
+
+// Some registers are defined for completeness but are not yet used.
+#![allow(dead_code)]
+
+// ── I2C addresses ──────────────────────────────────────────────────────────
+
+/// Default I2C address (CSB pin low).
+pub const I2C_ADDR_CSB_LOW: u8 = 0x1A;
+
+/// Alternate I2C address (CSB pin high).
+pub const I2C_ADDR_CSB_HIGH: u8 = 0x1B;
+
+// ── Line inputs ────────────────────────────────────────────────────────────
+
+/// Left line-in control.
+/// - Bit  8   — LRINBOTH (write to both channels)
+/// - Bit  7   — LINMUTE
+/// - Bits 4:0 — LINVOL (0x00 = −34.5 dB … 0x1F = +12 dB, 1.5 dB steps)
+pub const LLINEIN: u8 = 0x00;
+
+/// Right line-in control (same layout as [`LLINEIN`]).
+pub const RLINEIN: u8 = 0x01;
+
+// ── Headphone outputs ──────────────────────────────────────────────────────
+
+/// Left headphone out control.
+/// - Bit  8   — LRHPBOTH (write to both channels)
+/// - Bit  7   — LZCEN (zero-cross enable)
+/// - Bits 6:0 — LHPVOL (0x7F = +6 dB … 0x30 = −73 dB, below = mute)
+pub const LHEADOUT: u8 = 0x02;
+
+/// Right headphone out control (same layout as [`LHEADOUT`]).
+pub const RHEADOUT: u8 = 0x03;
+
+// ── Analog path ────────────────────────────────────────────────────────────
+
+/// Analog audio path control.
+/// - Bit 5 — SIDETONE
+/// - Bit 4 — DACSEL (DAC to output mixer)
+/// - Bit 3 — BYPASS (line-in to output mixer)
+/// - Bit 2 — INSEL (1 = mic, 0 = line-in to ADC)
+/// - Bit 1 — MUTEMIC
+/// - Bit 0 — MICBOOST (+20 dB)
+pub const ANALOG: u8 = 0x04;
+
+/// Digital audio path control.
+/// - Bit 3   — DACMU (DAC soft mute)
+/// - Bits 2:1 — DEEMPH
+/// - Bit 0   — ADCHPD (ADC high-pass disable)
+pub const DIGITAL: u8 = 0x05;
+
+// ── Power, interface, clocking ─────────────────────────────────────────────
+
+/// Power-down control (1 = powered down), one bit per block.
+pub const POWERDOWN: u8 = 0x06;
+
+/// Digital audio interface format.
+/// - Bit 6   — MS (master mode)
+/// - Bits 3:2 — IWL (00 = 16-bit)
+/// - Bits 1:0 — FORMAT (10 = I2S)
+pub const INTERFACE: u8 = 0x07;
+
+/// Sampling control.
+/// - Bits 5:2 — SR (sample rate select)
+/// - Bit  1   — BOSR
+/// - Bit  0   — USB mode
+pub const SAMPLING: u8 = 0x08;
+
+/// Active control. Bit 0 — ACTIVE (digital interface enabled).
+pub const ACTIVE: u8 = 0x09;
+
+/// Reset register. Writing 0 resets the device.
+pub const RESET: u8 = 0x0F;
+
+// ── Field values ───────────────────────────────────────────────────────────
+
+/// ANALOG: DAC selected, line-in to ADC, mic muted.
+pub const ANALOG_LINEIN: u16 = 0x12;
+
+/// ANALOG: DAC selected, mic to ADC with +20 dB boost.
+pub const ANALOG_MIC: u16 = 0x15;
+
+/// HEADOUT: write both channels at once.
+pub const HEADOUT_BOTH: u16 = 1 << 8;
+
+/// HEADOUT: zero-cross detection enable.
+pub const HEADOUT_ZCEN: u16 = 1 << 7;
+
+/// Highest headphone volume code (+6 dB).
+pub const HEADOUT_MAX: u16 = 0x7F;
+
+/// Lowest audible headphone volume code (−73 dB); anything below mutes.
+pub const HEADOUT_MIN: u16 = 0x30;
+
+/// Highest line-in volume code (+12 dB).
+pub const LINEIN_MAX: u8 = 0x1F;
@@ -2,7 +2,80 @@
 pub const AUDIO_BLOCK_SAMPLES: usize = 128;
 
 /// Number of audio blocks in the global pool.
-pub const POOL_SIZE: usize = 32;
+///
+/// 32 by default (8 KiB of sample storage), which fits the i.MX RT memory
+/// budget alongside typical graphs. The `pool-64` and `pool-128` features
+/// raise it for graphs with many live blocks (long delays, reverbs, FFT
+/// overlap); if both are enabled the larger wins.
+pub const POOL_SIZE: usize = if cfg!(feature = "pool-128") {
+    128
+} else if cfg!(feature = "pool-64") {
+    64
+} else {
+    32
+};
 
 /// Exact audio sample rate in Hz (matches Teensy hardware PLL configuration).
 pub const AUDIO_SAMPLE_RATE_EXACT: f32 = 44_117.647;
+
+/// Duration of one audio block in seconds (≈ 2.9 ms).
+pub const BLOCK_DURATION_SECS: f32 = AUDIO_BLOCK_SAMPLES as f32 / AUDIO_SAMPLE_RATE_EXACT;
+
+/// Convert a duration in milliseconds to the nearest whole number of
+/// samples.
+///
+/// Negative (and NaN) durations give 0; very long ones saturate at
+/// `u32::MAX`.
+#[inline]
+pub fn ms_to_samples(ms: f32) -> u32 {
+    (ms * (AUDIO_SAMPLE_RATE_EXACT / 1000.0) + 0.5) as u32
+}
+
+/// Convert a duration in milliseconds to whole samples, rounding down.
+///
+/// Matches the C++ library's `(uint32_t)(ms * SAMPLES_PER_MSEC)` casts,
+/// for nodes whose timing should stay sample-for-sample with it. Negative
+/// (and NaN) durations give 0.
+#[inline]
+pub fn ms_to_samples_truncated(ms: f32) -> u32 {
+    (ms * (AUDIO_SAMPLE_RATE_EXACT / 1000.0)) as u32
+}
+
+/// Convert a number of samples to a duration in milliseconds.
+#[inline]
+pub fn samples_to_ms(n: u32) -> f32 {
+    n as f32 * (1000.0 / AUDIO_SAMPLE_RATE_EXACT)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn block_duration() {
+        // 128 / 44117.647 ≈ 2.9 ms
+        assert!((BLOCK_DURATION_SECS * 1000.0 - 2.9).abs() < 0.1);
+        assert!(
+            (samples_to_ms(AUDIO_BLOCK_SAMPLES as u32) - BLOCK_DURATION_SECS * 1000.0).abs() < 1e-4
+        );
+        assert_eq!(
+            ms_to_samples(BLOCK_DURATION_SECS * 1000.0),
+            AUDIO_BLOCK_SAMPLES as u32
+        );
+    }
+
+    #[test]
+    fn ms_sample_conversions() {
+        // 44117.647 samples per second
+        assert_eq!(ms_to_samples(1000.0), 44118);
+        assert_eq!(ms_to_samples(100.0), 4412);
+        assert_eq!(ms_to_samples(1.0), 44);
+        assert_eq!(ms_to_samples(0.0), 0);
+        assert_eq!(ms_to_samples(-5.0), 0);
+        assert_eq!(ms_to_samples_truncated(1000.0), 44117);
+        assert_eq!(ms_to_samples_truncated(100.0), 4411);
+        assert_eq!(ms_to_samples_truncated(-5.0), 0);
+        assert!((samples_to_ms(44118) - 1000.0).abs() < 0.01);
+        assert!((samples_to_ms(4412) - 100.0).abs() < 0.02);
+    }
+}
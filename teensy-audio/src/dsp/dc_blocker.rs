@@ -0,0 +1,121 @@
+//! One-pole DC blocker.
+//!
+//! The classic `y[n] = x[n] − x[n-1] + R·y[n-1]` recurrence: a zero at DC
+//! and a pole just inside the unit circle. Unlike [`OnePole`](super::OnePole)'s
+//! highpass it is tuned by the pole radius `R` directly, the way DC
+//! blockers are usually specified.
+
+use super::intrinsics::{mul_32x32_rshift32, saturate16};
+
+/// Default pole radius: −3 dB at roughly 35 Hz at 44.1 kHz.
+pub const DC_BLOCKER_DEFAULT_POLE: f32 = 0.995;
+
+/// Largest accepted pole radius; `R = 1` would stop blocking DC.
+const MAX_POLE: f32 = 0.9999;
+
+/// Fixed-point one-pole DC blocker.
+///
+/// `R` is held in Q31. The state keeps 14 fractional bits below the sample,
+/// which leaves headroom for a full-scale step (`x[n] − x[n-1]` up to
+/// ±65535) and keeps truncation from leaving a residual offset.
+///
+/// # Example
+/// ```ignore
+/// let mut dc = DcBlocker::new();
+/// dc.set_pole(0.999);
+/// let y = dc.process(x);
+/// ```
+#[derive(Clone, Debug)]
+pub struct DcBlocker {
+    /// Pole radius `R` in Q31.
+    pole: i32,
+    /// Previous input, sample scaled by 2^14.
+    x1: i32,
+    /// Previous output, sample scaled by 2^14.
+    y1: i32,
+}
+
+impl DcBlocker {
+    /// Create a blocker with the default pole ([`DC_BLOCKER_DEFAULT_POLE`]).
+    pub const fn new() -> Self {
+        DcBlocker {
+            pole: (DC_BLOCKER_DEFAULT_POLE * 2_147_483_648.0) as i32,
+            x1: 0,
+            y1: 0,
+        }
+    }
+
+    /// Set the pole radius, clamped to 0.0–0.9999. Closer to 1.0 gives a
+    /// lower cutoff and a slower settle.
+    pub fn set_pole(&mut self, pole: f32) {
+        self.pole = pole_to_q31(pole);
+    }
+
+    /// Clear the filter state.
+    pub fn reset(&mut self) {
+        self.x1 = 0;
+        self.y1 = 0;
+    }
+
+    /// Filter one sample.
+    #[inline]
+    pub fn process(&mut self, x: i16) -> i16 {
+        let input = (x as i32) << 14;
+        // |y| stays within a few times full scale, well inside 2^31 at Q14
+        let y = input - self.x1 + (mul_32x32_rshift32(self.y1, self.pole) << 1);
+        self.x1 = input;
+        self.y1 = y;
+        saturate16((y + (1 << 13)) >> 14)
+    }
+}
+
+impl Default for DcBlocker {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Pole radius in Q31, clamped to `0.0..=MAX_POLE`.
+fn pole_to_q31(pole: f32) -> i32 {
+    (pole.clamp(0.0, MAX_POLE) * 2_147_483_648.0) as i32
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dc_blocker_removes_step() {
+        let mut dc = DcBlocker::new();
+        let first = dc.process(10000);
+        assert!(first > 9900, "the edge should pass, got {}", first);
+        let mut y = first;
+        for _ in 0..44100 {
+            y = dc.process(10000);
+        }
+        assert!(y.abs() <= 1, "DC should be removed, got {}", y);
+    }
+
+    #[test]
+    fn dc_blocker_full_scale_step_saturates_cleanly() {
+        let mut dc = DcBlocker::new();
+        dc.process(-32768);
+        for _ in 0..2000 {
+            dc.process(-32768);
+        }
+        // Jump to the other rail: output clips instead of wrapping
+        assert_eq!(dc.process(32767), 32767);
+    }
+
+    #[test]
+    fn dc_blocker_pole_clamped() {
+        let mut dc = DcBlocker::new();
+        dc.set_pole(1.5);
+        assert_eq!(dc.pole, pole_to_q31(MAX_POLE));
+        dc.set_pole(-1.0);
+        assert_eq!(dc.pole, 0);
+        // R = 0 is a first difference
+        dc.process(500);
+        assert_eq!(dc.process(800), 300);
+    }
+}
@@ -0,0 +1,190 @@
+//! Fixed-point radix-2 FFT.
+//!
+//! In-place complex transforms on separate real and imaginary `i16`
+//! arrays, shared by spectrum analysis and fast convolution. Twiddle
+//! factors come from [`SINE_TABLE`](super::wavetables::SINE_TABLE),
+//! interpolated for sizes above 256.
+//!
+//! The forward transform halves the data at every stage, so it returns the
+//! DFT divided by `N` and cannot overflow. The inverse is unscaled, so
+//! `ifft_q15(fft_q15(x)) ≈ x`; a spectrum that did not come from
+//! [`fft_q15`] may saturate.
+
+use super::intrinsics::saturate16;
+use super::wavetables::sine_lookup;
+
+/// Compile-time check of the transform size.
+struct FftSize<const N: usize>;
+
+impl<const N: usize> FftSize<N> {
+    const CHECK: () = assert!(
+        N.is_power_of_two() && N >= 4 && N <= 1024,
+        "FFT size must be a power of two from 4 to 1024"
+    );
+}
+
+/// Forward FFT of `real + j·imag`, in place, scaled by `1/N`.
+///
+/// `N` must be a power of two from 4 to 1024 (256, 512 and 1024 are the
+/// usual sizes).
+pub fn fft_q15<const N: usize>(real: &mut [i16; N], imag: &mut [i16; N]) {
+    transform(real, imag, false);
+}
+
+/// Inverse FFT of `real + j·imag`, in place, unscaled.
+///
+/// Undoes [`fft_q15`]: the round trip returns the input to within the
+/// rounding noise of the scaled forward pass, which grows with `√N`.
+pub fn ifft_q15<const N: usize>(real: &mut [i16; N], imag: &mut [i16; N]) {
+    transform(real, imag, true);
+}
+
+/// Magnitudes `sqrt(re² + im²)` of the first `N / 2` bins (DC up to just
+/// below Nyquist), or as many as fit in `out`.
+pub fn magnitude_q15<const N: usize>(real: &[i16; N], imag: &[i16; N], out: &mut [u16]) {
+    for (bin, dst) in out.iter_mut().take(N / 2).enumerate() {
+        let re = real[bin] as i32;
+        let im = imag[bin] as i32;
+        // At most 32768·√2, which fits a u16 only up to 65535
+        let power = (re * re) as u32 + (im * im) as u32;
+        *dst = libm::sqrtf(power as f32).min(65535.0) as u16;
+    }
+}
+
+/// `(cos, sin)` of `2π·k/N` in Q15.
+#[inline(always)]
+fn twiddle<const N: usize>(k: usize) -> (i32, i32) {
+    let phase = (((k as u64) << 32) / N as u64) as u32;
+    (
+        sine_lookup(phase.wrapping_add(0x4000_0000)) >> 16,
+        sine_lookup(phase) >> 16,
+    )
+}
+
+/// `x / 2`, rounding halves to even. Plain `>> 1` would bias every bin
+/// the same way, which the inverse turns into a spike at sample 0.
+#[inline(always)]
+fn halve(x: i32) -> i16 {
+    ((x + ((x >> 1) & 1)) >> 1) as i16
+}
+
+fn transform<const N: usize>(real: &mut [i16; N], imag: &mut [i16; N], inverse: bool) {
+    #[allow(clippy::let_unit_value)]
+    let _ = FftSize::<N>::CHECK;
+
+    // Decimation in time: bit-reversed input, natural-order output
+    let bits = N.trailing_zeros();
+    for n in 0..N {
+        let j = n.reverse_bits() >> (usize::BITS - bits);
+        if j > n {
+            real.swap(n, j);
+            imag.swap(n, j);
+        }
+    }
+
+    let mut half = 1;
+    while half < N {
+        let step = N / (half * 2);
+        for start in (0..N).step_by(half * 2) {
+            for k in 0..half {
+                let (cos, sin) = twiddle::<N>(k * step);
+                // e^(∓j·2πk/N): negative angle forward, positive inverse
+                let (wr, wi) = if inverse { (cos, sin) } else { (cos, -sin) };
+                let a = start + k;
+                let b = a + half;
+                let (br, bi) = (real[b] as i32, imag[b] as i32);
+                let tr = (br * wr - bi * wi + 0x4000) >> 15;
+                let ti = (br * wi + bi * wr + 0x4000) >> 15;
+                let (ar, ai) = (real[a] as i32, imag[a] as i32);
+                if inverse {
+                    real[a] = saturate16(ar + tr);
+                    imag[a] = saturate16(ai + ti);
+                    real[b] = saturate16(ar - tr);
+                    imag[b] = saturate16(ai - ti);
+                } else {
+                    real[a] = halve(ar + tr);
+                    imag[a] = halve(ai + ti);
+                    real[b] = halve(ar - tr);
+                    imag[b] = halve(ai - ti);
+                }
+            }
+        }
+        half *= 2;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// `amplitude · cos(2π·bin·n/N)` in Q15.
+    fn cosine<const N: usize>(bin: usize, amplitude: f32) -> [i16; N] {
+        let mut x = [0i16; N];
+        for (n, s) in x.iter_mut().enumerate() {
+            let angle = 2.0 * core::f32::consts::PI * (bin * n) as f32 / N as f32;
+            *s = (amplitude * 32767.0 * libm::cosf(angle)) as i16;
+        }
+        x
+    }
+
+    fn single_bin<const N: usize>(bin: usize) {
+        let mut re = cosine::<N>(bin, 0.9);
+        let mut im = [0i16; N];
+        fft_q15(&mut re, &mut im);
+        let mut mag = [0u16; 512];
+        magnitude_q15(&re, &im, &mut mag);
+
+        // A cosine of amplitude A lands A/2 in its bin (after the 1/N)
+        let expected = 0.45 * 32767.0;
+        assert!(
+            (mag[bin] as f32 - expected).abs() < expected * 0.02,
+            "N={} bin {} magnitude {}",
+            N,
+            bin,
+            mag[bin]
+        );
+        for (k, &m) in mag[..N / 2].iter().enumerate() {
+            if k != bin {
+                assert!(m < 40, "N={} leakage into bin {}: {}", N, k, m);
+            }
+        }
+    }
+
+    #[test]
+    fn fft_single_bin_sinusoid() {
+        single_bin::<256>(10);
+        single_bin::<512>(37);
+        single_bin::<1024>(200);
+    }
+
+    fn round_trip<const N: usize>() {
+        let mut re = [0i16; N];
+        let mut im = [0i16; N];
+        // Two tones plus an impulse: broadband and narrowband content
+        for (n, s) in re.iter_mut().enumerate() {
+            let t = n as f32 / N as f32 * 2.0 * core::f32::consts::PI;
+            *s = (10000.0 * libm::sinf(3.0 * t) + 6000.0 * libm::cosf(41.0 * t)) as i16;
+        }
+        re[N / 3] = re[N / 3].saturating_add(12000);
+        let original = re;
+
+        fft_q15(&mut re, &mut im);
+        ifft_q15(&mut re, &mut im);
+        // Each bin carries ~1 LSB of rounding from the scaled forward pass,
+        // and the inverse sums N of them, so the error grows like √N
+        // (~0.6·√N rms, peaks near 5·√N at N=1024)
+        let tolerance = N as i32 / 4;
+        for n in 0..N {
+            let err = (re[n] as i32 - original[n] as i32).abs();
+            assert!(err <= tolerance, "N={} sample {}: error {}", N, n, err);
+            assert!((im[n] as i32).abs() <= tolerance, "N={} imag {}", N, im[n]);
+        }
+    }
+
+    #[test]
+    fn fft_ifft_round_trip() {
+        round_trip::<256>();
+        round_trip::<512>();
+        round_trip::<1024>();
+    }
+}
@@ -0,0 +1,203 @@
+//! Windowed-sinc FIR coefficient design.
+//!
+//! Generates Q15 kernels for [`AudioFilterFIR`](crate::nodes::AudioFilterFIR)
+//! at setup time, so sketches don't need an external filter designer for
+//! the common cases. Uses floating point; don't call from the audio ISR.
+
+use crate::constants::AUDIO_SAMPLE_RATE_EXACT;
+
+/// Lowest cutoff accepted, in Hz. Below this the kernel is just the window.
+const MIN_CUTOFF_HZ: f32 = 1.0;
+
+/// Highest cutoff accepted, as a fraction of the sample rate: just below
+/// Nyquist, where the sinc would alias onto itself.
+const MAX_CUTOFF: f32 = 0.499;
+
+/// Fill `out` with a lowpass kernel of `taps` coefficients cutting off at
+/// `cutoff_hz`.
+///
+/// The ideal `sin(x)/x` response is shaped by a Hamming window and scaled
+/// to unity gain at DC. The cutoff is clamped to 1 Hz – just below
+/// Nyquist. With an odd `taps` the kernel is centred on its middle tap;
+/// with an even one, between the middle two. At most `out.len()` taps are
+/// written and any of `out` beyond them is zeroed, so a short kernel can
+/// fill a longer [`AudioFilterFIR`](crate::nodes::AudioFilterFIR).
+///
+/// # Example
+/// ```ignore
+/// let mut kernel = [0i16; 63];
+/// fir_design::lowpass(2000.0, 63, &mut kernel);
+/// fir.set_coefficients(&kernel);
+/// ```
+pub fn lowpass(cutoff_hz: f32, taps: usize, out: &mut [i16]) {
+    let taps = taps.min(out.len());
+    let lp = Kernel::lowpass(cutoff_hz, taps);
+    write(out, taps, |n| lp.at(n));
+}
+
+/// Fill `out` with a highpass kernel of `taps` coefficients cutting off at
+/// `cutoff_hz`: a unit impulse minus the matching [`lowpass`].
+///
+/// An even-length symmetric kernel always has a zero at Nyquist, so with
+/// an even `taps` only the first `taps - 1` are used and the last is zero.
+pub fn highpass(cutoff_hz: f32, taps: usize, out: &mut [i16]) {
+    let taps = taps.min(out.len());
+    let used = if taps.is_multiple_of(2) {
+        taps.saturating_sub(1)
+    } else {
+        taps
+    };
+    let lp = Kernel::lowpass(cutoff_hz, used);
+    let centre = used / 2;
+    write(out, used, |n| {
+        let impulse = if n == centre { 1.0 } else { 0.0 };
+        impulse - lp.at(n)
+    });
+}
+
+/// Fill `out` with a bandpass kernel of `taps` coefficients passing
+/// `low_hz` to `high_hz`: the difference of two [`lowpass`] kernels.
+///
+/// The edges are swapped if given in the wrong order.
+pub fn bandpass(low_hz: f32, high_hz: f32, taps: usize, out: &mut [i16]) {
+    let taps = taps.min(out.len());
+    let (low_hz, high_hz) = if low_hz <= high_hz {
+        (low_hz, high_hz)
+    } else {
+        (high_hz, low_hz)
+    };
+    let low = Kernel::lowpass(low_hz, taps);
+    let high = Kernel::lowpass(high_hz, taps);
+    write(out, taps, |n| high.at(n) - low.at(n));
+}
+
+/// A windowed-sinc lowpass, evaluated tap by tap.
+struct Kernel {
+    /// Cutoff as a fraction of the sample rate.
+    fc: f32,
+    taps: usize,
+    /// Reciprocal of the sum of the unnormalized taps (the DC gain).
+    scale: f32,
+}
+
+impl Kernel {
+    fn lowpass(cutoff_hz: f32, taps: usize) -> Self {
+        let fc = (cutoff_hz.max(MIN_CUTOFF_HZ) / AUDIO_SAMPLE_RATE_EXACT).min(MAX_CUTOFF);
+        let mut kernel = Kernel {
+            fc,
+            taps,
+            scale: 1.0,
+        };
+        let gain: f32 = (0..taps).map(|n| kernel.at(n)).sum();
+        kernel.scale = 1.0 / gain;
+        kernel
+    }
+
+    /// Tap `n`, as a fraction of unity.
+    fn at(&self, n: usize) -> f32 {
+        use core::f32::consts::PI;
+        if self.taps < 2 {
+            return self.scale;
+        }
+        let middle = (self.taps - 1) as f32;
+        let x = n as f32 - middle / 2.0;
+        let sinc = if x == 0.0 {
+            2.0 * self.fc
+        } else {
+            libm::sinf(2.0 * PI * self.fc * x) / (PI * x)
+        };
+        let hamming = 0.54 - 0.46 * libm::cosf(2.0 * PI * n as f32 / middle);
+        sinc * hamming * self.scale
+    }
+}
+
+/// Quantize `tap(n)` for the first `taps` entries of `out` to Q15 and zero
+/// the rest.
+fn write(out: &mut [i16], taps: usize, tap: impl Fn(usize) -> f32) {
+    for (n, dst) in out.iter_mut().enumerate() {
+        *dst = if n < taps {
+            libm::roundf(tap(n) * 32768.0).clamp(-32768.0, 32767.0) as i16
+        } else {
+            0
+        };
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::block::AudioBlockMut;
+    use crate::node::AudioNode;
+    use crate::nodes::{AudioFilterFIR, AudioSynthSine};
+
+    fn sum(kernel: &[i16]) -> i32 {
+        kernel.iter().map(|&h| h as i32).sum()
+    }
+
+    #[test]
+    fn fir_design_dc_gain() {
+        // Each tap rounds by at most half an LSB
+        for taps in [63, 64] {
+            let mut h = [0i16; 64];
+            lowpass(2000.0, taps, &mut h);
+            assert!((sum(&h) - 32768).abs() <= 16, "{} taps: {}", taps, sum(&h));
+            // Linear phase: symmetric about the centre
+            for n in 0..taps {
+                assert_eq!(h[n], h[taps - 1 - n], "{} taps", taps);
+            }
+        }
+
+        let mut h = [0i16; 64];
+        lowpass(30000.0, 63, &mut h);
+        assert_eq!(h[63], 0, "taps beyond the kernel are zeroed");
+        assert!((sum(&h) - 32768).abs() <= 16, "clamped cutoff: {}", sum(&h));
+
+        highpass(2000.0, 64, &mut h);
+        assert_eq!(h[63], 0, "even highpass drops a tap");
+        assert!(sum(&h).abs() <= 16, "highpass DC gain {}", sum(&h));
+        bandpass(4000.0, 1000.0, 64, &mut h);
+        assert!(sum(&h).abs() <= 16, "bandpass DC gain {}", sum(&h));
+    }
+
+    /// Peak output of `fir` for a sine at `hz`, once the kernel is full.
+    fn peak(fir: &mut AudioFilterFIR<63>, hz: f32) -> i16 {
+        let mut sine = AudioSynthSine::new();
+        sine.frequency(hz);
+        sine.amplitude(1.0);
+        let mut peak = 0;
+        for n in 0..4 {
+            let mut src = [AudioBlockMut::alloc()];
+            sine.update(&[], &mut src);
+            let inputs = [src[0].take().map(|b| b.into_shared())];
+            let mut outputs = [AudioBlockMut::alloc()];
+            fir.update(&inputs, &mut outputs);
+            if n > 0 {
+                let out = outputs[0].take().unwrap();
+                peak = peak.max(out.iter().map(|&s| s.saturating_abs()).max().unwrap());
+            }
+        }
+        peak
+    }
+
+    #[test]
+    fn fir_design_lowpass_attenuates_high_tone() {
+        POOL.reset();
+        let mut h = [0i16; 63];
+        let mut fir: AudioFilterFIR<63> = AudioFilterFIR::new();
+
+        lowpass(2000.0, 63, &mut h);
+        fir.set_coefficients(&h);
+        let pass = peak(&mut fir, 500.0);
+        let stop = peak(&mut fir, 12000.0);
+        assert!(pass > 31000, "passband {}", pass);
+        // Hamming stopband is ~-53 dB
+        assert!(stop < 330, "stopband {}", stop);
+
+        highpass(2000.0, 63, &mut h);
+        fir.set_coefficients(&h);
+        assert!(peak(&mut fir, 12000.0) > 31000);
+        assert!(peak(&mut fir, 200.0) < 330);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+}
@@ -1,8 +1,20 @@
 //! Block-level DSP helper functions and Q15 arithmetic.
 
-use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::constants::{AUDIO_BLOCK_SAMPLES, AUDIO_SAMPLE_RATE_EXACT};
 use super::intrinsics::saturate16;
 
+/// Convert a frequency in Hz to a 32-bit phase-accumulator increment.
+///
+/// One full cycle is 2^32, so the increment is
+/// `hz / AUDIO_SAMPLE_RATE_EXACT * 2^32` (about 97,353 per Hz). Negative
+/// frequencies give 0. Being a `const fn`, tuning tables can be computed at
+/// compile time and handed to raw setters such as
+/// `AudioSynthSine::phase_increment_raw()` from an ISR without touching
+/// the FPU.
+pub const fn freq_to_increment(hz: f32) -> u32 {
+    (hz * (4_294_967_296.0 / AUDIO_SAMPLE_RATE_EXACT)) as u32
+}
+
 /// Saturating multiply of two Q15 values.
 ///
 /// Computes `(a * b) >> 15`, saturated to `i16` range.
@@ -36,6 +48,20 @@ pub fn block_accumulate(
     }
 }
 
+/// Sum of squared samples over one block.
+///
+/// Each square is at most `32768²` (2³⁰), so a full block of 128 squares
+/// stays below 2³⁷ and cannot overflow the `u64` result. Callers that
+/// accumulate across many blocks should use `saturating_add`.
+pub fn block_sum_squares(block: &[i16; AUDIO_BLOCK_SAMPLES]) -> u64 {
+    let mut sum: u64 = 0;
+    for &s in block.iter() {
+        let s = s as i32;
+        sum += (s * s) as u32 as u64;
+    }
+    sum
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -88,4 +114,22 @@ mod tests {
         assert_eq!(dst[0], 300);
         assert_eq!(dst[1], 32767); // saturated
     }
+
+    #[test]
+    fn test_block_sum_squares_full_scale() {
+        let expected = AUDIO_BLOCK_SAMPLES as u64 * 32767 * 32767;
+        assert_eq!(block_sum_squares(&[32767i16; AUDIO_BLOCK_SAMPLES]), expected);
+        assert_eq!(block_sum_squares(&[-32767i16; AUDIO_BLOCK_SAMPLES]), expected);
+
+        let mut alternating = [0i16; AUDIO_BLOCK_SAMPLES];
+        for (i, s) in alternating.iter_mut().enumerate() {
+            *s = if i % 2 == 0 { 32767 } else { -32767 };
+        }
+        assert_eq!(block_sum_squares(&alternating), expected);
+
+        // -32768² = 2³⁰ fits in i32, but 128 of them (2³⁷) only fit the
+        // widened sum.
+        let min = block_sum_squares(&[i16::MIN; AUDIO_BLOCK_SAMPLES]);
+        assert_eq!(min, AUDIO_BLOCK_SAMPLES as u64 * 32768 * 32768);
+    }
 }
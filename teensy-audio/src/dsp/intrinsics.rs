@@ -4,6 +4,39 @@
 //! single-cycle ARM instructions. On other targets (host tests, Cortex-M0),
 //! equivalent pure-Rust implementations are used.
 
+/// Whether this build uses the DSP instructions rather than the fallbacks.
+///
+/// `false` on host builds and Cortex-M0, but also on a Cortex-M4/M7 target
+/// built without `target_feature = "dsp"` (e.g. plain `thumbv7m`), where
+/// everything still works, just slower.
+pub const HAS_HARDWARE_DSP: bool = cfg!(all(target_arch = "arm", target_feature = "dsp"));
+
+/// [`HAS_HARDWARE_DSP`], for reporting at runtime (e.g. from a
+/// diagnostics node).
+#[inline(always)]
+pub const fn has_hardware_dsp() -> bool {
+    HAS_HARDWARE_DSP
+}
+
+/// Fail the build unless the DSP instructions are in use. Here they are.
+#[cfg(all(target_arch = "arm", target_feature = "dsp"))]
+pub const fn assert_hardware_dsp() {}
+
+/// Fail the build unless the DSP instructions are in use.
+///
+/// Evaluate it in a const item so a misconfigured target is caught at
+/// compile time rather than as a slow audio loop:
+///
+/// ```ignore
+/// const _: () = teensy_audio::dsp::intrinsics::assert_hardware_dsp();
+/// ```
+///
+/// Called at runtime instead, it panics.
+#[cfg(not(all(target_arch = "arm", target_feature = "dsp")))]
+pub const fn assert_hardware_dsp() {
+    panic!("DSP intrinsics use the pure-Rust fallbacks: build with target_feature = \"dsp\"");
+}
+
 /// Signed saturate with arithmetic right shift.
 ///
 /// Computes `saturate(val >> RSHIFT, -(2^(BITS-1))..2^(BITS-1)-1)`.
@@ -346,6 +379,64 @@ pub fn qsub16(a: u32, b: u32) -> u32 {
     }
 }
 
+/// Saturating quad 8-bit addition.
+///
+/// Independently saturate-adds each of the four signed bytes.
+/// Maps to ARM `QADD8`.
+#[inline(always)]
+pub fn qadd8(a: u32, b: u32) -> u32 {
+    #[cfg(all(target_arch = "arm", target_feature = "dsp"))]
+    {
+        let out: u32;
+        unsafe {
+            core::arch::asm!(
+                "qadd8 {out}, {a}, {b}",
+                out = out(reg) out,
+                a = in(reg) a,
+                b = in(reg) b,
+            );
+        }
+        out
+    }
+    #[cfg(not(all(target_arch = "arm", target_feature = "dsp")))]
+    {
+        let lane = |shift: u32| {
+            let r = ((a >> shift) as i8 as i32 + (b >> shift) as i8 as i32).clamp(-128, 127);
+            (r as i8 as u8 as u32) << shift
+        };
+        lane(24) | lane(16) | lane(8) | lane(0)
+    }
+}
+
+/// Saturating quad 8-bit subtraction.
+///
+/// Independently saturate-subtracts each of the four signed bytes.
+/// Maps to ARM `QSUB8`.
+#[inline(always)]
+pub fn qsub8(a: u32, b: u32) -> u32 {
+    #[cfg(all(target_arch = "arm", target_feature = "dsp"))]
+    {
+        let out: u32;
+        unsafe {
+            core::arch::asm!(
+                "qsub8 {out}, {a}, {b}",
+                out = out(reg) out,
+                a = in(reg) a,
+                b = in(reg) b,
+            );
+        }
+        out
+    }
+    #[cfg(not(all(target_arch = "arm", target_feature = "dsp")))]
+    {
+        let lane = |shift: u32| {
+            let r = ((a >> shift) as i8 as i32 - (b >> shift) as i8 as i32).clamp(-128, 127);
+            (r as i8 as u8 as u32) << shift
+        };
+        lane(24) | lane(16) | lane(8) | lane(0)
+    }
+}
+
 /// Multiply bottom halfwords: `a[15:0] * b[15:0]`. Maps to ARM `SMULBB`.
 #[inline(always)]
 pub fn mul_16bx16b(a: u32, b: u32) -> i32 {
@@ -480,10 +571,79 @@ pub fn multiply_accumulate_32x16t(sum: i32, a: i32, b: u32) -> i32 {
     }
 }
 
+/// Dual 16-bit multiply-add: `a[15:0] * b[15:0] + a[31:16] * b[31:16]`.
+/// Maps to ARM `SMUAD`.
+///
+/// The sum only overflows when all four halfwords are -32768; it then
+/// wraps, as on hardware.
+#[inline(always)]
+pub fn dual_mul_add(a: u32, b: u32) -> i32 {
+    #[cfg(all(target_arch = "arm", target_feature = "dsp"))]
+    {
+        let out: i32;
+        unsafe {
+            core::arch::asm!(
+                "smuad {out}, {a}, {b}",
+                out = out(reg) out,
+                a = in(reg) a,
+                b = in(reg) b,
+            );
+        }
+        out
+    }
+    #[cfg(not(all(target_arch = "arm", target_feature = "dsp")))]
+    {
+        let lo = (a as i16 as i32) * (b as i16 as i32);
+        let hi = ((a >> 16) as i16 as i32) * ((b >> 16) as i16 as i32);
+        lo.wrapping_add(hi)
+    }
+}
+
+/// Dual 16-bit multiply-accumulate:
+/// `sum + a[15:0] * b[15:0] + a[31:16] * b[31:16]`. Maps to ARM `SMLAD`.
+///
+/// Two taps per instruction for FIR and biquad inner loops, with both
+/// operands packed by [`pack_16b_16b`] and friends. Wraps on overflow.
+#[inline(always)]
+pub fn dual_mac(sum: i32, a: u32, b: u32) -> i32 {
+    #[cfg(all(target_arch = "arm", target_feature = "dsp"))]
+    {
+        let out: i32;
+        unsafe {
+            core::arch::asm!(
+                "smlad {out}, {a}, {b}, {sum}",
+                out = out(reg) out,
+                a = in(reg) a,
+                b = in(reg) b,
+                sum = in(reg) sum,
+            );
+        }
+        out
+    }
+    #[cfg(not(all(target_arch = "arm", target_feature = "dsp")))]
+    {
+        let lo = (a as i16 as i32) * (b as i16 as i32);
+        let hi = ((a >> 16) as i16 as i32) * ((b >> 16) as i16 as i32);
+        sum.wrapping_add(lo).wrapping_add(hi)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn test_no_hardware_dsp_on_host() {
+        const { assert!(!HAS_HARDWARE_DSP) };
+        assert!(!has_hardware_dsp());
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_assert_hardware_dsp_on_host() {
+        assert_hardware_dsp();
+    }
+
     #[test]
     fn test_saturate16() {
         assert_eq!(saturate16(0), 0);
@@ -572,6 +732,31 @@ mod tests {
         assert_eq!((result >> 16) as i16, 7i16); // top: 10-3
     }
 
+    #[test]
+    fn test_qadd8() {
+        // Bytes, low to high: (1, 2, 3, 4) + (10, 20, 30, 40)
+        let result = qadd8(0x0403_0201, 0x281E_140A);
+        assert_eq!(result.to_le_bytes(), [11, 22, 33, 44]);
+    }
+
+    #[test]
+    fn test_qadd8_saturation() {
+        // Each lane saturates on its own: 127+1, -128+-1, 100+27, -100+-28
+        let a = u32::from_le_bytes([127, -128i8 as u8, 100, -100i8 as u8]);
+        let b = u32::from_le_bytes([1, -1i8 as u8, 27, -28i8 as u8]);
+        let result = qadd8(a, b).to_le_bytes().map(|x| x as i8);
+        assert_eq!(result, [127, -128, 127, -128]);
+    }
+
+    #[test]
+    fn test_qsub8_saturation() {
+        // -128-1 and 127-(-1) saturate; the others land exactly on the limits
+        let a = u32::from_le_bytes([-128i8 as u8, 127, 0, -1i8 as u8]);
+        let b = u32::from_le_bytes([1, -1i8 as u8, -127i8 as u8, 127]);
+        let result = qsub8(a, b).to_le_bytes().map(|x| x as i8);
+        assert_eq!(result, [-128, 127, 127, -128]);
+    }
+
     #[test]
     fn test_mul_16bx16b() {
         // 3 * 4 = 12
@@ -628,4 +813,28 @@ mod tests {
         // 10 + (0x10000 * 5) >> 16 = 10 + 5 = 15
         assert_eq!(result, 15);
     }
+
+    #[test]
+    fn test_dual_mul_add() {
+        // (3 * 5) + (-2 * 7) = 1, bottom and top halfwords
+        let a = pack_16b_16b(-2, 3);
+        let b = pack_16b_16b(7, 5);
+        assert_eq!(dual_mul_add(a, b), 1);
+        // Full-scale negative products in both lanes
+        let a = pack_16b_16b(32767, -32768);
+        let b = pack_16b_16b(-32768, 32767);
+        assert_eq!(dual_mul_add(a, b), -32767 * 32768 - 32768 * 32767);
+    }
+
+    #[test]
+    fn test_dual_mac() {
+        // 100 + (3 * 5) + (-2 * 7) = 101
+        let a = pack_16b_16b(-2, 3);
+        let b = pack_16b_16b(7, 5);
+        assert_eq!(dual_mac(100, a, b), 101);
+        // Two Q15 taps (0.5, 0.25) against two samples
+        let taps = pack_16b_16b(16384, 8192);
+        let samples = pack_16b_16b(1000, -4000);
+        assert_eq!(dual_mac(0, samples, taps) >> 15, 1000 / 2 - 4000 / 4);
+    }
 }
@@ -1,3 +1,12 @@
 pub mod intrinsics;
 pub mod helpers;
+pub mod dc_blocker;
+pub mod fft;
+pub mod fir_design;
+pub mod music;
+pub mod one_pole;
 pub mod wavetables;
+
+pub use dc_blocker::DcBlocker;
+pub use helpers::{block_sum_squares, freq_to_increment};
+pub use one_pole::OnePole;
@@ -0,0 +1,165 @@
+//! Musical unit conversions: pitch ↔ MIDI note, note values ↔ time.
+//!
+//! Used by analyzers and effects that report or accept musical values, so
+//! tuner and sequencer code doesn't reimplement the math.
+
+use crate::constants::ms_to_samples;
+
+/// Frequency of MIDI note 69 (A4) in Hz.
+pub const A4_FREQUENCY: f32 = 440.0;
+
+/// MIDI note number of A4.
+pub const A4_MIDI_NOTE: u8 = 69;
+
+/// Convert a frequency to the nearest MIDI note and its deviation in cents.
+///
+/// Uses 12-tone equal temperament with A4 = 440 Hz. Cents are in
+/// [-50.0, 50.0]; positive means the frequency is sharp of the note.
+/// Returns `None` for non-positive or non-finite frequencies and for
+/// frequencies whose nearest note is outside MIDI range 0–127.
+pub fn frequency_to_midi_note(hz: f32) -> Option<(u8, f32)> {
+    if !hz.is_finite() || hz <= 0.0 {
+        return None;
+    }
+    let note = A4_MIDI_NOTE as f32 + 12.0 * libm::log2f(hz / A4_FREQUENCY);
+    let nearest = libm::roundf(note);
+    if !(0.0..=127.0).contains(&nearest) {
+        return None;
+    }
+    Some((nearest as u8, (note - nearest) * 100.0))
+}
+
+/// Frequency in Hz of a MIDI note (12-TET, A4 = 440 Hz).
+pub fn midi_note_to_frequency(note: u8) -> f32 {
+    A4_FREQUENCY * libm::exp2f((note as f32 - A4_MIDI_NOTE as f32) / 12.0)
+}
+
+/// Base note length, in quarter-note beats as written in 4/4.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteValue {
+    /// 4 beats.
+    Whole,
+    /// 2 beats.
+    Half,
+    /// 1 beat.
+    Quarter,
+    /// 1/2 beat.
+    Eighth,
+    /// 1/4 beat.
+    Sixteenth,
+    /// 1/8 beat.
+    ThirtySecond,
+}
+
+impl NoteValue {
+    /// Length in quarter-note beats.
+    pub fn beats(self) -> f32 {
+        match self {
+            NoteValue::Whole => 4.0,
+            NoteValue::Half => 2.0,
+            NoteValue::Quarter => 1.0,
+            NoteValue::Eighth => 0.5,
+            NoteValue::Sixteenth => 0.25,
+            NoteValue::ThirtySecond => 0.125,
+        }
+    }
+}
+
+/// A note value with an optional dotted or triplet modifier, for
+/// tempo-synced times (delay taps, LFO rates).
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum NoteDivision {
+    /// Plain note value.
+    Straight(NoteValue),
+    /// Dotted: 1.5 × the note value.
+    Dotted(NoteValue),
+    /// Triplet: 2/3 × the note value.
+    Triplet(NoteValue),
+}
+
+impl NoteDivision {
+    /// Length in quarter-note beats.
+    pub fn beats(self) -> f32 {
+        match self {
+            NoteDivision::Straight(v) => v.beats(),
+            NoteDivision::Dotted(v) => v.beats() * 1.5,
+            NoteDivision::Triplet(v) => v.beats() * (2.0 / 3.0),
+        }
+    }
+
+    /// Duration in milliseconds at `bpm` quarter-note beats per minute.
+    ///
+    /// Returns 0.0 for a non-positive tempo.
+    pub fn milliseconds(self, bpm: f32) -> f32 {
+        if bpm <= 0.0 {
+            return 0.0;
+        }
+        self.beats() * 60_000.0 / bpm
+    }
+
+    /// Duration in samples at `bpm`, using `AUDIO_SAMPLE_RATE_EXACT`.
+    pub fn samples(self, bpm: f32) -> u32 {
+        ms_to_samples(self.milliseconds(bpm))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::constants::AUDIO_SAMPLE_RATE_EXACT;
+
+    #[test]
+    fn a440_is_note_69() {
+        let (note, cents) = frequency_to_midi_note(440.0).unwrap();
+        assert_eq!(note, 69);
+        assert!(cents.abs() < 0.01, "cents = {}", cents);
+    }
+
+    #[test]
+    fn detuned_reports_cents() {
+        // +10 cents above A4
+        let (note, cents) = frequency_to_midi_note(440.0 * libm::exp2f(10.0 / 1200.0)).unwrap();
+        assert_eq!(note, 69);
+        assert!((cents - 10.0).abs() < 0.05, "cents = {}", cents);
+
+        // 30 cents flat of C4 (261.63 Hz)
+        let c4 = midi_note_to_frequency(60);
+        let (note, cents) = frequency_to_midi_note(c4 * libm::exp2f(-30.0 / 1200.0)).unwrap();
+        assert_eq!(note, 60);
+        assert!((cents + 30.0).abs() < 0.05, "cents = {}", cents);
+    }
+
+    #[test]
+    fn rounds_to_nearest_note() {
+        // 60 cents sharp of A4 is 40 cents flat of A#4
+        let (note, cents) = frequency_to_midi_note(440.0 * libm::exp2f(60.0 / 1200.0)).unwrap();
+        assert_eq!(note, 70);
+        assert!((cents + 40.0).abs() < 0.05, "cents = {}", cents);
+    }
+
+    #[test]
+    fn out_of_range_is_none() {
+        assert_eq!(frequency_to_midi_note(0.0), None);
+        assert_eq!(frequency_to_midi_note(-440.0), None);
+        assert_eq!(frequency_to_midi_note(f32::NAN), None);
+        assert_eq!(frequency_to_midi_note(1.0), None);
+        assert_eq!(frequency_to_midi_note(20_000.0), None);
+    }
+
+    #[test]
+    fn quarter_at_120_bpm_is_500ms() {
+        let q = NoteDivision::Straight(NoteValue::Quarter);
+        assert!((q.milliseconds(120.0) - 500.0).abs() < 1e-3);
+        let expected = (AUDIO_SAMPLE_RATE_EXACT * 0.5) as i64;
+        assert!((q.samples(120.0) as i64 - expected).abs() <= 1);
+    }
+
+    #[test]
+    fn dotted_and_triplet_divisions() {
+        let dotted_eighth = NoteDivision::Dotted(NoteValue::Eighth);
+        assert!((dotted_eighth.milliseconds(120.0) - 375.0).abs() < 1e-3);
+        let quarter_triplet = NoteDivision::Triplet(NoteValue::Quarter);
+        assert!((quarter_triplet.milliseconds(120.0) - 333.333).abs() < 1e-2);
+        assert_eq!(NoteDivision::Straight(NoteValue::Whole).milliseconds(0.0), 0.0);
+    }
+}
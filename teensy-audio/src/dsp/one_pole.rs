@@ -0,0 +1,168 @@
+//! One-pole filter primitive.
+//!
+//! Shared by nodes that need a cheap first-order smoother or DC blocker
+//! (damping, envelope following, parameter smoothing) so each doesn't carry
+//! its own copy of the recurrence.
+
+use super::intrinsics::{mul_32x32_rshift32, saturate16};
+use crate::constants::AUDIO_SAMPLE_RATE_EXACT;
+
+/// Filter response selected by [`OnePole::set_lowpass`] /
+/// [`OnePole::set_highpass`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+enum Mode {
+    Lowpass,
+    Highpass,
+}
+
+/// Fixed-point one-pole filter.
+///
+/// The lowpass is `y[n] = y[n-1] + a·(x[n] − y[n-1])` with
+/// `a = 1 − e^(−2π·fc/fs)`; the highpass is the input minus that lowpass,
+/// so it removes DC completely. `a` is held in Q31 and the state carries
+/// 15 fractional bits below the sample, so very low cutoffs still settle
+/// instead of stalling on truncation.
+///
+/// # Example
+/// ```ignore
+/// let mut dc_block = OnePole::new();
+/// dc_block.set_highpass(10.0);
+/// let y = dc_block.process(x);
+/// ```
+#[derive(Clone, Debug)]
+pub struct OnePole {
+    /// Smoothing coefficient `a` in Q31.
+    coefficient: i32,
+    /// Lowpass state, sample scaled by 2^15.
+    state: i32,
+    mode: Mode,
+}
+
+impl OnePole {
+    /// Create a filter that passes the input through unchanged
+    /// (lowpass with `a = 1`).
+    pub const fn new() -> Self {
+        OnePole {
+            coefficient: i32::MAX,
+            state: 0,
+            mode: Mode::Lowpass,
+        }
+    }
+
+    /// Configure as a lowpass with a -3 dB point at `cutoff` Hz.
+    pub fn set_lowpass(&mut self, cutoff: f32) {
+        self.coefficient = cutoff_to_coefficient(cutoff);
+        self.mode = Mode::Lowpass;
+    }
+
+    /// Configure as a highpass with a -3 dB point at `cutoff` Hz.
+    pub fn set_highpass(&mut self, cutoff: f32) {
+        self.coefficient = cutoff_to_coefficient(cutoff);
+        self.mode = Mode::Highpass;
+    }
+
+    /// Clear the filter state.
+    pub fn reset(&mut self) {
+        self.state = 0;
+    }
+
+    /// Filter one sample.
+    #[inline]
+    pub fn process(&mut self, x: i16) -> i16 {
+        let input = (x as i32) << 15;
+        // Both operands fit in 16.15, so the difference fits in an i32
+        let delta = input - self.state;
+        self.state += mul_32x32_rshift32(delta, self.coefficient) << 1;
+        match self.mode {
+            Mode::Lowpass => saturate16((self.state + 0x4000) >> 15),
+            Mode::Highpass => saturate16((input - self.state + 0x4000) >> 15),
+        }
+    }
+}
+
+impl Default for OnePole {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `1 − e^(−2π·fc/fs)` in Q31, clamped to (0, 1].
+fn cutoff_to_coefficient(cutoff: f32) -> i32 {
+    let w = 2.0 * core::f32::consts::PI * cutoff.max(0.0) / AUDIO_SAMPLE_RATE_EXACT;
+    let a = 1.0 - libm::expf(-w);
+    // f32→i32 casts saturate, so a == 1.0 lands on i32::MAX
+    ((a * 2_147_483_648.0) as i32).max(1)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn one_pole_default_passes_through() {
+        let mut f = OnePole::new();
+        for &x in &[0i16, 1000, -20000, 32767, -32768] {
+            let y = f.process(x);
+            assert!((y as i32 - x as i32).abs() <= 1, "{} -> {}", x, y);
+        }
+    }
+
+    #[test]
+    fn one_pole_lowpass_step_response() {
+        let cutoff = 100.0;
+        let mut f = OnePole::new();
+        f.set_lowpass(cutoff);
+
+        // One time constant is 1/(2π·fc) seconds
+        let tau = AUDIO_SAMPLE_RATE_EXACT / (2.0 * core::f32::consts::PI * cutoff);
+        let tau = libm::roundf(tau) as usize;
+
+        let step = 20000i16;
+        let mut prev = 0;
+        let mut at_tau = 0;
+        for n in 1..=tau * 10 {
+            let y = f.process(step);
+            assert!(y >= prev && y <= step, "not monotonic at {}: {}", n, y);
+            prev = y;
+            if n == tau {
+                at_tau = y;
+            }
+        }
+
+        // 1 − 1/e ≈ 63.2% after one time constant
+        let expected = step as f32 * 0.632;
+        assert!(
+            (at_tau as f32 - expected).abs() < step as f32 * 0.02,
+            "at tau: {} (expected ~{})",
+            at_tau,
+            expected
+        );
+        // Ten time constants in, within 0.01%
+        assert!(prev >= step - 2, "settled at {}", prev);
+    }
+
+    #[test]
+    fn one_pole_highpass_removes_dc() {
+        let mut f = OnePole::new();
+        f.set_highpass(20.0);
+
+        let first = f.process(10000);
+        assert!(first > 9900, "highpass should pass the edge, got {}", first);
+        let mut y = first;
+        for _ in 0..44100 {
+            y = f.process(10000);
+        }
+        assert!(y.abs() <= 1, "DC should be removed, got {}", y);
+    }
+
+    #[test]
+    fn one_pole_low_cutoff_still_settles() {
+        let mut f = OnePole::new();
+        f.set_lowpass(1.0);
+        let mut y = 0;
+        for _ in 0..44100 * 2 {
+            y = f.process(100);
+        }
+        assert!(y >= 99, "settled at {}", y);
+    }
+}
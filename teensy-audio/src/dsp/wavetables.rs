@@ -1,7 +1,8 @@
-//! Pre-computed wavetables for audio synthesis and crossfading.
+//! Pre-computed wavetables for audio synthesis, crossfading and saturation.
 //!
-//! All tables have 257 entries (256-point period + wraparound duplicate for
-//! linear interpolation).
+//! All tables have 257 entries (256 intervals plus a final endpoint, so linear
+//! interpolation never reads past the end; for periodic tables the endpoint
+//! duplicates entry 0).
 
 /// 257-point sine wavetable, Q15 format.
 ///
@@ -70,6 +71,97 @@ pub static FADER_TABLE: [i16; 257] = [
     32723,32737,32748,32756,32763,32766,32767,
 ];
 
+/// 257-point hyperbolic tangent table, Q15 format.
+///
+/// Generated as `tanh((i - 128) / 32) * 32767`, rounded half away from zero,
+/// so it spans `x` in [-4.0, 4.0] in steps of 1/32 and is exactly
+/// odd-symmetric about entry 128. Use [`tanh_lookup`] to read it.
+pub static TANH_TABLE: [i16; 257] = [
+    -32745,-32744,-32742,-32740,-32739,-32737,-32735,-32733,-32731,-32728,
+    -32726,-32723,-32720,-32717,-32714,-32711,-32707,-32703,-32699,-32695,
+    -32690,-32685,-32680,-32675,-32669,-32662,-32656,-32648,-32641,-32633,
+    -32624,-32615,-32605,-32595,-32583,-32572,-32559,-32546,-32531,-32516,
+    -32500,-32483,-32465,-32446,-32425,-32403,-32380,-32355,-32328,-32300,
+    -32270,-32239,-32205,-32169,-32131,-32090,-32047,-32001,-31952,-31900,
+    -31845,-31787,-31725,-31658,-31588,-31514,-31435,-31350,-31261,-31166,
+    -31066,-30959,-30846,-30726,-30599,-30464,-30321,-30169,-30009,-29839,
+    -29659,-29469,-29267,-29054,-28829,-28592,-28340,-28075,-27796,-27501,
+    -27190,-26863,-26518,-26156,-25775,-25375,-24955,-24515,-24053,-23570,
+    -23065,-22537,-21986,-21411,-20812,-20189,-19541,-18869,-18173,-17451,
+    -16706,-15936,-15142,-14325,-13486,-12625,-11742,-10840, -9919, -8980,
+     -8025, -7056, -6073, -5079, -4075, -3063, -2045, -1024,     0,  1024,
+      2045,  3063,  4075,  5079,  6073,  7056,  8025,  8980,  9919, 10840,
+     11742, 12625, 13486, 14325, 15142, 15936, 16706, 17451, 18173, 18869,
+     19541, 20189, 20812, 21411, 21986, 22537, 23065, 23570, 24053, 24515,
+     24955, 25375, 25775, 26156, 26518, 26863, 27190, 27501, 27796, 28075,
+     28340, 28592, 28829, 29054, 29267, 29469, 29659, 29839, 30009, 30169,
+     30321, 30464, 30599, 30726, 30846, 30959, 31066, 31166, 31261, 31350,
+     31435, 31514, 31588, 31658, 31725, 31787, 31845, 31900, 31952, 32001,
+     32047, 32090, 32131, 32169, 32205, 32239, 32270, 32300, 32328, 32355,
+     32380, 32403, 32425, 32446, 32465, 32483, 32500, 32516, 32531, 32546,
+     32559, 32572, 32583, 32595, 32605, 32615, 32624, 32633, 32641, 32648,
+     32656, 32662, 32669, 32675, 32680, 32685, 32690, 32695, 32699, 32703,
+     32707, 32711, 32714, 32717, 32720, 32723, 32726, 32728, 32731, 32733,
+     32735, 32737, 32739, 32740, 32742, 32744, 32745,
+];
+
+/// Largest input magnitude covered by [`TANH_TABLE`]: 4.0 in Q15.
+const TANH_INPUT_LIMIT: u32 = 4 << 15;
+
+/// Fast soft-saturation: `tanh(x)` for a Q15 input (`32768` = 1.0).
+///
+/// The argument is an `i32` so callers can pass gained-up signals beyond
+/// ±1.0. Inputs beyond ±4.0 clamp to the table ends (±0.9993). Uses
+/// linear interpolation between table entries and is computed on `|x|`
+/// so the result is exactly odd-symmetric.
+#[inline]
+pub fn tanh_lookup(x_q15: i32) -> i16 {
+    let mag = x_q15.unsigned_abs();
+    let y = if mag >= TANH_INPUT_LIMIT {
+        TANH_TABLE[256] as i32
+    } else {
+        // 1024 Q15 units per table step; positive half starts at entry 128.
+        let index = 128 + (mag >> 10) as usize;
+        let frac = (mag & 0x3FF) as i32;
+        let val1 = TANH_TABLE[index] as i32;
+        let val2 = TANH_TABLE[index + 1] as i32;
+        val1 + (((val2 - val1) * frac) >> 10)
+    };
+    if x_q15 < 0 {
+        -y as i16
+    } else {
+        y as i16
+    }
+}
+
+/// Sine of a 32-bit phase (`2^32` = one cycle) from [`SINE_TABLE`] with
+/// linear interpolation, as Q31 (the table value << 16).
+///
+/// The top 8 bits of `phase` pick the entry and the next 16 weight the
+/// interpolation.
+#[inline(always)]
+pub(crate) fn sine_lookup(phase: u32) -> i32 {
+    let index = (phase >> 24) as usize;
+    let val1 = SINE_TABLE[index] as i32;
+    let val2 = SINE_TABLE[index + 1] as i32;
+    let scale = ((phase >> 8) & 0xFFFF) as i32;
+    val1 * (0x10000 - scale) + val2 * scale
+}
+
+/// Fader gain at a 32-bit position (`u32::MAX` = fully up) from
+/// [`FADER_TABLE`] with linear interpolation, as Q15.
+///
+/// The top 8 bits of `pos` pick the entry and the next 16 weight the
+/// interpolation.
+#[inline(always)]
+pub(crate) fn fader_lookup(pos: u32) -> i32 {
+    let index = (pos >> 24) as usize;
+    let val1 = FADER_TABLE[index] as i32;
+    let val2 = FADER_TABLE[index + 1] as i32;
+    let scale = ((pos >> 8) & 0xFFFF) as i32;
+    (val1 * (0x10000 - scale) + val2 * scale) >> 16
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -102,6 +194,18 @@ mod tests {
         }
     }
 
+    #[test]
+    fn sine_lookup_entries_and_midpoints() {
+        assert_eq!(sine_lookup(0), 0);
+        assert_eq!(sine_lookup(0x4000_0000), 32767 << 16);
+        assert_eq!(sine_lookup(0xC000_0000), -32767 << 16);
+        // Halfway between entries 1 and 2
+        let mid = (SINE_TABLE[1] as i32 + SINE_TABLE[2] as i32) << 15;
+        assert_eq!(sine_lookup(0x0180_0000), mid);
+        // The last interval interpolates towards the guard entry
+        assert!(sine_lookup(u32::MAX) < 0 && sine_lookup(u32::MAX) > -(1 << 16));
+    }
+
     #[test]
     fn fader_table_length() {
         assert_eq!(FADER_TABLE.len(), 257);
@@ -131,4 +235,50 @@ mod tests {
         // At index 128 (midpoint), value should be approximately 16383 (half scale)
         assert_eq!(FADER_TABLE[128], 16383);
     }
+
+    #[test]
+    fn tanh_table_endpoints() {
+        assert_eq!(TANH_TABLE.len(), 257);
+        assert_eq!(TANH_TABLE[128], 0);
+        assert_eq!(TANH_TABLE[0], -TANH_TABLE[256]);
+    }
+
+    #[test]
+    fn tanh_lookup_matches_libm() {
+        let mut x = -6 * 32768;
+        while x <= 6 * 32768 {
+            let expected = libm::tanhf(x as f32 / 32768.0).clamp(-0.9994, 0.9994);
+            let got = tanh_lookup(x) as f32 / 32767.0;
+            assert!(
+                (got - expected).abs() < 3e-4,
+                "tanh({}) = {}, expected {}",
+                x as f32 / 32768.0,
+                got,
+                expected
+            );
+            x += 37;
+        }
+    }
+
+    #[test]
+    fn tanh_lookup_monotonic_and_odd() {
+        let mut prev = tanh_lookup(-5 * 32768);
+        for x in (-5 * 32768 + 1)..=(5 * 32768) {
+            let y = tanh_lookup(x);
+            assert!(y >= prev, "not monotonic at {}: {} < {}", x, y, prev);
+            assert_eq!(tanh_lookup(-x), -y, "not odd-symmetric at {}", x);
+            prev = y;
+        }
+        assert_eq!(tanh_lookup(0), 0);
+        assert_eq!(tanh_lookup(i32::MIN), -TANH_TABLE[256]);
+    }
+
+    #[test]
+    fn fader_lookup_endpoints() {
+        // Position 0 → gain 0
+        assert_eq!(fader_lookup(0), 0);
+        // Position MAX → gain ~32767
+        let gain = fader_lookup(u32::MAX);
+        assert!(gain >= 32766, "expected ~32767, got {}", gain);
+    }
 }
@@ -30,31 +30,232 @@
 //! - `{ (node, 0), _ }` — input 0 connected, input 1 unconnected (silence)
 //! - `{ (a, 0), (b, 0) }` — two inputs from different sources
 //! - `{ (mixer, 0), (mixer, 0) }` — fan-out: same output to two inputs
+//! - `{ (filter.lowpass) }` — input 0 connected to the output port named
+//!   `lowpass` in the source type's [`AudioNode::OUTPUT_NAMES`]; the name is
+//!   resolved at compile time and an unknown name fails the build. Stereo
+//!   sources such as `AudioInputI2S` name their outputs `left` / `right`
+//! - `{ (echo, 0) @delayed }` — feedback: input 0 reads `echo`'s output from
+//!   the *previous* cycle, so `echo` may be declared later (see below)
+//!
+//! [`AudioNode::OUTPUT_NAMES`]: crate::node::AudioNode::OUTPUT_NAMES
+//!
+//! Numeric ports must be constant expressions and are checked against the
+//! source's [`AudioNode::NUM_OUTPUTS`], so wiring past the last output
+//! fails to compile:
+//!
+//! ```compile_fail
+//! use teensy_audio::audio_graph;
+//! use teensy_audio::nodes::*;
+//!
+//! audio_graph! {
+//!     pub struct Broken {
+//!         sine: AudioSynthSine {},
+//!         amp:  AudioAmplifier { (sine, 1) }, // sine has only output 0
+//!     }
+//! }
+//! ```
+//!
+//! [`AudioNode::NUM_OUTPUTS`]: crate::node::AudioNode::NUM_OUTPUTS
+//!
+//! Likewise each `{ ... }` list must have exactly one entry (`_` included)
+//! per input in the node type's [`AudioNode::NUM_INPUTS`]. A miscounted
+//! list fails with a message naming the node, e.g.
+//! "node `mixer` expects 4 inputs, found 2":
+//!
+//! ```compile_fail
+//! use teensy_audio::audio_graph;
+//! use teensy_audio::nodes::*;
+//!
+//! audio_graph! {
+//!     pub struct Broken {
+//!         sine: AudioSynthSine {},
+//!         mixer: AudioMixer<4> { (sine, 0), (sine, 0) }, // needs 4 entries
+//!     }
+//! }
+//! ```
+//!
+//! ```compile_fail
+//! use teensy_audio::audio_graph;
+//! use teensy_audio::nodes::*;
+//!
+//! audio_graph! {
+//!     pub struct Broken {
+//!         sine: AudioSynthSine {},
+//!         amp: AudioAmplifier { (sine, 0), (sine, 0) }, // only 1 input
+//!     }
+//! }
+//! ```
+//!
+//! [`AudioNode::NUM_INPUTS`]: crate::node::AudioNode::NUM_INPUTS
 //!
 //! ## Generated API
 //!
 //! - A struct with `pub` fields for each node (direct access for configuration)
 //! - `new()` — constructs all nodes via their `new()` methods
 //! - `update_all()` — processes one block cycle, routing audio between nodes
+//! - `update_all_with(|node| ...)` — the same, calling back with the node's
+//!   field name whenever the pool runs out while allocating its outputs
+//! - `NODE_COUNT` — number of nodes in the graph
+//! - `NODE_NAMES` / `node_names()` — node field names, in declaration order
+//! - `NODE_TYPE_NAMES` / `node_type_names()` — each node's
+//!   [`AudioNode::NAME`](crate::node::AudioNode::NAME), in the same order
+//! - One `usize` index const per node, `INDEX_` plus the upper-cased field
+//!   name (e.g. `MyGraph::INDEX_SINE`), giving its position in declaration
+//!   order. Use these instead of magic numbers when referring to nodes by
+//!   index.
+//! - `write_dot(&mut w)` — writes a Graphviz description of the topology to
+//!   any `core::fmt::Write` (nodes labelled `name: Type`, edges labelled with
+//!   output and input port), e.g. for `dot -Tsvg` on the host
+//! - `update_phase(n)` / `release_phase_blocks()` — phased processing, below
+//! - `reset_all()` — clears every node's state (see
+//!   [`AudioNode::reset`](crate::node::AudioNode::reset)) and any blocks the
+//!   graph holds between cycles, without rebuilding it
+//!
+//! ## Output node
+//!
+//! Nodes are built with their no-argument `new()`, except one tagged
+//! `#[output]`: a DMA output such as `AudioOutputI2S`, built with update
+//! responsibility via [`DmaOutput::new_responsible`]. The graph then
+//! implements [`AudioGraph`] for it, so an
+//! [`AudioSystem`](crate::io::AudioSystem) can run it straight from the
+//! DMA interrupt:
+//!
+//! ```ignore
+//! audio_graph! {
+//!     pub struct Synth {
+//!         sine: AudioSynthSine {},
+//!         #[output]
+//!         out: AudioOutputI2S { (sine, 0), (sine, 0) },
+//!     }
+//! }
+//! ```
+//!
+//! [`DmaOutput::new_responsible`]: crate::io::DmaOutput::new_responsible
+//! [`AudioGraph`]: crate::io::AudioGraph
+//!
+//! ## Update phases
+//!
+//! On-device, I/O nodes are time-critical while analyzers can tolerate
+//! running later. Prefix a node with `#[phase(n)]` to put it in phase `n`
+//! (untagged nodes are phase 0), then call `update_phase(0)`,
+//! `update_phase(1)`, ... once per block cycle instead of `update_all()`,
+//! e.g. phase 0 from the DMA interrupt and phase 1 from a lower-priority
+//! task:
+//!
+//! ```ignore
+//! audio_graph! {
+//!     pub struct Graph {
+//!         synth: AudioSynthWaveform {},
+//!         rec: AudioRecordQueue { (synth, 0) },
+//!         #[phase(1)]
+//!         fft: AudioAnalyzeFFT256 { (synth, 0) },
+//!     }
+//! }
+//! ```
+//!
+//! Each phase runs its nodes in declaration order. Unlike `update_all()`,
+//! which keeps blocks in locals for the duration of one call, phased
+//! updates store every node's output blocks inside the graph struct, so a
+//! later phase can read what an earlier one produced. These are ordinary
+//! refcounted [`AudioBlockRef`](crate::block::AudioBlockRef)s: a node's
+//! previous outputs are released when it next runs (or by
+//! `release_phase_blocks()`), never while a reader still holds them. Both
+//! methods take `&mut self`, so sharing the graph between priority levels
+//! needs the usual lock (e.g. an RTIC shared resource); the borrow checker
+//! rules out a phase observing another half-way through.
+//!
+//! Two consequences to plan for: stored blocks stay allocated between
+//! cycles, so the pool needs roughly one extra block per node output; and a
+//! node reading from a node in a *later* phase sees that node's output
+//! from the previous cycle (one block of latency).
+//!
+//! ## Feedback
+//!
+//! Inputs normally come from nodes declared earlier. To close a loop, e.g.
+//! recirculating a delay line through a mixer, mark the backward edge
+//! `@delayed`:
+//!
+//! ```ignore
+//! audio_graph! {
+//!     pub struct Echo {
+//!         src: AudioSynthWaveform {},
+//!         mix: AudioMixer<2> { (src, 0), (feedback, 0) @delayed },
+//!         delay: AudioEffectDelay { (mix, 0) },
+//!         feedback: AudioAmplifier { (delay, 0) },
+//!     }
+//! }
+//! ```
+//!
+//! The graph keeps the outputs of every node read through `@delayed` in
+//! hidden fields between `update_all()` calls, so the loop adds exactly
+//! one block (128 samples, ~2.9 ms) of latency on top of whatever the
+//! nodes themselves add, and each such output holds one pool block between
+//! cycles. Phased updates already read stored outputs, so there a
+//! `@delayed` input behaves like any input from a later node.
+//!
+//! ## Instrumentation
+//!
+//! Put `#[instrument]` before any other attributes on the struct to time
+//! each node's `update()`:
+//!
+//! ```ignore
+//! audio_graph! {
+//!     #[instrument]
+//!     pub struct Graph {
+//!         synth: AudioSynthWaveform {},
+//!         filter: AudioFilterBiquad { (synth, 0) },
+//!     }
+//! }
+//!
+//! g.update_all();
+//! for (name, cycles) in g.cycles() { /* log */ }
+//! ```
+//!
+//! The generated `cycles()` returns `(node name, cycles)` pairs from the
+//! most recent `update_all()` / `update_phase()`. Counts come from the
+//! Cortex-M DWT cycle counter when the `metrics` feature is enabled (the
+//! application must start the counter); otherwise, e.g. on the host, they
+//! are always 0. Graphs without `#[instrument]` carry no timing code.
 //!
 //! ## Block routing
 //!
 //! - Output blocks are converted to shared `AudioBlockRef` for routing
 //! - Fan-out uses `AudioBlockRef::clone()` (refcount increment, no copy)
 //! - Unconnected inputs (`_`) receive `None` (silence)
-//! - Pool exhaustion degrades gracefully (nodes see `None` outputs)
+//! - Pool exhaustion degrades gracefully (nodes see `None` outputs);
+//!   `update_all_with()` reports which node went without
 
 /// Declare and wire an audio processing graph.
 ///
 /// See the [module documentation](crate::graph) for full syntax.
 #[macro_export]
 macro_rules! audio_graph {
-    // ── Main entry point ──────────────────────────────────────────────
+    // ── Entry points ──────────────────────────────────────────────────
+    // `#[instrument]` first selects per-node cycle counting
     (
+        #[instrument]
+        $($rest:tt)*
+    ) => {
+        $crate::audio_graph!(@graph [instrument] $($rest)*);
+    };
+
+    (
+        $(#[$struct_meta:meta])*
+        $vis:vis struct $name:ident { $($body:tt)* }
+    ) => {
+        $crate::audio_graph!(@graph [] $(#[$struct_meta])* $vis struct $name { $($body)* });
+    };
+
+    // ── Graph generation ──────────────────────────────────────────────
+    (
+        @graph $mode:tt
         $(#[$struct_meta:meta])*
         $vis:vis struct $name:ident {
             $(
-                $node_name:ident : $node_type:ty { $( $input_item:tt ),* $(,)? }
+                $( #[$attr:ident $( ( $attr_arg:literal ) )?] )*
+                $node_name:ident : $node_type:ty {
+                    $( $input_item:tt $( @ $delayed:ident )? ),* $(,)?
+                }
             ),+
             $(,)?
         }
@@ -63,13 +264,165 @@ macro_rules! audio_graph {
         $(#[$struct_meta])*
         $vis struct $name {
             $( pub $node_name: $node_type, )+
+            /// Output blocks of each node, kept between `update_phase()` calls.
+            __phase_blocks: [
+                [Option<$crate::block::AudioBlockRef>;
+                    $crate::graph::max_outputs(&[$( <$node_type as $crate::node::AudioNode>::NUM_OUTPUTS ),+])];
+                [$( stringify!($node_name) ),+].len()
+            ],
+            /// Outputs of `@delayed` sources from the last `update_all()`.
+            __feedback: [
+                [Option<$crate::block::AudioBlockRef>;
+                    $crate::graph::max_outputs(&[$( <$node_type as $crate::node::AudioNode>::NUM_OUTPUTS ),+])];
+                [$( stringify!($node_name) ),+].len()
+            ],
+            /// Cycle counts of each node's last `update()` (instrumented only).
+            __cycles: $crate::audio_graph!(@cycles_type $mode [$( stringify!($node_name) ),+].len()),
         }
 
         impl $name {
+            /// Number of nodes in the graph.
+            #[allow(dead_code)]
+            pub const NODE_COUNT: usize = [$( stringify!($node_name) ),+].len();
+
+            /// Node field names, in declaration (processing) order.
+            #[allow(dead_code)]
+            pub const NODE_NAMES: &'static [&'static str] = &[$( stringify!($node_name) ),+];
+
+            /// Each node's [`AudioNode::NAME`], in declaration order.
+            ///
+            /// [`AudioNode::NAME`]: $crate::node::AudioNode::NAME
+            #[allow(dead_code)]
+            pub const NODE_TYPE_NAMES: &'static [&'static str] =
+                &[$( <$node_type as $crate::node::AudioNode>::NAME ),+];
+
+            /// Node field names, in declaration (processing) order; the same
+            /// order as `cycles()` and the `INDEX_` constants.
+            #[allow(dead_code)]
+            pub fn node_names() -> &'static [&'static str] {
+                Self::NODE_NAMES
+            }
+
+            /// Each node's type name, in declaration order, paired by index
+            /// with [`node_names()`](Self::node_names).
+            #[allow(dead_code)]
+            pub fn node_type_names() -> &'static [&'static str] {
+                Self::NODE_TYPE_NAMES
+            }
+
+            $crate::audio_graph!(@node_indices 0usize; $( $node_name )+);
+
+            /// Which nodes feed an `@delayed` input, by node index.
+            #[doc(hidden)]
+            const __FEEDBACK: [bool; [$( stringify!($node_name) ),+].len()] =
+                $crate::graph::feedback_sources(&[
+                    $( $( $crate::audio_graph!(@delayed_source $input_item $( @ $delayed )?), )* )+
+                ]);
+
+            /// Write a Graphviz DOT description of the graph topology to `w`.
+            ///
+            /// Edges carry the source output port as `taillabel` and the
+            /// destination input index as `headlabel`.
+            #[allow(dead_code)]
+            pub fn write_dot<W: core::fmt::Write>(w: &mut W) -> core::fmt::Result {
+                w.write_str(concat!("digraph ", stringify!($name), " {\n"))?;
+                $(
+                    w.write_str(concat!(
+                        "    ", stringify!($node_name),
+                        " [label=\"", stringify!($node_name), ": ", stringify!($node_type), "\"];\n"
+                    ))?;
+                )+
+                $(
+                    {
+                        #[allow(unused_mut, unused_variables, unused_assignments)]
+                        let mut _input = 0usize;
+                        $(
+                            $crate::audio_graph!(
+                                @dot_edge w, $node_name, _input, $input_item $( @ $delayed )?
+                            );
+                            _input += 1;
+                        )*
+                    }
+                )+
+                w.write_str("}\n")
+            }
+
             /// Create a new audio graph with all nodes default-initialized.
             pub fn new() -> Self {
                 Self {
-                    $( $node_name: <$node_type>::new(), )+
+                    $( $node_name: $crate::audio_graph!(
+                        @new $node_type, [$( $attr $( ($attr_arg) )? )*]
+                    ), )+
+                    __phase_blocks: core::array::from_fn(|_| core::array::from_fn(|_| None)),
+                    __feedback: core::array::from_fn(|_| core::array::from_fn(|_| None)),
+                    __cycles: $crate::audio_graph!(@cycles_init $mode Self::NODE_NAMES),
+                }
+            }
+
+            $crate::audio_graph!(@cycles_fn $mode);
+
+            /// Process the nodes tagged `#[phase(phase)]` (untagged nodes are
+            /// phase 0), in declaration order.
+            ///
+            /// Inputs are read from, and outputs stored into, the graph's
+            /// phase block storage; see the [module documentation](crate::graph)
+            /// for the lifetime rules.
+            #[allow(unused_variables, dead_code)]
+            pub fn update_phase(&mut self, phase: usize) {
+                $(
+                    #[allow(non_camel_case_types, dead_code)]
+                    type $node_name = $node_type;
+                )+
+                $(
+                    if phase == $crate::audio_graph!(@phase [$( $attr $( ($attr_arg) )? )*]) {
+                        let _inputs: [Option<$crate::block::AudioBlockRef>; _] = {
+                            let _blocks = &self.__phase_blocks;
+                            [ $( $crate::audio_graph!(
+                                @stored_input _blocks, $input_item $( @ $delayed )?
+                            ) ),* ]
+                        };
+
+                        let mut _outs: [Option<$crate::block::AudioBlockMut>;
+                            <$node_type as $crate::node::AudioNode>::NUM_OUTPUTS
+                        ] = core::array::from_fn(|_| $crate::block::AudioBlockMut::alloc());
+
+                        let _start = $crate::audio_graph!(@cycles_start $mode);
+                        <$node_type as $crate::node::AudioNode>::update(
+                            &mut self.$node_name, &_inputs, &mut _outs
+                        );
+                        $crate::audio_graph!(
+                            @cycles_record $mode self.__cycles[$crate::audio_graph!(@index $node_name)].1, _start
+                        );
+
+                        // Replace (and so release) this node's previous outputs
+                        let _slot = &mut self.__phase_blocks[$crate::audio_graph!(@index $node_name)];
+                        for (stored, out) in _slot.iter_mut().zip(_outs) {
+                            *stored = out.map(|b| b.into_shared());
+                        }
+                    }
+                )+
+            }
+
+            /// Drop every block held in phase block storage, returning them
+            /// to the pool.
+            #[allow(dead_code)]
+            pub fn release_phase_blocks(&mut self) {
+                for slot in self.__phase_blocks.iter_mut() {
+                    slot.fill(None);
+                }
+            }
+
+            /// Reset every node (see [`AudioNode::reset`]) and drop all stored
+            /// phase and feedback blocks, so the graph starts again from
+            /// silence with its configuration intact.
+            ///
+            /// [`AudioNode::reset`]: $crate::node::AudioNode::reset
+            #[allow(dead_code)]
+            pub fn reset_all(&mut self) {
+                $( <$node_type as $crate::node::AudioNode>::reset(&mut self.$node_name); )+
+                self.release_phase_blocks();
+                for slot in self.__feedback.iter_mut() {
+                    slot.fill(None);
                 }
             }
 
@@ -77,45 +430,434 @@ macro_rules! audio_graph {
             ///
             /// Calls `update()` on each node in declaration order, allocating
             /// output blocks and routing them to connected input ports.
-            #[allow(unused_variables)]
             pub fn update_all(&mut self) {
+                self.update_all_with(|_| {});
+            }
+
+            /// [`update_all()`](Self::update_all), calling `on_exhaustion`
+            /// with the node's field name for every output block the pool
+            /// could not supply.
+            ///
+            /// The node still runs and sees `None` for that output, exactly
+            /// as in `update_all()`; the callback just makes the glitch
+            /// visible while debugging pool sizing.
+            #[allow(unused_variables, unused_mut)]
+            pub fn update_all_with(&mut self, mut on_exhaustion: impl FnMut(&'static str)) {
+                // Per-node type aliases (type namespace, so they don't clash
+                // with the block arrays below) for resolving named ports.
+                $(
+                    #[allow(non_camel_case_types, dead_code)]
+                    type $node_name = $node_type;
+                )+
                 $(
                     // Process node: $node_name
                     #[allow(unused_variables, clippy::let_unit_value)]
                     let $node_name: [Option<$crate::block::AudioBlockRef>;
                         <$node_type as $crate::node::AudioNode>::NUM_OUTPUTS
                     ] = {
-                        // Build input array from connection specifications
-                        let _inputs: [Option<$crate::block::AudioBlockRef>;
-                            <$node_type as $crate::node::AudioNode>::NUM_INPUTS
-                        ] = [ $( $crate::audio_graph!(@input_expr $input_item) ),* ];
+                        // Build input array from connection specifications (their
+                        // count is checked against NUM_INPUTS below)
+                        let _inputs: [Option<$crate::block::AudioBlockRef>; _] = [ $( $crate::audio_graph!(
+                            @input_expr self.__feedback, $input_item $( @ $delayed )?
+                        ) ),* ];
 
                         // Allocate output blocks
                         let mut _outs: [Option<$crate::block::AudioBlockMut>;
                             <$node_type as $crate::node::AudioNode>::NUM_OUTPUTS
-                        ] = core::array::from_fn(|_| $crate::block::AudioBlockMut::alloc());
+                        ] = core::array::from_fn(|_| {
+                            let _block = $crate::block::AudioBlockMut::alloc();
+                            if _block.is_none() {
+                                on_exhaustion(stringify!($node_name));
+                            }
+                            _block
+                        });
 
                         // Call the node's update method
+                        let _start = $crate::audio_graph!(@cycles_start $mode);
                         <$node_type as $crate::node::AudioNode>::update(
                             &mut self.$node_name, &_inputs, &mut _outs
                         );
+                        $crate::audio_graph!(
+                            @cycles_record $mode self.__cycles[$crate::audio_graph!(@index $node_name)].1, _start
+                        );
 
                         // Convert outputs to shared refs for downstream routing
-                        _outs.map(|opt| opt.map(|b| b.into_shared()))
+                        let _shared = _outs.map(|opt| opt.map(|b| b.into_shared()));
+
+                        // Keep outputs read by `@delayed` inputs for the next cycle
+                        if Self::__FEEDBACK[$crate::audio_graph!(@index $node_name)] {
+                            let _slot = &mut self.__feedback[$crate::audio_graph!(@index $node_name)];
+                            for (stored, out) in _slot.iter_mut().zip(_shared.iter()) {
+                                *stored = out.clone();
+                            }
+                        }
+                        _shared
                     };
                 )+
             }
         }
+
+        $(
+            $crate::audio_graph!(
+                @output_impl $name, $node_name, $node_type, [$( $attr $( ($attr_arg) )? )*]
+            );
+        )+
+
+        // Port and input count checks as a standalone const item, evaluated
+        // whether or not the update methods are ever called
+        const _: () = {
+            $(
+                #[allow(non_camel_case_types, dead_code)]
+                type $node_name = $node_type;
+            )+
+            $( $( $crate::audio_graph!(@check_port $input_item); )* )+
+            $(
+                $crate::graph::check_input_count(
+                    stringify!($node_name),
+                    <$node_type as $crate::node::AudioNode>::NUM_INPUTS,
+                    <[&str]>::len(&[$( stringify!($input_item) ),*]),
+                );
+            )+
+        };
+    };
+
+    // ── Node index constants ──────────────────────────────────────────
+    // One associated const per node, counting up in declaration order
+    (@node_indices $idx:expr; $first:ident $( $rest:ident )*) => {
+        $crate::paste::paste! {
+            #[allow(dead_code)]
+            #[doc = concat!("Index of node `", stringify!($first), "` in declaration order.")]
+            pub const [<INDEX_ $first:upper>]: usize = $idx;
+        }
+
+        $crate::audio_graph!(@node_indices $idx + 1; $( $rest )*);
+    };
+    (@node_indices $idx:expr;) => {};
+    // A node's index const, inside the graph's `impl`
+    (@index $node:ident) => {
+        $crate::paste::paste! { Self::[<INDEX_ $node:upper>] }
+    };
+
+    // ── DOT edge helpers ──────────────────────────────────────────────
+    // Unconnected input: no edge
+    (@dot_edge $w:ident, $dst:ident, $idx:ident, _) => {};
+
+    (@dot_edge $w:ident, $dst:ident, $idx:ident, ($src:ident, $port:expr)) => {
+        ::core::write!(
+            $w,
+            concat!("    ", stringify!($src), " -> ", stringify!($dst),
+                " [taillabel=\"{}\", headlabel=\"{}\"];\n"),
+            $port, $idx
+        )?;
+    };
+
+    (@dot_edge $w:ident, $dst:ident, $idx:ident, ($src:ident . $port:ident)) => {
+        ::core::write!(
+            $w,
+            concat!("    ", stringify!($src), " -> ", stringify!($dst),
+                " [taillabel=\"", stringify!($port), "\", headlabel=\"{}\"];\n"),
+            $idx
+        )?;
+    };
+
+    // Feedback (`@delayed`) edges are dashed
+    (@dot_edge $w:ident, $dst:ident, $idx:ident, ($src:ident, $port:expr) @ delayed) => {
+        ::core::write!(
+            $w,
+            concat!("    ", stringify!($src), " -> ", stringify!($dst),
+                " [taillabel=\"{}\", headlabel=\"{}\", style=dashed];\n"),
+            $port, $idx
+        )?;
+    };
+
+    (@dot_edge $w:ident, $dst:ident, $idx:ident, ($src:ident . $port:ident) @ delayed) => {
+        ::core::write!(
+            $w,
+            concat!("    ", stringify!($src), " -> ", stringify!($dst),
+                " [taillabel=\"", stringify!($port), "\", headlabel=\"{}\", style=dashed];\n"),
+            $idx
+        )?;
+    };
+
+    // ── Instrumentation helpers ───────────────────────────────────────
+    // Without #[instrument] these all expand to nothing (or `()`)
+    (@cycles_type [] $count:expr) => { () };
+    (@cycles_type [instrument] $count:expr) => { [(&'static str, u32); $count] };
+
+    (@cycles_init [] $names:expr) => { () };
+    (@cycles_init [instrument] $names:expr) => {
+        core::array::from_fn(|i| ($names[i], 0))
+    };
+
+    (@cycles_fn []) => {};
+    (@cycles_fn [instrument]) => {
+        /// Cycles each node's last `update()` took, as `(name, cycles)` in
+        /// declaration order.
+        ///
+        /// Counts come from the DWT cycle counter with the `metrics`
+        /// feature on Cortex-M, and are 0 otherwise.
+        pub fn cycles(&self) -> &[(&'static str, u32)] {
+            &self.__cycles
+        }
+    };
+
+    (@cycles_start []) => { 0u32 };
+    (@cycles_start [instrument]) => { $crate::graph::cycle_count() };
+
+    (@cycles_record [] $slot:expr, $start:ident) => {};
+    (@cycles_record [instrument] $slot:expr, $start:ident) => {
+        $slot = $crate::graph::cycle_count().wrapping_sub($start);
+    };
+
+    // ── Phase helpers ─────────────────────────────────────────────────
+    // Untagged nodes belong to phase 0
+    (@phase []) => { 0usize };
+    (@phase [phase ($phase:literal) $($rest:tt)*]) => { $phase };
+    (@phase [output $($rest:tt)*]) => { $crate::audio_graph!(@phase [$($rest)*]) };
+
+    // ── Output helpers ────────────────────────────────────────────────
+    // Construct a node: `#[output]` nodes drive graph updates from their ISR
+    (@new $node_type:ty, []) => { <$node_type>::new() };
+    (@new $node_type:ty, [output $($rest:tt)*]) => {
+        <$node_type as $crate::io::DmaOutput>::new_responsible()
+    };
+    (@new $node_type:ty, [phase ($phase:literal) $($rest:tt)*]) => {
+        $crate::audio_graph!(@new $node_type, [$($rest)*])
+    };
+
+    // `AudioGraph` impl for an `#[output]` node, so `AudioSystem` can drive it
+    (@output_impl $name:ident, $node_name:ident, $node_type:ty, []) => {};
+    (@output_impl $name:ident, $node_name:ident, $node_type:ty, [output $($rest:tt)*]) => {
+        impl $crate::io::AudioGraph<$node_type> for $name {
+            fn update_all(&mut self) {
+                $name::update_all(self);
+            }
+
+            fn output(&mut self) -> &mut $node_type {
+                &mut self.$node_name
+            }
+        }
+    };
+    (@output_impl $name:ident, $node_name:ident, $node_type:ty, [phase ($phase:literal) $($rest:tt)*]) => {
+        $crate::audio_graph!(@output_impl $name, $node_name, $node_type, [$($rest)*]);
+    };
+
+    // Inputs for update_phase(): read from stored outputs by node index
+    (@stored_input $blocks:ident, _) => { None };
+
+    (@stored_input $blocks:ident, ($src:ident, $port:expr)) => {
+        $blocks[$crate::audio_graph!(@index $src)][$crate::audio_graph!(@port $src, $port)].clone()
+    };
+
+    (@stored_input $blocks:ident, ($src:ident . $port:ident)) => {
+        $blocks[$crate::audio_graph!(@index $src)][$crate::audio_graph!(@port_named $src, $port)].clone()
+    };
+
+    // Stored outputs of a later node are already last cycle's
+    (@stored_input $blocks:ident, $item:tt @ delayed) => {
+        $crate::audio_graph!(@stored_input $blocks, $item)
+    };
+
+    // ── Feedback helpers ──────────────────────────────────────────────
+    // Node index of an `@delayed` input's source, usize::MAX for others
+    (@delayed_source ($src:ident, $port:expr) @ delayed) => { $crate::audio_graph!(@index $src) };
+    (@delayed_source ($src:ident . $port:ident) @ delayed) => { $crate::audio_graph!(@index $src) };
+    (@delayed_source $item:tt) => { usize::MAX };
+
+    // ── Port helpers ──────────────────────────────────────────────────
+    // Numeric output port, checked against the source's NUM_OUTPUTS at
+    // compile time
+    (@port $src:ident, $port:expr) => {{
+        const PORT: usize = $crate::node::checked_output_port(
+            $port,
+            <$src as $crate::node::AudioNode>::NUM_OUTPUTS,
+        );
+        PORT
+    }};
+
+    // Named output port, resolved against the source's OUTPUT_NAMES
+    (@port_named $src:ident, $port:ident) => {{
+        const PORT: usize = $crate::node::output_port_index(
+            <$src as $crate::node::AudioNode>::OUTPUT_NAMES,
+            stringify!($port),
+        );
+        PORT
+    }};
+
+    // Compile-time check of one input spec against its source node
+    (@check_port _) => {};
+
+    (@check_port ($src:ident, $port:expr)) => {
+        let _ = $crate::node::checked_output_port(
+            $port,
+            <$src as $crate::node::AudioNode>::NUM_OUTPUTS,
+        );
+    };
+
+    (@check_port ($src:ident . $port:ident)) => {
+        let _ = $crate::node::output_port_index(
+            <$src as $crate::node::AudioNode>::OUTPUT_NAMES,
+            stringify!($port),
+        );
     };
 
     // ── Input expression helpers ──────────────────────────────────────
     // Unconnected input: produces None (silence)
-    (@input_expr _) => { None };
+    (@input_expr $feedback:expr, _) => { None };
 
     // Connected input: clone a shared ref from a source node's output port
-    (@input_expr ($src:ident, $port:expr)) => {
-        $src[$port].clone()
+    (@input_expr $feedback:expr, ($src:ident, $port:expr)) => {
+        $src[$crate::audio_graph!(@port $src, $port)].clone()
+    };
+
+    // Connected input by port name: resolved against the source's OUTPUT_NAMES
+    (@input_expr $feedback:expr, ($src:ident . $port:ident)) => {
+        $src[$crate::audio_graph!(@port_named $src, $port)].clone()
+    };
+
+    // Feedback input: the source's output from the previous update_all()
+    (@input_expr $feedback:expr, ($src:ident, $port:expr) @ delayed) => {
+        $feedback[$crate::audio_graph!(@index $src)][$crate::audio_graph!(@port $src, $port)].clone()
     };
+
+    (@input_expr $feedback:expr, ($src:ident . $port:ident) @ delayed) => {
+        $feedback[$crate::audio_graph!(@index $src)][$crate::audio_graph!(@port_named $src, $port)].clone()
+    };
+}
+
+/// Current cycle count, for `#[instrument]` graphs.
+///
+/// Reads the DWT cycle counter (`CYCCNT`) when the `metrics` feature is on
+/// and the target is Cortex-M; the application must have enabled the
+/// counter (e.g. `DCB::enable_trace()` and `DWT::enable_cycle_counter()` in
+/// `cortex-m`). Returns 0 everywhere else, so instrumented graphs still
+/// build and run on the host.
+#[doc(hidden)]
+#[inline(always)]
+pub fn cycle_count() -> u32 {
+    #[cfg(all(feature = "metrics", target_arch = "arm"))]
+    {
+        const DWT_CYCCNT: *const u32 = 0xE000_1004 as *const u32;
+        // SAFETY: the DWT is always mapped on Cortex-M7 and reading
+        // CYCCNT has no side effects.
+        unsafe { core::ptr::read_volatile(DWT_CYCCNT) }
+    }
+
+    #[cfg(not(all(feature = "metrics", target_arch = "arm")))]
+    {
+        0
+    }
+}
+
+/// Flags, by node index, the nodes listed in `sources`; other entries
+/// (`usize::MAX` for non-feedback inputs) are ignored.
+///
+/// Used by [`audio_graph!`] to decide which nodes' outputs to keep for
+/// `@delayed` inputs.
+#[doc(hidden)]
+pub const fn feedback_sources<const N: usize>(sources: &[usize]) -> [bool; N] {
+    let mut flags = [false; N];
+    let mut i = 0;
+    while i < sources.len() {
+        if sources[i] < N {
+            flags[sources[i]] = true;
+        }
+        i += 1;
+    }
+    flags
+}
+
+/// Check that a node lists as many input connections as its type has
+/// inputs.
+///
+/// Used by [`audio_graph!`] in const context, so a miscounted `{ ... }`
+/// list fails the build with e.g. "node `mixer` expects 4 inputs, found 2"
+/// rather than an array length mismatch.
+#[doc(hidden)]
+pub const fn check_input_count(node: &str, expected: usize, found: usize) {
+    if found != expected {
+        let message = Message::new()
+            .push_str("audio_graph!: node `")
+            .push_str(node)
+            .push_str("` expects ")
+            .push_usize(expected)
+            .push_str(if expected == 1 { " input, found " } else { " inputs, found " })
+            .push_usize(found);
+        panic!("{}", message.as_str());
+    }
+}
+
+/// Fixed-capacity string for building panic messages in const context.
+struct Message {
+    buf: [u8; 128],
+    len: usize,
+}
+
+impl Message {
+    const fn new() -> Self {
+        Message {
+            buf: [0; 128],
+            len: 0,
+        }
+    }
+
+    /// Append `s`, truncated to the space left.
+    const fn push_str(mut self, s: &str) -> Self {
+        let bytes = s.as_bytes();
+        let mut i = 0;
+        while i < bytes.len() && self.len < self.buf.len() {
+            self.buf[self.len] = bytes[i];
+            self.len += 1;
+            i += 1;
+        }
+        self
+    }
+
+    /// Append `n` in decimal.
+    const fn push_usize(self, n: usize) -> Self {
+        let mut digits = [0u8; 20];
+        let mut start = digits.len();
+        let mut n = n;
+        loop {
+            start -= 1;
+            digits[start] = b'0' + (n % 10) as u8;
+            n /= 10;
+            if n == 0 {
+                break;
+            }
+        }
+        match core::str::from_utf8(digits.split_at(start).1) {
+            Ok(s) => self.push_str(s),
+            Err(_) => self,
+        }
+    }
+
+    const fn as_str(&self) -> &str {
+        // Truncation can split a multi-byte character; keep what decodes
+        match core::str::from_utf8(self.buf.split_at(self.len).0) {
+            Ok(s) => s,
+            Err(e) => match core::str::from_utf8(self.buf.split_at(e.valid_up_to()).0) {
+                Ok(s) => s,
+                Err(_) => "",
+            },
+        }
+    }
+}
+
+/// Largest value in `counts` (0 if empty).
+///
+/// Used by [`audio_graph!`] to size per-node output storage.
+#[doc(hidden)]
+pub const fn max_outputs(counts: &[usize]) -> usize {
+    let mut max = 0;
+    let mut i = 0;
+    while i < counts.len() {
+        if counts[i] > max {
+            max = counts[i];
+        }
+        i += 1;
+    }
+    max
 }
 
 #[cfg(test)]
@@ -187,6 +929,34 @@ mod tests {
         assert!(rms_level > 0.0, "rms should detect signal");
     }
 
+    #[test]
+    fn graph_reports_pool_exhaustion() {
+        use crate::block::AudioBlockMut;
+        use crate::constants::POOL_SIZE;
+
+        reset_pool();
+        let mut graph = ChainGraph::new();
+        graph.sine.amplitude(1.0);
+
+        // Hold all but one block: the sine gets it, the amplifier goes without
+        let held: [Option<AudioBlockMut>; POOL_SIZE - 1] =
+            core::array::from_fn(|_| AudioBlockMut::alloc());
+        let mut starved = [""; 4];
+        let mut count = 0;
+        graph.update_all_with(|node| {
+            starved[count] = node;
+            count += 1;
+        });
+        assert_eq!(&starved[..count], &["amp"]);
+        assert!(!graph.peak.available(), "nothing reached the analyzers");
+
+        drop(held);
+        count = 0;
+        graph.update_all_with(|_| count += 1);
+        assert_eq!(count, 0);
+        assert!(graph.peak.available());
+    }
+
     // ── Mixer graph with multiple inputs ──────────────────────────────
     crate::audio_graph! {
         struct MixerGraph {
@@ -311,4 +1081,379 @@ mod tests {
         let level = graph.peak.read();
         assert!(level > 0.0);
     }
+
+    // ── Named output ports ────────────────────────────────────────────
+    /// Test-only three-output node: writes a distinct DC level per port.
+    pub struct ThreeBand;
+
+    impl ThreeBand {
+        pub fn new() -> Self {
+            ThreeBand
+        }
+    }
+
+    impl crate::node::AudioNode for ThreeBand {
+        const NUM_INPUTS: usize = 0;
+        const NUM_OUTPUTS: usize = 3;
+        const NAME: &'static str = "ThreeBand";
+        const OUTPUT_NAMES: &'static [&'static str] = &["lowpass", "bandpass", "highpass"];
+
+        fn update(
+            &mut self,
+            _inputs: &[Option<crate::block::AudioBlockRef>],
+            outputs: &mut [Option<crate::block::AudioBlockMut>],
+        ) {
+            for (port, out) in outputs.iter_mut().enumerate() {
+                if let Some(block) = out.as_mut() {
+                    block.fill(8000 * (port as i16 + 1));
+                }
+            }
+        }
+    }
+
+    crate::audio_graph! {
+        struct NamedPortGraph {
+            bands: ThreeBand {},
+            by_name: crate::nodes::AudioAnalyzePeak { (bands.highpass) },
+            by_index: crate::nodes::AudioAnalyzePeak { (bands, 2) },
+            low: crate::nodes::AudioAnalyzePeak { (bands.lowpass) },
+        }
+    }
+
+    #[test]
+    fn graph_named_port_matches_numeric_port() {
+        reset_pool();
+        let mut graph = NamedPortGraph::new();
+
+        graph.update_all();
+
+        let by_name = graph.by_name.read();
+        let by_index = graph.by_index.read();
+        assert_eq!(by_name, by_index);
+        assert!((by_name - 24000.0 / 32767.0).abs() < 1e-4);
+        assert!((graph.low.read() - 8000.0 / 32767.0).abs() < 1e-4);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    /// Test-only graph wrapper around the I2S input, whose `new()` takes an
+    /// argument. Keeps the input's port names.
+    pub struct LineIn(crate::io::AudioInputI2S);
+
+    impl LineIn {
+        pub fn new() -> Self {
+            LineIn(crate::io::AudioInputI2S::new(false))
+        }
+    }
+
+    impl crate::node::AudioNode for LineIn {
+        const NUM_INPUTS: usize = 0;
+        const NUM_OUTPUTS: usize = 2;
+        const NAME: &'static str = "LineIn";
+        const OUTPUT_NAMES: &'static [&'static str] =
+            <crate::io::AudioInputI2S as crate::node::AudioNode>::OUTPUT_NAMES;
+
+        fn update(
+            &mut self,
+            inputs: &[Option<crate::block::AudioBlockRef>],
+            outputs: &mut [Option<crate::block::AudioBlockMut>],
+        ) {
+            self.0.update(inputs, outputs);
+        }
+    }
+
+    crate::audio_graph! {
+        struct StereoMeter {
+            line: LineIn {},
+            left_peak: crate::nodes::AudioAnalyzePeak { (line.left) },
+            right_peak: crate::nodes::AudioAnalyzePeak { (line.right) },
+        }
+    }
+
+    #[test]
+    fn graph_input_left_right_by_name() {
+        use crate::constants::AUDIO_BLOCK_SAMPLES;
+
+        reset_pool();
+        let mut graph = StereoMeter::new();
+        // First cycle only allocates the input's working blocks
+        graph.update_all();
+
+        let mut dma_buf = [0u32; AUDIO_BLOCK_SAMPLES * 2];
+        for frame in dma_buf.chunks_exact_mut(2) {
+            frame[0] = (4000u16 as u32) << 16;
+            frame[1] = (16000u16 as u32) << 16;
+        }
+        graph.line.0.isr(&dma_buf);
+        graph.update_all();
+
+        assert!((graph.left_peak.read() - 4000.0 / 32767.0).abs() < 1e-4);
+        assert!((graph.right_peak.read() - 16000.0 / 32767.0).abs() < 1e-4);
+    }
+
+    // ── Generated node count and indices ──────────────────────────────
+    #[test]
+    fn graph_node_count_and_indices() {
+        assert_eq!(MixerGraph::NODE_COUNT, 4);
+        assert_eq!(MixerGraph::NODE_NAMES, &["sine1", "sine2", "mixer", "peak"]);
+        assert_eq!(MixerGraph::INDEX_SINE1, 0);
+        assert_eq!(MixerGraph::INDEX_SINE2, 1);
+        assert_eq!(MixerGraph::INDEX_MIXER, 2);
+        assert_eq!(MixerGraph::INDEX_PEAK, 3);
+        let indices = [
+            MixerGraph::INDEX_SINE1,
+            MixerGraph::INDEX_SINE2,
+            MixerGraph::INDEX_MIXER,
+            MixerGraph::INDEX_PEAK,
+        ];
+        assert!(indices.windows(2).all(|w| w[0] < w[1]), "indices must be distinct and ordered");
+
+        assert_eq!(SineToAnalyzer::NODE_COUNT, 2);
+        assert_eq!(SineToAnalyzer::INDEX_SINE, 0);
+        assert_eq!(SineToAnalyzer::INDEX_PEAK, 1);
+    }
+
+    #[test]
+    fn graph_node_names_follow_declaration_order() {
+        assert_eq!(ChainGraph::node_names(), &["sine", "amp", "peak", "rms"]);
+        assert_eq!(
+            ChainGraph::node_type_names(),
+            &["AudioSynthSine", "AudioAmplifier", "AudioAnalyzePeak", "AudioAnalyzeRms"]
+        );
+        assert_eq!(MixerGraph::node_names()[MixerGraph::INDEX_MIXER], "mixer");
+        assert_eq!(MixerGraph::node_type_names()[MixerGraph::INDEX_MIXER], "AudioMixer");
+    }
+
+    // ── DOT export ────────────────────────────────────────────────────
+    /// Fixed-capacity `fmt::Write` sink for checking generated text.
+    struct TextBuf {
+        data: [u8; 1024],
+        len: usize,
+    }
+
+    impl TextBuf {
+        fn new() -> Self {
+            TextBuf { data: [0; 1024], len: 0 }
+        }
+
+        fn as_str(&self) -> &str {
+            core::str::from_utf8(&self.data[..self.len]).unwrap()
+        }
+    }
+
+    impl core::fmt::Write for TextBuf {
+        fn write_str(&mut self, s: &str) -> core::fmt::Result {
+            let end = self.len + s.len();
+            if end > self.data.len() {
+                return Err(core::fmt::Error);
+            }
+            self.data[self.len..end].copy_from_slice(s.as_bytes());
+            self.len = end;
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn graph_write_dot_lists_nodes_and_edges() {
+        let mut buf = TextBuf::new();
+        MixerGraph::write_dot(&mut buf).unwrap();
+        let dot = buf.as_str();
+
+        assert!(dot.starts_with("digraph MixerGraph {\n"), "{}", dot);
+        assert!(dot.ends_with("}\n"));
+        assert!(dot.contains("    sine1 [label=\"sine1: crate::nodes::AudioSynthSine\"];\n"), "{}", dot);
+        assert!(dot.contains("    mixer [label=\"mixer: crate::nodes::AudioMixer<4>\"];\n"), "{}", dot);
+        assert!(dot.contains("    sine1 -> mixer [taillabel=\"0\", headlabel=\"0\"];\n"), "{}", dot);
+        assert!(dot.contains("    sine2 -> mixer [taillabel=\"0\", headlabel=\"1\"];\n"), "{}", dot);
+        assert!(dot.contains("    mixer -> peak [taillabel=\"0\", headlabel=\"0\"];\n"), "{}", dot);
+        // Unconnected mixer inputs 2 and 3 produce no edges
+        assert_eq!(dot.matches(" -> ").count(), 3);
+    }
+
+    #[test]
+    fn graph_write_dot_uses_port_names() {
+        let mut buf = TextBuf::new();
+        NamedPortGraph::write_dot(&mut buf).unwrap();
+        let dot = buf.as_str();
+
+        assert!(dot.contains("    bands -> by_name [taillabel=\"highpass\", headlabel=\"0\"];\n"), "{}", dot);
+        assert!(dot.contains("    bands -> by_index [taillabel=\"2\", headlabel=\"0\"];\n"), "{}", dot);
+        assert!(dot.contains("    bands -> low [taillabel=\"lowpass\", headlabel=\"0\"];\n"), "{}", dot);
+    }
+
+    // ── Update phases ─────────────────────────────────────────────────
+    crate::audio_graph! {
+        struct PhasedGraph {
+            sine: crate::nodes::AudioSynthSine {},
+            bands: crate::nodes::AudioFilterStateVariable { (sine, 0), _ },
+            amp: crate::nodes::AudioAmplifier { (bands.lowpass) },
+            #[phase(1)]
+            rec: crate::io::AudioRecordQueue { (amp, 0) },
+            #[phase(1)]
+            peak: crate::nodes::AudioAnalyzePeak { (bands, 2) },
+        }
+    }
+
+    fn configure(graph: &mut PhasedGraph) {
+        graph.sine.frequency(600.0);
+        graph.sine.amplitude(0.8);
+        graph.bands.frequency(1000.0);
+        graph.amp.gain(0.7);
+        graph.rec.start();
+    }
+
+    #[test]
+    fn graph_phases_match_update_all() {
+        reset_pool();
+        let mut whole = PhasedGraph::new();
+        let mut phased = PhasedGraph::new();
+        configure(&mut whole);
+        configure(&mut phased);
+
+        for _ in 0..6 {
+            whole.update_all();
+            phased.update_phase(0);
+            // Phase 1 has not run: nothing recorded yet this cycle
+            assert_eq!(phased.rec.available(), 0);
+            phased.update_phase(1);
+
+            assert_eq!(whole.peak.read(), phased.peak.read());
+            let a = whole.rec.read().unwrap();
+            let b = phased.rec.read().unwrap();
+            assert_eq!(&a[..], &b[..]);
+            assert!(a.iter().any(|&s| s != 0));
+        }
+    }
+
+    #[test]
+    fn graph_phase_blocks_held_until_released() {
+        reset_pool();
+        let mut graph = PhasedGraph::new();
+        configure(&mut graph);
+        graph.rec.stop();
+
+        graph.update_phase(0);
+        // sine (1) + bands (3) + amp (1) outputs are stored
+        assert_eq!(crate::block::pool::POOL.allocated_count(), 5);
+        graph.update_phase(1);
+        graph.update_phase(0);
+        assert_eq!(crate::block::pool::POOL.allocated_count(), 5);
+
+        graph.release_phase_blocks();
+        assert_eq!(crate::block::pool::POOL.allocated_count(), 0);
+    }
+
+    // ── Feedback edges ────────────────────────────────────────────────
+    crate::audio_graph! {
+        struct FeedbackGraph {
+            dc: crate::nodes::AudioSynthWaveformDc {},
+            mix: crate::nodes::AudioMixer<2> { (dc, 0), (half, 0) @delayed },
+            half: crate::nodes::AudioAmplifier { (mix, 0) },
+            rec: crate::io::AudioRecordQueue { (mix, 0) },
+        }
+    }
+
+    #[test]
+    fn graph_feedback_recirculates_one_block_later() {
+        reset_pool();
+        let mut graph = FeedbackGraph::new();
+        graph.half.gain(0.5);
+        graph.rec.start();
+
+        // One block of DC, then silence: each later cycle hears the
+        // previous cycle's mix at half level
+        graph.dc.amplitude(0.5);
+        graph.update_all();
+        graph.dc.amplitude(0.0);
+        let first = graph.rec.read().unwrap()[0];
+        assert!(first > 16000);
+
+        for cycle in 1..5 {
+            graph.update_all();
+            let block = graph.rec.read().unwrap();
+            assert!(block.iter().all(|&s| s == first >> cycle), "cycle {cycle}");
+        }
+    }
+
+    #[test]
+    fn graph_reset_all_drops_feedback() {
+        reset_pool();
+        let mut graph = FeedbackGraph::new();
+        graph.half.gain(0.5);
+        graph.rec.start();
+
+        graph.dc.amplitude(0.5);
+        graph.update_all();
+        graph.dc.amplitude(0.0);
+        assert!(graph.rec.read().unwrap()[0] > 16000);
+
+        // Without the reset the next cycle would hear half of the last
+        graph.reset_all();
+        graph.update_all();
+        let block = graph.rec.read().unwrap();
+        assert!(block.iter().all(|&s| s == 0));
+    }
+
+    #[test]
+    fn graph_feedback_dot_edge_is_dashed() {
+        let mut buf = TextBuf::new();
+        FeedbackGraph::write_dot(&mut buf).unwrap();
+        let dot = buf.as_str();
+
+        assert!(dot.contains("    half -> mix [taillabel=\"0\", headlabel=\"1\", style=dashed];\n"), "{}", dot);
+        assert!(dot.contains("    mix -> half [taillabel=\"0\", headlabel=\"0\"];\n"), "{}", dot);
+    }
+
+    // ── Instrumentation ───────────────────────────────────────────────
+    crate::audio_graph! {
+        #[instrument]
+        struct InstrumentedGraph {
+            sine: crate::nodes::AudioSynthSine {},
+            bands: crate::nodes::AudioFilterStateVariable { (sine, 0), _ },
+            amp: crate::nodes::AudioAmplifier { (bands.lowpass) },
+            #[phase(1)]
+            rec: crate::io::AudioRecordQueue { (amp, 0) },
+            #[phase(1)]
+            peak: crate::nodes::AudioAnalyzePeak { (bands, 2) },
+        }
+    }
+
+    #[test]
+    fn graph_instrumented_output_matches_plain() {
+        reset_pool();
+        let mut plain = PhasedGraph::new();
+        configure(&mut plain);
+        let mut timed = InstrumentedGraph::new();
+        timed.sine.frequency(600.0);
+        timed.sine.amplitude(0.8);
+        timed.bands.frequency(1000.0);
+        timed.amp.gain(0.7);
+        timed.rec.start();
+
+        for _ in 0..6 {
+            plain.update_all();
+            timed.update_all();
+
+            assert_eq!(plain.peak.read(), timed.peak.read());
+            let a = plain.rec.read().unwrap();
+            let b = timed.rec.read().unwrap();
+            assert_eq!(&a[..], &b[..]);
+        }
+
+        let names: [&str; 5] = core::array::from_fn(|i| timed.cycles()[i].0);
+        assert_eq!(names, ["sine", "bands", "amp", "rec", "peak"]);
+        // No DWT on the host: the timer is a no-op
+        assert!(timed.cycles().iter().all(|&(_, cycles)| cycles == 0));
+    }
+
+    #[test]
+    fn check_input_count_accepts_matching_list() {
+        super::check_input_count("mixer", 4, 4);
+        super::check_input_count("sine", 0, 0);
+    }
+
+    #[test]
+    #[should_panic(expected = "node `mixer` expects 4 inputs, found 2")]
+    fn check_input_count_names_node_and_counts() {
+        super::check_input_count("mixer", 4, 2);
+    }
 }
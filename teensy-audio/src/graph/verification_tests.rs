@@ -605,8 +605,7 @@ mod tests {
         assert_eq!(AUDIO_BLOCK_SAMPLES, 128, "block size should be 128 samples");
 
         // Verify block duration: 128 / 44117.647 ≈ 2.9 ms
-        let block_duration_ms =
-            AUDIO_BLOCK_SAMPLES as f64 / crate::constants::AUDIO_SAMPLE_RATE_EXACT as f64 * 1000.0;
+        let block_duration_ms = crate::constants::BLOCK_DURATION_SECS * 1000.0;
         assert!(
             (block_duration_ms - 2.9).abs() < 0.1,
             "block duration should be ~2.9 ms, got {} ms",
@@ -28,6 +28,7 @@
 //! let mut outputs = [None, None];
 //! input.update(&[], &mut outputs);
 //! // outputs[0] = left channel, outputs[1] = right channel
+//! // (named `left` / `right` for `audio_graph!` wiring)
 //! ```
 //!
 //! ## Reference
@@ -38,7 +39,8 @@ use crate::block::{AudioBlockMut, AudioBlockRef};
 use crate::constants::AUDIO_BLOCK_SAMPLES;
 use crate::node::AudioNode;
 
-use super::interleave::deinterleave;
+use super::interleave::{deinterleave, DmaLen};
+use super::record_queue::AudioRecordQueue;
 
 /// DMA-driven I2S stereo input node.
 ///
@@ -56,6 +58,12 @@ pub struct AudioInputI2S {
     block_right: Option<AudioBlockMut>,
     /// Whether the working blocks are filled and ready for output.
     blocks_ready: bool,
+    /// Number of frames written into the working blocks so far.
+    frames_filled: usize,
+    /// If `true`, the next `update()` emits partially-filled working blocks.
+    flush_pending: bool,
+    /// Frames received by [`isr()`](Self::isr) since the last block boundary.
+    dma_frames: usize,
     /// If `true`, this node's ISR triggers the audio graph update cycle.
     update_responsibility: bool,
 }
@@ -72,35 +80,89 @@ impl AudioInputI2S {
             block_left: None,
             block_right: None,
             blocks_ready: false,
+            frames_filled: 0,
+            flush_pending: false,
+            dma_frames: 0,
             update_responsibility,
         }
     }
 
     /// Handle DMA interrupt — de-interleave the completed RX buffer.
     ///
-    /// Call this from the DMA completion ISR. It reads the entire DMA buffer
-    /// and splits the interleaved stereo data into the working left/right blocks.
+    /// Call this from the DMA completion ISR. With the usual full-block
+    /// buffer (`[u32; AUDIO_BLOCK_SAMPLES * 2]`) the whole working block is
+    /// overwritten in one call. Shorter buffers are appended after the
+    /// frames already received, so several calls build up one block.
+    ///
+    /// `LEN` must be even and `LEN / 2` must divide `AUDIO_BLOCK_SAMPLES`;
+    /// this is checked at compile time.
     ///
     /// # Arguments
     ///
-    /// - `dma_buffer`: The DMA receive buffer (`[u32; AUDIO_BLOCK_SAMPLES * 2]`).
+    /// - `dma_buffer`: The DMA receive buffer.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the audio graph should be updated. This happens once per
+    /// block's worth of DMA transfers, and only when `update_responsibility`
+    /// is set.
+    pub fn isr<const LEN: usize>(&mut self, dma_buffer: &[u32; LEN]) -> bool {
+        let frames = DmaLen::<LEN>::FRAMES;
+        if frames == AUDIO_BLOCK_SAMPLES {
+            self.frames_filled = 0;
+        }
+        self.append_frames(dma_buffer);
+
+        self.dma_frames += frames;
+        if self.dma_frames < AUDIO_BLOCK_SAMPLES {
+            return false;
+        }
+        self.dma_frames = 0;
+        self.update_responsibility
+    }
+
+    /// Handle a partial DMA transfer — de-interleave `dma_data` after the
+    /// frames already received.
+    ///
+    /// `dma_data` holds interleaved stereo words (2 per frame). Frames are
+    /// appended to the working blocks; once 128 frames have accumulated the
+    /// blocks are marked ready. Frames that do not fit are discarded.
     ///
     /// # Returns
     ///
     /// `true` if the audio graph should be updated.
-    pub fn isr(
-        &mut self,
-        dma_buffer: &[u32; AUDIO_BLOCK_SAMPLES * 2],
-    ) -> bool {
-        // De-interleave into working blocks
+    pub fn isr_partial(&mut self, dma_data: &[u32]) -> bool {
+        self.append_frames(dma_data);
+        self.update_responsibility
+    }
+
+    /// De-interleave `dma_data` into the working blocks after the frames
+    /// already received, marking them ready once full.
+    fn append_frames(&mut self, dma_data: &[u32]) {
         if let (Some(ref mut left), Some(ref mut right)) =
             (&mut self.block_left, &mut self.block_right)
         {
-            deinterleave(dma_buffer, &mut left[..], &mut right[..]);
-            self.blocks_ready = true;
+            let start = self.frames_filled;
+            let frames = (dma_data.len() / 2).min(AUDIO_BLOCK_SAMPLES - start);
+            deinterleave(
+                &dma_data[..frames * 2],
+                &mut left[start..start + frames],
+                &mut right[start..start + frames],
+            );
+            self.frames_filled = start + frames;
+            if self.frames_filled == AUDIO_BLOCK_SAMPLES {
+                self.blocks_ready = true;
+            }
         }
+    }
 
-        self.update_responsibility
+    /// Force partially-filled working blocks out on the next `update()`.
+    ///
+    /// Use this when the stream stops mid-block so the tail of a recording
+    /// is not lost. The unfilled remainder of each block is zero-padded.
+    /// Has no effect if no frames have been received.
+    pub fn flush(&mut self) {
+        self.flush_pending = true;
     }
 
     /// Whether this input is responsible for triggering graph updates.
@@ -117,11 +179,48 @@ impl AudioInputI2S {
     pub fn blocks_ready(&self) -> bool {
         self.blocks_ready
     }
+
+    /// Number of frames received into the current working blocks.
+    pub fn frames_filled(&self) -> usize {
+        self.frames_filled
+    }
+
+    /// Run [`update()`](AudioNode::update) and hand the captured blocks
+    /// straight to record queues.
+    ///
+    /// Each emitted channel block is shared and passed to its queue (if one
+    /// is attached) exactly as if the queue were wired to that output in the
+    /// graph, so the queue's own start/stop state still applies. The shared
+    /// blocks are returned as `[left, right]` for any further routing.
+    ///
+    /// ```ignore
+    /// // In audio update task, instead of input.update(...):
+    /// let [left, right] = input.update_recording(Some(&mut rec_l), Some(&mut rec_r));
+    /// ```
+    pub fn update_recording(
+        &mut self,
+        left_queue: Option<&mut AudioRecordQueue>,
+        right_queue: Option<&mut AudioRecordQueue>,
+    ) -> [Option<AudioBlockRef>; 2] {
+        let mut outputs = [None, None];
+        self.update(&[], &mut outputs);
+
+        let [left, right] = outputs;
+        let shared = [left.map(|b| b.into_shared()), right.map(|b| b.into_shared())];
+        for (block, queue) in shared.iter().zip([left_queue, right_queue]) {
+            if let (Some(block), Some(queue)) = (block, queue) {
+                queue.update(&[Some(block.clone())], &mut []);
+            }
+        }
+        shared
+    }
 }
 
 impl AudioNode for AudioInputI2S {
     const NUM_INPUTS: usize = 0;
     const NUM_OUTPUTS: usize = 2;
+    const NAME: &'static str = "AudioInputI2S";
+    const OUTPUT_NAMES: &'static [&'static str] = &["left", "right"];
 
     fn update(
         &mut self,
@@ -136,15 +235,22 @@ impl AudioNode for AudioInputI2S {
             None
         };
 
-        if self.blocks_ready {
-            // Working blocks are full — provide them as outputs
-            if let Some(left) = self.block_left.take() {
+        let flushing = self.flush_pending && self.frames_filled > 0;
+        self.flush_pending = false;
+
+        if self.blocks_ready || flushing {
+            // Working blocks are full (or being flushed) — provide them as outputs
+            let filled = self.frames_filled;
+            if let Some(mut left) = self.block_left.take() {
+                left[filled..].fill(0);
                 outputs[0] = Some(left);
             }
-            if let Some(right) = self.block_right.take() {
+            if let Some(mut right) = self.block_right.take() {
+                right[filled..].fill(0);
                 outputs[1] = Some(right);
             }
             self.blocks_ready = false;
+            self.frames_filled = 0;
 
             // Install new working blocks for the next DMA cycle
             if let (Some(nl), Some(nr)) = (new_left, new_right) {
@@ -250,6 +356,110 @@ mod tests {
         }
     }
 
+    #[test]
+    fn flush_emits_partial_block_zero_padded() {
+        reset_pool();
+        let mut input = AudioInputI2S::new(false);
+
+        let mut outputs = [None, None];
+        input.update(&[], &mut outputs); // allocate working blocks
+
+        // Half a block of frames: left = i + 1, right = -(i + 1)
+        let half = AUDIO_BLOCK_SAMPLES / 2;
+        let mut dma_buf = [0u32; AUDIO_BLOCK_SAMPLES];
+        for i in 0..half {
+            dma_buf[i * 2] = ((i as i16 + 1) as u16 as u32) << 16;
+            dma_buf[i * 2 + 1] = ((-(i as i16) - 1) as u16 as u32) << 16;
+        }
+        input.isr_partial(&dma_buf);
+        assert_eq!(input.frames_filled(), half);
+        assert!(!input.blocks_ready());
+
+        // Without a flush, the partial block is held back
+        let mut outputs = [None, None];
+        input.update(&[], &mut outputs);
+        assert!(outputs[0].is_none());
+
+        input.flush();
+        let mut outputs = [None, None];
+        input.update(&[], &mut outputs);
+
+        let left = outputs[0].as_ref().expect("expected flushed left block");
+        let right = outputs[1].as_ref().expect("expected flushed right block");
+        for i in 0..half {
+            assert_eq!(left[i], i as i16 + 1, "left mismatch at {i}");
+            assert_eq!(right[i], -(i as i16) - 1, "right mismatch at {i}");
+        }
+        for i in half..AUDIO_BLOCK_SAMPLES {
+            assert_eq!(left[i], 0, "left padding at {i}");
+            assert_eq!(right[i], 0, "right padding at {i}");
+        }
+        assert_eq!(input.frames_filled(), 0);
+        assert!(input.has_working_blocks());
+    }
+
+    #[test]
+    fn isr_partial_completes_block() {
+        reset_pool();
+        let mut input = AudioInputI2S::new(false);
+        let mut outputs = [None, None];
+        input.update(&[], &mut outputs);
+
+        let dma_buf = [(7u16 as u32) << 16; AUDIO_BLOCK_SAMPLES];
+        input.isr_partial(&dma_buf);
+        input.isr_partial(&dma_buf);
+        assert!(input.blocks_ready());
+
+        let mut outputs = [None, None];
+        input.update(&[], &mut outputs);
+        let left = outputs[0].as_ref().unwrap();
+        assert!(left.iter().all(|&s| s == 7));
+    }
+
+    #[test]
+    fn isr_half_size_buffer_builds_block() {
+        reset_pool();
+        let mut input = AudioInputI2S::new(true);
+        let mut outputs = [None, None];
+        input.update(&[], &mut outputs);
+
+        const HALF: usize = AUDIO_BLOCK_SAMPLES / 2;
+        let mut dma_buf = [0u32; AUDIO_BLOCK_SAMPLES];
+        for half in 0..2 {
+            for i in 0..HALF {
+                let frame = (half * HALF + i) as i16;
+                dma_buf[i * 2] = (frame as u16 as u32) << 16;
+                dma_buf[i * 2 + 1] = ((-frame) as u16 as u32) << 16;
+            }
+            // Only the transfer that completes the block signals an update
+            assert_eq!(input.isr(&dma_buf), half == 1);
+        }
+        assert!(input.blocks_ready());
+
+        let mut outputs = [None, None];
+        input.update(&[], &mut outputs);
+        let left = outputs[0].as_ref().unwrap();
+        let right = outputs[1].as_ref().unwrap();
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            assert_eq!(left[i], i as i16, "left mismatch at {i}");
+            assert_eq!(right[i], -(i as i16), "right mismatch at {i}");
+        }
+    }
+
+    #[test]
+    fn flush_without_data_emits_nothing() {
+        reset_pool();
+        let mut input = AudioInputI2S::new(false);
+        let mut outputs = [None, None];
+        input.update(&[], &mut outputs);
+
+        input.flush();
+        let mut outputs = [None, None];
+        input.update(&[], &mut outputs);
+        assert!(outputs[0].is_none());
+        assert!(outputs[1].is_none());
+    }
+
     #[test]
     fn isr_without_working_blocks_is_safe() {
         let mut input = AudioInputI2S::new(false);
@@ -293,13 +503,66 @@ mod tests {
         assert!(!input.blocks_ready());
     }
 
+    #[test]
+    fn update_recording_captures_blocks_in_order() {
+        reset_pool();
+        let mut input = AudioInputI2S::new(false);
+        let mut rec_left = AudioRecordQueue::new();
+        let mut rec_right = AudioRecordQueue::new();
+        rec_left.start();
+        rec_right.start();
+
+        // Nothing captured yet: only working blocks are allocated
+        let [l, r] = input.update_recording(Some(&mut rec_left), Some(&mut rec_right));
+        assert!(l.is_none() && r.is_none());
+        assert_eq!(rec_left.len(), 0);
+
+        let mut dma_buf = [0u32; AUDIO_BLOCK_SAMPLES * 2];
+        for cycle in 1..=3i16 {
+            for i in 0..AUDIO_BLOCK_SAMPLES {
+                dma_buf[i * 2] = ((cycle * 100) as u16 as u32) << 16;
+                dma_buf[i * 2 + 1] = ((-cycle * 100) as u16 as u32) << 16;
+            }
+            input.isr(&dma_buf);
+            let [l, r] = input.update_recording(Some(&mut rec_left), Some(&mut rec_right));
+            assert_eq!(l.unwrap()[0], cycle * 100);
+            assert_eq!(r.unwrap()[0], -cycle * 100);
+        }
+
+        assert_eq!(rec_left.len(), 3);
+        assert_eq!(rec_right.len(), 3);
+        for cycle in 1..=3i16 {
+            let l = rec_left.read().unwrap();
+            let r = rec_right.read().unwrap();
+            assert!(l.iter().all(|&s| s == cycle * 100));
+            assert!(r.iter().all(|&s| s == -cycle * 100));
+        }
+        assert_eq!(rec_left.available(), 0);
+    }
+
+    #[test]
+    fn update_recording_respects_stopped_queue() {
+        reset_pool();
+        let mut input = AudioInputI2S::new(false);
+        let mut rec_left = AudioRecordQueue::new();
+        rec_left.start();
+        let mut rec_right = AudioRecordQueue::new(); // never started
+
+        input.update_recording(Some(&mut rec_left), None);
+        input.isr(&[0u32; AUDIO_BLOCK_SAMPLES * 2]);
+        let [l, r] = input.update_recording(Some(&mut rec_left), Some(&mut rec_right));
+        assert!(l.is_some() && r.is_some());
+        assert_eq!(rec_left.len(), 1);
+        assert_eq!(rec_right.len(), 0);
+    }
+
     #[test]
     fn pool_exhaustion_handled_gracefully() {
         reset_pool();
         let mut input = AudioInputI2S::new(false);
 
-        // Exhaust the pool (32 blocks = POOL_SIZE)
-        let mut _blocks = [const { None }; 32];
+        // Exhaust the pool
+        let mut _blocks = [const { None }; crate::constants::POOL_SIZE];
         for slot in _blocks.iter_mut() {
             *slot = Some(AudioBlockMut::alloc().unwrap());
         }
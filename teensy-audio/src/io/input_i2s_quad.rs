@@ -0,0 +1,281 @@
+//! DMA-driven quad (two stereo pairs) I2S input.
+//!
+//! [`AudioInputI2SQuad`] reads two I2S data lines' worth of interleaved
+//! stereo audio, e.g. from two codecs or a 4-channel ADC, and provides four
+//! mono blocks to the audio graph. It parallels
+//! [`AudioInputI2S`](super::AudioInputI2S).
+//!
+//! ## DMA Buffer Layout
+//!
+//! - `[u32; AUDIO_BLOCK_SAMPLES * 4]` — 128 frames, 4 words each
+//! - Each frame: `[left_1, right_1, left_2, right_2]`, MSB-aligned like the
+//!   stereo input (see [`deinterleave_quad()`])
+//!
+//! Shorter buffers are accepted: [`isr()`] is generic over the buffer
+//! length, which must be a multiple of 4 words whose frame count divides
+//! 128 (checked at compile time).
+//!
+//! [`isr()`]: AudioInputI2SQuad::isr
+//!
+//! ## Reference
+//!
+//! Ported from `TeensyAudio/input_i2s_quad.cpp`.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::node::AudioNode;
+
+use super::interleave::{deinterleave_quad, DmaLen};
+
+/// Number of channels (outputs) of the quad input.
+pub const QUAD_CHANNELS: usize = 4;
+
+/// DMA buffer size in `u32` words: 4 words per frame.
+pub const QUAD_DMA_BUFFER_WORDS: usize = AUDIO_BLOCK_SAMPLES * QUAD_CHANNELS;
+
+/// DMA-driven quad I2S input node.
+///
+/// Implements [`AudioNode`] with 0 inputs and 4 outputs: line 1 left,
+/// line 1 right, line 2 left, line 2 right (named `left1`, `right1`,
+/// `left2`, `right2`).
+///
+/// Works like [`AudioInputI2S`](super::AudioInputI2S): the ISR fills four
+/// working blocks and [`update()`](AudioNode::update) hands them to the
+/// graph once full, allocating fresh ones. The four working blocks are
+/// allocated together: if the pool can't supply all of them, none are
+/// taken.
+pub struct AudioInputI2SQuad {
+    /// Working blocks being filled by the ISR, one per channel.
+    blocks: [Option<AudioBlockMut>; QUAD_CHANNELS],
+    /// Whether the working blocks are filled and ready for output.
+    blocks_ready: bool,
+    /// Number of frames written into the working blocks so far.
+    frames_filled: usize,
+    /// Frames received by [`isr()`](Self::isr) since the last block boundary.
+    dma_frames: usize,
+    /// If `true`, this node's ISR triggers the audio graph update cycle.
+    update_responsibility: bool,
+}
+
+impl AudioInputI2SQuad {
+    /// Create a new quad I2S input node.
+    ///
+    /// # Arguments
+    ///
+    /// - `update_responsibility`: If `true`, this node's ISR will signal
+    ///   that the audio graph should be updated.
+    pub const fn new(update_responsibility: bool) -> Self {
+        AudioInputI2SQuad {
+            blocks: [const { None }; QUAD_CHANNELS],
+            blocks_ready: false,
+            frames_filled: 0,
+            dma_frames: 0,
+            update_responsibility,
+        }
+    }
+
+    /// Handle DMA interrupt — de-interleave the completed RX buffer.
+    ///
+    /// With the usual full-block buffer (`[u32; AUDIO_BLOCK_SAMPLES * 4]`)
+    /// the working blocks are overwritten in one call; shorter buffers are
+    /// appended after the frames already received.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the audio graph should be updated. This happens once per
+    /// block's worth of DMA transfers, and only when `update_responsibility`
+    /// is set.
+    pub fn isr<const LEN: usize>(&mut self, dma_buffer: &[u32; LEN]) -> bool {
+        let frames = DmaLen::<LEN>::FOUR_WORD_FRAMES;
+        if frames == AUDIO_BLOCK_SAMPLES {
+            self.frames_filled = 0;
+        }
+
+        if let [Some(a), Some(b), Some(c), Some(d)] = &mut self.blocks {
+            let start = self.frames_filled;
+            let end = (start + frames).min(AUDIO_BLOCK_SAMPLES);
+            deinterleave_quad(
+                &dma_buffer[..(end - start) * 4],
+                [
+                    &mut a[start..end],
+                    &mut b[start..end],
+                    &mut c[start..end],
+                    &mut d[start..end],
+                ],
+            );
+            self.frames_filled = end;
+            if end == AUDIO_BLOCK_SAMPLES {
+                self.blocks_ready = true;
+            }
+        }
+
+        self.dma_frames += frames;
+        if self.dma_frames < AUDIO_BLOCK_SAMPLES {
+            return false;
+        }
+        self.dma_frames = 0;
+        self.update_responsibility
+    }
+
+    /// Whether this input is responsible for triggering graph updates.
+    pub fn has_update_responsibility(&self) -> bool {
+        self.update_responsibility
+    }
+
+    /// Whether the input currently has working blocks allocated.
+    pub fn has_working_blocks(&self) -> bool {
+        self.blocks.iter().all(Option::is_some)
+    }
+
+    /// Whether the working blocks are filled and ready for output.
+    pub fn blocks_ready(&self) -> bool {
+        self.blocks_ready
+    }
+
+    /// Allocate one block per channel, or none if the pool runs short.
+    fn alloc_all() -> Option<[AudioBlockMut; QUAD_CHANNELS]> {
+        // Blocks allocated before a failure drop back to the pool
+        let [a, b, c, d] = core::array::from_fn(|_| AudioBlockMut::alloc());
+        Some([a?, b?, c?, d?])
+    }
+}
+
+impl AudioNode for AudioInputI2SQuad {
+    const NUM_INPUTS: usize = 0;
+    const NUM_OUTPUTS: usize = QUAD_CHANNELS;
+    const NAME: &'static str = "AudioInputI2SQuad";
+    const OUTPUT_NAMES: &'static [&'static str] = &["left1", "right1", "left2", "right2"];
+
+    fn update(&mut self, _inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        let new_blocks = Self::alloc_all();
+
+        if self.blocks_ready {
+            // Working blocks are full — provide them as outputs
+            for (output, block) in outputs.iter_mut().zip(self.blocks.iter_mut()) {
+                *output = block.take();
+            }
+            self.blocks_ready = false;
+            self.frames_filled = 0;
+        } else if self.has_working_blocks() {
+            // Not ready yet: keep filling the current blocks, discard the new ones
+            return;
+        }
+
+        // Install new working blocks for the next DMA cycle
+        if let Some(new_blocks) = new_blocks {
+            self.blocks = new_blocks.map(Some);
+        }
+        // else: couldn't allocate — nothing we can do
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::constants::POOL_SIZE;
+    use crate::io::interleave::interleave_lr;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Build a quad DMA buffer the way two stereo transmitters would:
+    /// each line carries an interleaved L/R pair.
+    fn loopback_buffer(
+        channels: &[[i16; AUDIO_BLOCK_SAMPLES]; QUAD_CHANNELS],
+    ) -> [u32; QUAD_DMA_BUFFER_WORDS] {
+        let mut line1 = [0u32; AUDIO_BLOCK_SAMPLES * 2];
+        let mut line2 = [0u32; AUDIO_BLOCK_SAMPLES * 2];
+        interleave_lr(&mut line1, &channels[0], &channels[1]);
+        interleave_lr(&mut line2, &channels[2], &channels[3]);
+
+        let mut dma_buf = [0u32; QUAD_DMA_BUFFER_WORDS];
+        for (i, frame) in dma_buf.chunks_exact_mut(4).enumerate() {
+            frame[..2].copy_from_slice(&line1[i * 2..i * 2 + 2]);
+            frame[2..].copy_from_slice(&line2[i * 2..i * 2 + 2]);
+        }
+        dma_buf
+    }
+
+    #[test]
+    fn loopback_delivers_four_channels() {
+        reset_pool();
+        let mut input = AudioInputI2SQuad::new(true);
+        let mut outputs: [Option<AudioBlockMut>; QUAD_CHANNELS] = Default::default();
+        input.update(&[], &mut outputs);
+        assert!(input.has_working_blocks());
+        assert!(outputs.iter().all(Option::is_none));
+
+        let channels: [[i16; AUDIO_BLOCK_SAMPLES]; QUAD_CHANNELS] =
+            core::array::from_fn(|ch| core::array::from_fn(|i| (ch as i16 + 1) * 1000 - i as i16));
+        assert!(input.isr(&loopback_buffer(&channels)));
+        assert!(input.blocks_ready());
+
+        input.update(&[], &mut outputs);
+        for (ch, output) in outputs.iter().enumerate() {
+            assert_eq!(
+                &output.as_ref().unwrap()[..],
+                &channels[ch][..],
+                "channel {ch}"
+            );
+        }
+        assert!(input.has_working_blocks());
+        assert!(!input.blocks_ready());
+    }
+
+    #[test]
+    fn quarter_buffers_build_block() {
+        reset_pool();
+        let mut input = AudioInputI2SQuad::new(true);
+        let mut outputs: [Option<AudioBlockMut>; QUAD_CHANNELS] = Default::default();
+        input.update(&[], &mut outputs);
+
+        const QUARTER: usize = AUDIO_BLOCK_SAMPLES / 4;
+        let mut dma_buf = [0u32; QUARTER * 4];
+        for part in 0..4 {
+            for (i, frame) in dma_buf.chunks_exact_mut(4).enumerate() {
+                let n = (part * QUARTER + i) as i16;
+                for (ch, word) in frame.iter_mut().enumerate() {
+                    *word = ((n + ch as i16 * 200) as u16 as u32) << 16;
+                }
+            }
+            assert_eq!(input.isr(&dma_buf), part == 3);
+        }
+
+        input.update(&[], &mut outputs);
+        let ch3 = outputs[3].as_ref().unwrap();
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            assert_eq!(ch3[i], i as i16 + 600, "mismatch at {i}");
+        }
+    }
+
+    #[test]
+    fn isr_without_working_blocks_is_safe() {
+        let mut input = AudioInputI2SQuad::new(false);
+        assert!(!input.isr(&[0u32; QUAD_DMA_BUFFER_WORDS]));
+        assert!(!input.blocks_ready());
+    }
+
+    #[test]
+    fn pool_shortage_allocates_none() {
+        reset_pool();
+        let mut input = AudioInputI2SQuad::new(false);
+
+        // Leave only three free blocks: not enough for all four channels
+        let mut _blocks = [const { None }; POOL_SIZE - 3];
+        for slot in _blocks.iter_mut() {
+            *slot = Some(AudioBlockMut::alloc().unwrap());
+        }
+
+        let mut outputs: [Option<AudioBlockMut>; QUAD_CHANNELS] = Default::default();
+        input.update(&[], &mut outputs);
+        assert!(!input.has_working_blocks());
+        assert!(input.blocks.iter().all(Option::is_none));
+        assert_eq!(
+            POOL.allocated_count() as usize,
+            POOL_SIZE - 3,
+            "partial allocation released"
+        );
+    }
+}
@@ -19,6 +19,9 @@
 //!
 //! A buffer of `N` mono samples produces `N * 2` u32 words.
 
+#[cfg(feature = "dsp")]
+use crate::dsp::intrinsics::saturate16;
+
 /// Interleave left and right channel samples into I2S stereo DMA format.
 ///
 /// Each frame becomes two `u32` words: left (MSB-aligned), then right (MSB-aligned).
@@ -68,6 +71,68 @@ pub fn interleave_r(dest: &mut [u32], right: &[i16]) {
     }
 }
 
+/// Scale one sample by a Q15 gain and MSB-align it for an I2S word.
+#[cfg(feature = "dsp")]
+#[inline(always)]
+fn gain_word(sample: i16, gain_q15: i32) -> u32 {
+    (saturate16((sample as i32 * gain_q15) >> 15) as u16 as u32) << 16
+}
+
+/// Interleave left and right channels into I2S stereo DMA format, applying
+/// a Q15 gain on the way.
+///
+/// Each sample becomes `saturate16((sample * gain_q15) >> 15)`, bit-exact
+/// with [`block_multiply()`](crate::dsp::helpers::block_multiply) followed
+/// by [`interleave_lr()`], but in a single pass. `gain_q15` is 32768 for
+/// unity; keep its magnitude below 65536 (just under +6 dB) so the product
+/// stays in `i32` range.
+///
+/// # Panics
+///
+/// Debug-asserts that `dest.len() == left.len() * 2` and `left.len() == right.len()`.
+#[cfg(feature = "dsp")]
+pub fn interleave_lr_gain(dest: &mut [u32], left: &[i16], right: &[i16], gain_q15: i32) {
+    debug_assert_eq!(dest.len(), left.len() * 2);
+    debug_assert_eq!(left.len(), right.len());
+
+    for i in 0..left.len() {
+        dest[i * 2] = gain_word(left[i], gain_q15);
+        dest[i * 2 + 1] = gain_word(right[i], gain_q15);
+    }
+}
+
+/// Interleave the left channel with a Q15 gain; the right channel is
+/// silent. See [`interleave_lr_gain()`].
+///
+/// # Panics
+///
+/// Debug-asserts that `dest.len() == left.len() * 2`.
+#[cfg(feature = "dsp")]
+pub fn interleave_l_gain(dest: &mut [u32], left: &[i16], gain_q15: i32) {
+    debug_assert_eq!(dest.len(), left.len() * 2);
+
+    for i in 0..left.len() {
+        dest[i * 2] = gain_word(left[i], gain_q15);
+        dest[i * 2 + 1] = 0;
+    }
+}
+
+/// Interleave the right channel with a Q15 gain; the left channel is
+/// silent. See [`interleave_lr_gain()`].
+///
+/// # Panics
+///
+/// Debug-asserts that `dest.len() == right.len() * 2`.
+#[cfg(feature = "dsp")]
+pub fn interleave_r_gain(dest: &mut [u32], right: &[i16], gain_q15: i32) {
+    debug_assert_eq!(dest.len(), right.len() * 2);
+
+    for i in 0..right.len() {
+        dest[i * 2] = 0;
+        dest[i * 2 + 1] = gain_word(right[i], gain_q15);
+    }
+}
+
 /// Deinterleave I2S stereo DMA buffer into separate left and right channels.
 ///
 /// Reads the upper 16 bits of each `u32` word (MSB-aligned samples).
@@ -85,11 +150,127 @@ pub fn deinterleave(src: &[u32], left: &mut [i16], right: &mut [i16]) {
     }
 }
 
+/// Pack left and right samples into MQS DMA format, one `u32` per frame.
+///
+/// The MQS path runs SAI3 with 16-bit words, so each frame is a pair of
+/// halfwords read in memory order: left in bits 15–0, right in bits 31–16.
+/// A missing channel is written as silence.
+///
+/// # Panics
+///
+/// Debug-asserts that each present channel has `dest.len()` samples.
+pub fn interleave_packed(dest: &mut [u32], left: Option<&[i16]>, right: Option<&[i16]>) {
+    debug_assert!(left.is_none_or(|l| l.len() == dest.len()));
+    debug_assert!(right.is_none_or(|r| r.len() == dest.len()));
+
+    for (i, word) in dest.iter_mut().enumerate() {
+        let l = left.map_or(0, |l| l[i]) as u16 as u32;
+        let r = right.map_or(0, |r| r[i]) as u16 as u32;
+        *word = (r << 16) | l;
+    }
+}
+
+/// Channels per TDM frame, see [`channel_interleave()`].
+pub const TDM_CHANNELS: usize = 8;
+
+/// `u32` words per TDM frame: two 16-bit channels per word.
+pub const TDM_WORDS_PER_FRAME: usize = TDM_CHANNELS / 2;
+
+/// Interleave eight channels into TDM DMA format.
+///
+/// Each frame becomes [`TDM_WORDS_PER_FRAME`] words; word `k` carries
+/// channel `2k` in bits 31–16 and channel `2k + 1` in bits 15–0, so the
+/// slots go out in channel order MSB first. Missing channels are written
+/// as silence.
+///
+/// # Panics
+///
+/// Debug-asserts that each present channel has
+/// `dest.len() / TDM_WORDS_PER_FRAME` samples.
+pub fn channel_interleave(dest: &mut [u32], channels: &[Option<&[i16]>; TDM_CHANNELS]) {
+    let frames = dest.len() / TDM_WORDS_PER_FRAME;
+    debug_assert_eq!(dest.len(), frames * TDM_WORDS_PER_FRAME);
+    debug_assert!(channels.iter().flatten().all(|c| c.len() == frames));
+
+    for (pair, chunk) in channels.chunks_exact(2).enumerate() {
+        for i in 0..frames {
+            let hi = chunk[0].map_or(0, |c| c[i]) as u16 as u32;
+            let lo = chunk[1].map_or(0, |c| c[i]) as u16 as u32;
+            dest[i * TDM_WORDS_PER_FRAME + pair] = (hi << 16) | lo;
+        }
+    }
+}
+
+/// Deinterleave a quad (two stereo data lines) I2S DMA buffer into four
+/// channels.
+///
+/// Each frame is four MSB-aligned words: line 1 left, line 1 right,
+/// line 2 left, line 2 right, written to `channels[0..4]` in that order.
+///
+/// # Panics
+///
+/// Debug-asserts that every channel holds `src.len() / 4` samples.
+pub fn deinterleave_quad(src: &[u32], channels: [&mut [i16]; 4]) {
+    debug_assert!(channels.iter().all(|c| c.len() * 4 == src.len()));
+
+    for (ch, dest) in channels.into_iter().enumerate() {
+        for (i, sample) in dest.iter_mut().enumerate() {
+            *sample = (src[i * 4 + ch] >> 16) as i16;
+        }
+    }
+}
+
 /// Fill a region of the DMA buffer with silence (zero for both channels).
 pub fn silence(dest: &mut [u32]) {
     dest.fill(0);
 }
 
+/// Compile-time checks for a DMA buffer of `LEN` words.
+///
+/// The buffer must hold a whole number of stereo frames, and that frame
+/// count must divide `AUDIO_BLOCK_SAMPLES` so every audio block is split
+/// across an exact number of DMA transfers.
+pub(crate) struct DmaLen<const LEN: usize>;
+
+impl<const LEN: usize> DmaLen<LEN> {
+    /// Stereo frames per DMA transfer.
+    pub(crate) const FRAMES: usize = {
+        assert!(
+            LEN >= 2 && LEN.is_multiple_of(2),
+            "DMA buffer length must be a non-zero even number of words"
+        );
+        assert!(
+            crate::constants::AUDIO_BLOCK_SAMPLES.is_multiple_of(LEN / 2),
+            "DMA buffer frames must divide AUDIO_BLOCK_SAMPLES"
+        );
+        LEN / 2
+    };
+
+    /// Stereo frames per DMA transfer for packed (one word per frame)
+    /// buffers, see [`interleave_packed()`].
+    pub(crate) const PACKED_FRAMES: usize = {
+        assert!(
+            LEN >= 1 && crate::constants::AUDIO_BLOCK_SAMPLES.is_multiple_of(LEN),
+            "packed DMA buffer length must divide AUDIO_BLOCK_SAMPLES"
+        );
+        LEN
+    };
+
+    /// Frames per DMA transfer for four-word frames: TDM buffers (see
+    /// [`channel_interleave()`]) and quad I2S (see [`deinterleave_quad()`]).
+    pub(crate) const FOUR_WORD_FRAMES: usize = {
+        assert!(
+            LEN >= 4 && LEN.is_multiple_of(4),
+            "DMA buffer length must be a non-zero multiple of 4 words"
+        );
+        assert!(
+            crate::constants::AUDIO_BLOCK_SAMPLES.is_multiple_of(LEN / 4),
+            "DMA buffer frames must divide AUDIO_BLOCK_SAMPLES"
+        );
+        LEN / 4
+    };
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -191,6 +372,73 @@ mod tests {
         deinterleave(&[], &mut left, &mut right);
     }
 
+    #[test]
+    fn interleave_packed_layout() {
+        let left = [100i16, -1];
+        let right = [-2i16, 300];
+        let mut dest = [0u32; 2];
+
+        interleave_packed(&mut dest, Some(&left), Some(&right));
+        assert_eq!(dest, [0xFFFE_0064, 0x012C_FFFF]);
+
+        interleave_packed(&mut dest, Some(&left), None);
+        assert_eq!(dest, [0x0000_0064, 0x0000_FFFF]);
+
+        interleave_packed(&mut dest, None, Some(&right));
+        assert_eq!(dest, [0xFFFE_0000, 0x012C_0000]);
+    }
+
+    #[test]
+    fn channel_interleave_layout() {
+        let ch: [[i16; 2]; TDM_CHANNELS] = core::array::from_fn(|c| {
+            let base = (c as i16 + 1) * 100;
+            [base, -base]
+        });
+        let channels = core::array::from_fn(|c| Some(&ch[c][..]));
+        let mut dest = [0u32; 2 * TDM_WORDS_PER_FRAME];
+
+        channel_interleave(&mut dest, &channels);
+
+        for frame in 0..2 {
+            for c in 0..TDM_CHANNELS {
+                let word = dest[frame * TDM_WORDS_PER_FRAME + c / 2];
+                let sample = if c.is_multiple_of(2) {
+                    (word >> 16) as i16
+                } else {
+                    word as u16 as i16
+                };
+                assert_eq!(sample, ch[c][frame], "frame {frame} channel {c}");
+            }
+        }
+    }
+
+    #[test]
+    fn channel_interleave_missing_channels_are_silent() {
+        let data = [i16::MIN, i16::MAX];
+        let mut channels = [None; TDM_CHANNELS];
+        channels[3] = Some(&data[..]);
+        let mut dest = [0xDEAD_BEEFu32; 2 * TDM_WORDS_PER_FRAME];
+
+        channel_interleave(&mut dest, &channels);
+
+        assert_eq!(dest, [0, 0x0000_8000, 0, 0, 0, 0x0000_7FFF, 0, 0]);
+    }
+
+    #[test]
+    fn deinterleave_quad_basic() {
+        let msb = |v: i16| (v as u16 as u32) << 16;
+        // Two frames: [L1, R1, L2, R2]
+        let src = [1, 2, 3, 4, -1, -2, -3, -4].map(msb);
+        let (mut a, mut b, mut c, mut d) = ([0i16; 2], [0i16; 2], [0i16; 2], [0i16; 2]);
+
+        deinterleave_quad(&src, [&mut a, &mut b, &mut c, &mut d]);
+
+        assert_eq!(a, [1, -1]);
+        assert_eq!(b, [2, -2]);
+        assert_eq!(c, [3, -3]);
+        assert_eq!(d, [4, -4]);
+    }
+
     #[test]
     fn silence_zeroes_buffer() {
         let mut buf = [0xDEAD_BEEFu32; 8];
@@ -213,4 +461,46 @@ mod tests {
         assert_eq!(out_left, [i16::MIN, i16::MAX]);
         assert_eq!(out_right, [i16::MAX, i16::MIN]);
     }
+
+    /// Pseudo-random full-range block (xorshift32).
+    #[cfg(feature = "dsp")]
+    fn noise_block(seed: u32) -> [i16; crate::constants::AUDIO_BLOCK_SAMPLES] {
+        let mut x = seed;
+        core::array::from_fn(|_| {
+            x ^= x << 13;
+            x ^= x >> 17;
+            x ^= x << 5;
+            x as i16
+        })
+    }
+
+    #[cfg(feature = "dsp")]
+    #[test]
+    fn interleave_gain_matches_separate_passes() {
+        use crate::constants::AUDIO_BLOCK_SAMPLES;
+        use crate::dsp::helpers::block_multiply;
+
+        // Cut, unity, boost into saturation, and inverted
+        for (n, &gain) in [9830i32, 32768, 52000, -40000].iter().enumerate() {
+            let left = noise_block(0x1234_5678 + n as u32);
+            let right = noise_block(0x9E37_79B9 ^ n as u32);
+            let (mut l_ref, mut r_ref) = (left, right);
+            block_multiply(&mut l_ref, gain);
+            block_multiply(&mut r_ref, gain);
+
+            let mut fused = [0u32; AUDIO_BLOCK_SAMPLES * 2];
+            let mut reference = [0u32; AUDIO_BLOCK_SAMPLES * 2];
+            interleave_lr_gain(&mut fused, &left, &right, gain);
+            interleave_lr(&mut reference, &l_ref, &r_ref);
+            assert_eq!(fused, reference, "lr, gain {}", gain);
+
+            interleave_l_gain(&mut fused, &left, gain);
+            interleave_l(&mut reference, &l_ref);
+            assert_eq!(fused, reference, "l, gain {}", gain);
+
+            interleave_r_gain(&mut fused, &right, gain);
+            interleave_r(&mut reference, &r_ref);
+            assert_eq!(fused, reference, "r, gain {}", gain);
+        }
+    }
 }
@@ -1,20 +1,29 @@
 //! I/O drivers for the audio processing graph.
 //!
-//! This module provides DMA-driven I2S input/output nodes and user-facing
-//! queue buffers for injecting/extracting audio data.
+//! This module provides DMA-driven I2S input/output nodes, an on-chip MQS
+//! output for boards without a codec, and user-facing queue buffers for
+//! injecting/extracting audio data.
 //!
 //! ## Components
 //!
 //! | Node | Inputs | Outputs | Description |
 //! |------|--------|---------|-------------|
 //! | [`AudioOutputI2S`] | 2 (L, R) | 0 | DMA-driven I2S stereo output |
+//! | [`AudioOutputMQS`] | 2 (L, R) | 0 | DMA-driven on-chip MQS stereo output |
+//! | [`AudioOutputTDM`] | 8 | 0 | DMA-driven 8-channel TDM output |
 //! | [`AudioInputI2S`] | 0 | 2 (L, R) | DMA-driven I2S stereo input |
+//! | [`AudioInputI2SQuad`] | 0 | 4 (L1, R1, L2, R2) | DMA-driven two-line I2S input |
 //! | [`AudioPlayQueue`] | 0 | 1 | User code → audio graph |
+//! | [`AudioPlayMemory`] | 0 | 1 | One-shot `i16` clip from memory |
 //! | [`AudioRecordQueue`] | 1 | 0 | Audio graph → user code |
 //!
+//! [`AudioSystem`] bundles a codec and a graph holding one of the output
+//! nodes (anything implementing [`DmaOutput`]) and dispatches the DMA
+//! interrupt.
+//!
 //! ## Utilities
 //!
-//! - [`interleave`] — Stereo interleave/deinterleave for DMA buffers
+//! - [`interleave`] — Stereo, quad and TDM interleave/deinterleave for DMA buffers
 //! - [`spsc`] — Lock-free single-producer single-consumer ring buffer
 //!
 //! ## DMA Buffer Layout
@@ -23,18 +32,34 @@
 //! - Each stereo frame occupies 2 `u32` words: `[left_msb_aligned, right_msb_aligned]`
 //! - 16-bit samples are placed in the upper 16 bits of each 32-bit word (`<< 16`)
 //! - DMA runs in one-shot mode: ISR fills the buffer and re-arms DMA
+//!
+//! Shorter buffers trade more interrupts for lower latency. The `isr()`
+//! methods are generic over the buffer length `LEN`, which must be even with
+//! `LEN / 2` dividing `AUDIO_BLOCK_SAMPLES` (checked at compile time); the
+//! graph update is then signalled once every `AUDIO_BLOCK_SAMPLES * 2 / LEN`
+//! interrupts.
 
 pub mod interleave;
 pub mod spsc;
 pub mod output_i2s;
+pub mod output_mqs;
+pub mod output_tdm;
 pub mod input_i2s;
+pub mod input_i2s_quad;
 pub mod play_queue;
+pub mod play_memory;
 pub mod record_queue;
+pub mod system;
 
 pub use output_i2s::AudioOutputI2S;
+pub use output_mqs::AudioOutputMQS;
+pub use output_tdm::AudioOutputTDM;
 pub use input_i2s::AudioInputI2S;
+pub use input_i2s_quad::AudioInputI2SQuad;
 pub use play_queue::AudioPlayQueue;
+pub use play_memory::AudioPlayMemory;
 pub use record_queue::AudioRecordQueue;
+pub use system::{AudioGraph, AudioSystem, DmaOutput};
 
 #[cfg(test)]
 mod integration_tests;
@@ -21,6 +21,13 @@
 //! - DMA runs in one-shot mode: the ISR fills the buffer and re-arms DMA
 //! - One ISR call per audio block (128 samples)
 //!
+//! Shorter DMA buffers are also accepted for lower latency: [`isr()`]
+//! is generic over the buffer length, which must be an even number of
+//! words whose frame count divides 128 (e.g. `[u32; 128]` for 64-frame
+//! transfers, two interrupts per block). This is checked at compile time.
+//!
+//! [`isr()`]: AudioOutputI2S::isr
+//!
 //! ## Usage with RTIC
 //!
 //! ```ignore
@@ -43,18 +50,26 @@ use crate::block::{AudioBlockMut, AudioBlockRef};
 use crate::constants::AUDIO_BLOCK_SAMPLES;
 use crate::node::AudioNode;
 
-use super::interleave::{interleave_l, interleave_lr, interleave_r};
+use super::interleave::{interleave_l, interleave_lr, interleave_r, DmaLen};
+use super::system::DmaOutput;
+#[cfg(feature = "dsp")]
+use crate::dsp::OnePole;
 
 /// DMA buffer size in `u32` words: 2 words per stereo frame.
 pub const DMA_BUFFER_WORDS: usize = AUDIO_BLOCK_SAMPLES * 2;
 
+/// Cutoff of the optional output DC blocker, in Hz.
+#[cfg(feature = "dsp")]
+pub const DC_BLOCK_CUTOFF_HZ: f32 = 5.0;
+
 /// DMA-driven I2S stereo output node.
 ///
 /// Implements [`AudioNode`] with 2 inputs (left, right) and 0 outputs.
 ///
 /// This node uses double-buffering: [`update()`](AudioNode::update) queues audio blocks from the
 /// graph, and the DMA ISR (via [`isr()`](Self::isr)) interleaves them into the DMA buffer.
-/// Each ISR call consumes one full audio block (128 samples).
+/// With a full-size DMA buffer each ISR call consumes one whole audio block
+/// (128 samples); with a shorter buffer each call consumes the next slice.
 pub struct AudioOutputI2S {
     /// First block being actively transmitted (left channel).
     block_left_1st: Option<AudioBlockRef>,
@@ -66,6 +81,13 @@ pub struct AudioOutputI2S {
     block_right_2nd: Option<AudioBlockRef>,
     /// If `true`, this node's ISR triggers the audio graph update cycle.
     update_responsibility: bool,
+    /// Number of ISR calls that signalled a graph update.
+    update_cycles: u64,
+    /// Frames of the current block already sent to DMA.
+    frames_sent: usize,
+    /// Per-channel DC blockers, `[left, right]`, when enabled.
+    #[cfg(feature = "dsp")]
+    dc_block: Option<[OnePole; 2]>,
 }
 
 impl AudioOutputI2S {
@@ -83,50 +105,100 @@ impl AudioOutputI2S {
             block_right_1st: None,
             block_right_2nd: None,
             update_responsibility,
+            update_cycles: 0,
+            frames_sent: 0,
+            #[cfg(feature = "dsp")]
+            dc_block: None,
         }
     }
 
-    /// Handle DMA interrupt — fill the entire DMA buffer with one audio block.
+    /// Enable or disable a DC blocker on the output.
+    ///
+    /// When enabled, each channel passes through a one-pole highpass at
+    /// [`DC_BLOCK_CUTOFF_HZ`] as it is written to the DMA buffer, so a DC
+    /// offset anywhere in the graph never reaches the DAC and speakers.
+    /// Enabling starts from a cleared filter state.
+    #[cfg(feature = "dsp")]
+    pub fn set_dc_block(&mut self, enable: bool) {
+        self.dc_block = enable.then(|| {
+            let mut filter = OnePole::new();
+            filter.set_highpass(DC_BLOCK_CUTOFF_HZ);
+            [filter.clone(), filter]
+        });
+    }
+
+    /// Handle DMA interrupt — fill the DMA buffer from the current audio block.
     ///
-    /// Call this from the DMA completion ISR. It interleaves the current
-    /// left/right audio blocks into the DMA buffer, then rotates the
-    /// double-buffer queue.
+    /// Call this from the DMA completion ISR. It interleaves the next
+    /// `LEN / 2` frames of the current left/right audio blocks into the DMA
+    /// buffer. Once a whole block has been sent the double-buffer queue
+    /// rotates.
+    ///
+    /// `LEN` must be even and `LEN / 2` must divide `AUDIO_BLOCK_SAMPLES`;
+    /// the usual full-block buffer is `[u32; AUDIO_BLOCK_SAMPLES * 2]`.
     ///
     /// # Arguments
     ///
-    /// - `dma_buffer`: The DMA transmit buffer (`[u32; AUDIO_BLOCK_SAMPLES * 2]`).
+    /// - `dma_buffer`: The DMA transmit buffer.
     ///
     /// # Returns
     ///
     /// `true` if the audio graph should be updated (i.e., `update_all()`
-    /// should be called), when `update_responsibility` is set.
-    pub fn isr(
-        &mut self,
-        dma_buffer: &mut [u32; AUDIO_BLOCK_SAMPLES * 2],
-    ) -> bool {
+    /// should be called). This happens once per block, on the call that
+    /// finishes it, and only when `update_responsibility` is set.
+    pub fn isr<const LEN: usize>(&mut self, dma_buffer: &mut [u32; LEN]) -> bool {
+        let frames = DmaLen::<LEN>::FRAMES;
+        let range = self.frames_sent..self.frames_sent + frames;
+
         // Interleave audio data into the DMA buffer
         match (&self.block_left_1st, &self.block_right_1st) {
             (Some(left), Some(right)) => {
-                interleave_lr(dma_buffer, &left[..], &right[..]);
+                interleave_lr(dma_buffer, &left[range.clone()], &right[range]);
             }
             (Some(left), None) => {
-                interleave_l(dma_buffer, &left[..]);
+                interleave_l(dma_buffer, &left[range]);
             }
             (None, Some(right)) => {
-                interleave_r(dma_buffer, &right[..]);
+                interleave_r(dma_buffer, &right[range]);
             }
             (None, None) => {
                 dma_buffer.fill(0);
             }
         }
 
+        #[cfg(feature = "dsp")]
+        if let Some([left, right]) = &mut self.dc_block {
+            for frame in dma_buffer.chunks_exact_mut(2) {
+                frame[0] = (left.process((frame[0] >> 16) as i16) as u16 as u32) << 16;
+                frame[1] = (right.process((frame[1] >> 16) as i16) as u16 as u32) << 16;
+            }
+        }
+
+        self.frames_sent += frames;
+        if self.frames_sent < AUDIO_BLOCK_SAMPLES {
+            return false;
+        }
+        self.frames_sent = 0;
+
         // Rotate: consume 1st block, promote 2nd → 1st
         self.block_left_1st = self.block_left_2nd.take();
         self.block_right_1st = self.block_right_2nd.take();
 
+        if self.update_responsibility {
+            self.update_cycles = self.update_cycles.wrapping_add(1);
+        }
         self.update_responsibility
     }
 
+    /// Number of ISR calls so far that signalled a graph update.
+    ///
+    /// A watchdog can sample this periodically: if it stops advancing, the
+    /// DMA interrupt (and therefore the audio pipeline) has stalled. Always
+    /// zero for an output without update responsibility.
+    pub fn update_cycles(&self) -> u64 {
+        self.update_cycles
+    }
+
     /// Whether this output is responsible for triggering graph updates.
     pub fn has_update_responsibility(&self) -> bool {
         self.update_responsibility
@@ -143,9 +215,20 @@ impl AudioOutputI2S {
     }
 }
 
+impl DmaOutput for AudioOutputI2S {
+    fn new_responsible() -> Self {
+        AudioOutputI2S::new(true)
+    }
+
+    fn isr<const LEN: usize>(&mut self, dma_buffer: &mut [u32; LEN]) -> bool {
+        AudioOutputI2S::isr(self, dma_buffer)
+    }
+}
+
 impl AudioNode for AudioOutputI2S {
     const NUM_INPUTS: usize = 2;
     const NUM_OUTPUTS: usize = 0;
+    const NAME: &'static str = "AudioOutputI2S";
 
     fn update(
         &mut self,
@@ -382,4 +465,113 @@ mod tests {
             );
         }
     }
+
+    #[test]
+    fn update_cycles_counts_signalled_updates() {
+        let mut output_responsible = AudioOutputI2S::new(true);
+        let mut output_not = AudioOutputI2S::new(false);
+        let mut dma_buf = [0u32; AUDIO_BLOCK_SAMPLES * 2];
+        assert_eq!(output_responsible.update_cycles(), 0);
+
+        for n in 1..=5 {
+            assert!(output_responsible.isr(&mut dma_buf));
+            assert_eq!(output_responsible.update_cycles(), n);
+            output_not.isr(&mut dma_buf);
+        }
+        assert_eq!(output_not.update_cycles(), 0);
+    }
+
+    #[test]
+    fn isr_half_size_buffer_splits_block() {
+        reset_pool();
+        let mut output = AudioOutputI2S::new(true);
+        output.update(&[Some(make_ramp_block(0)), Some(make_ramp_block(1000))], &mut []);
+        output.update(&[Some(make_block(7)), Some(make_block(8))], &mut []);
+
+        const HALF: usize = AUDIO_BLOCK_SAMPLES / 2;
+        let mut dma_buf = [0u32; AUDIO_BLOCK_SAMPLES];
+
+        // First half of the block: no graph update yet
+        assert!(!output.isr(&mut dma_buf));
+        assert_eq!(output.update_cycles(), 0);
+        for i in 0..HALF {
+            assert_eq!((dma_buf[i * 2] >> 16) as i16, i as i16);
+            assert_eq!((dma_buf[i * 2 + 1] >> 16) as i16, 1000 + i as i16);
+        }
+
+        // Second half continues the same block, then rotates
+        assert!(output.isr(&mut dma_buf));
+        assert_eq!(output.update_cycles(), 1);
+        for i in 0..HALF {
+            assert_eq!((dma_buf[i * 2] >> 16) as i16, (HALF + i) as i16);
+            assert_eq!((dma_buf[i * 2 + 1] >> 16) as i16, 1000 + (HALF + i) as i16);
+        }
+
+        // Next call starts the queued block
+        assert!(!output.isr(&mut dma_buf));
+        assert_eq!((dma_buf[0] >> 16) as i16, 7);
+        assert_eq!((dma_buf[1] >> 16) as i16, 8);
+    }
+
+    #[test]
+    fn isr_small_buffer_signals_once_per_block() {
+        let mut output = AudioOutputI2S::new(true);
+        let mut dma_buf = [0u32; 16];
+        let calls_per_block = AUDIO_BLOCK_SAMPLES / 8;
+        let signals = (0..calls_per_block * 3).filter(|_| output.isr(&mut dma_buf)).count();
+        assert_eq!(signals, 3);
+        assert_eq!(output.update_cycles(), 3);
+    }
+
+    #[cfg(feature = "dsp")]
+    #[test]
+    fn dc_block_removes_offset_keeps_signal() {
+        reset_pool();
+        let mut output = AudioOutputI2S::new(false);
+        output.set_dc_block(true);
+        let mut dma_buf = [0u32; AUDIO_BLOCK_SAMPLES * 2];
+
+        // 0.25 DC offset plus a ±0.25 square wave at fs/16 on the left;
+        // plain DC on the right. Run long enough for the 5 Hz pole to settle.
+        let mut left = AudioBlockMut::alloc().unwrap();
+        for (i, s) in left.iter_mut().enumerate() {
+            *s = if (i / 8) % 2 == 0 { 16384 } else { 0 };
+        }
+        let left = left.into_shared();
+        for _ in 0..2000 {
+            output.update(&[Some(left.clone()), Some(make_block(8192))], &mut []);
+            output.isr(&mut dma_buf);
+        }
+
+        let (mut sum_l, mut sum_r, mut min_l, mut max_l) = (0i32, 0i32, i16::MAX, i16::MIN);
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            let l = (dma_buf[i * 2] >> 16) as i16;
+            sum_l += l as i32;
+            sum_r += ((dma_buf[i * 2 + 1] >> 16) as i16) as i32;
+            min_l = min_l.min(l);
+            max_l = max_l.max(l);
+        }
+        let mean_l = sum_l / AUDIO_BLOCK_SAMPLES as i32;
+        let mean_r = sum_r / AUDIO_BLOCK_SAMPLES as i32;
+        assert!(mean_l.abs() < 100, "left DC left over: {}", mean_l);
+        assert!(mean_r.abs() < 100, "right DC left over: {}", mean_r);
+        // The square wave swings about ±8192 around zero
+        assert!(max_l > 7000 && min_l < -7000, "swing {}..{}", min_l, max_l);
+    }
+
+    #[cfg(feature = "dsp")]
+    #[test]
+    fn dc_block_disabled_is_bit_exact() {
+        reset_pool();
+        let mut output = AudioOutputI2S::new(false);
+        output.set_dc_block(true);
+        output.set_dc_block(false);
+        output.update(&[Some(make_block(8192)), Some(make_block(-8192))], &mut []);
+        let mut dma_buf = [0u32; AUDIO_BLOCK_SAMPLES * 2];
+        output.isr(&mut dma_buf);
+        for frame in dma_buf.chunks_exact(2) {
+            assert_eq!((frame[0] >> 16) as i16, 8192);
+            assert_eq!((frame[1] >> 16) as i16, -8192);
+        }
+    }
 }
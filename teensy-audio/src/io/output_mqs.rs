@@ -0,0 +1,341 @@
+//! DMA-driven MQS (Medium Quality Sound) stereo output.
+//!
+//! [`AudioOutputMQS`] drives the i.MX RT1062's on-chip MQS PWM outputs,
+//! for Teensy 4 boards without an audio codec. It is a drop-in
+//! replacement for [`AudioOutputI2S`](super::AudioOutputI2S): same inputs,
+//! same double-buffered queue, same `isr()` contract. Only the DMA buffer
+//! format differs.
+//!
+//! ## DMA Buffer Layout
+//!
+//! - `[u32; AUDIO_BLOCK_SAMPLES]` — 128 stereo frames, 1 word each
+//! - MQS is fed by SAI3 with 16-bit words, so each `u32` holds a frame as
+//!   two halfwords: left in bits 15–0, right in bits 31–16
+//!   (see [`interleave_packed()`])
+//! - DMA runs in one-shot mode: the ISR fills the buffer and re-arms DMA
+//!
+//! As with I2S, shorter buffers are accepted for lower latency: [`isr()`]
+//! is generic over the buffer length, which must divide 128 (checked at
+//! compile time).
+//!
+//! [`isr()`]: AudioOutputMQS::isr
+//!
+//! ## Reference
+//!
+//! Ported from `TeensyAudio/output_mqs.cpp`.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::node::AudioNode;
+
+use super::interleave::{interleave_packed, DmaLen};
+use super::system::DmaOutput;
+
+/// DMA buffer size in `u32` words: 1 word per stereo frame.
+pub const MQS_DMA_BUFFER_WORDS: usize = AUDIO_BLOCK_SAMPLES;
+
+/// DMA-driven MQS stereo output node.
+///
+/// Implements [`AudioNode`] with 2 inputs (left, right) and 0 outputs.
+///
+/// [`update()`](AudioNode::update) queues audio blocks from the graph and
+/// the DMA ISR (via [`isr()`](Self::isr)) packs them into the DMA buffer,
+/// exactly as [`AudioOutputI2S`](super::AudioOutputI2S) does.
+pub struct AudioOutputMQS {
+    /// First block being actively transmitted (left channel).
+    block_left_1st: Option<AudioBlockRef>,
+    /// Second block queued for transmission (left channel).
+    block_left_2nd: Option<AudioBlockRef>,
+    /// First block being actively transmitted (right channel).
+    block_right_1st: Option<AudioBlockRef>,
+    /// Second block queued for transmission (right channel).
+    block_right_2nd: Option<AudioBlockRef>,
+    /// If `true`, this node's ISR triggers the audio graph update cycle.
+    update_responsibility: bool,
+    /// Number of ISR calls that signalled a graph update.
+    update_cycles: u64,
+    /// Frames of the current block already sent to DMA.
+    frames_sent: usize,
+}
+
+impl AudioOutputMQS {
+    /// Create a new MQS output node.
+    ///
+    /// # Arguments
+    ///
+    /// - `update_responsibility`: If `true`, this node's ISR will signal that
+    ///   the audio graph should be updated. Typically only one output node
+    ///   in the system has this responsibility.
+    pub const fn new(update_responsibility: bool) -> Self {
+        AudioOutputMQS {
+            block_left_1st: None,
+            block_left_2nd: None,
+            block_right_1st: None,
+            block_right_2nd: None,
+            update_responsibility,
+            update_cycles: 0,
+            frames_sent: 0,
+        }
+    }
+
+    /// Handle DMA interrupt — fill the DMA buffer from the current audio block.
+    ///
+    /// Call this from the DMA completion ISR. It packs the next `LEN` frames
+    /// of the current left/right audio blocks into the DMA buffer. Once a
+    /// whole block has been sent the double-buffer queue rotates.
+    ///
+    /// `LEN` must divide `AUDIO_BLOCK_SAMPLES`; the usual full-block buffer
+    /// is `[u32; AUDIO_BLOCK_SAMPLES]`.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the audio graph should be updated. This happens once per
+    /// block, on the call that finishes it, and only when
+    /// `update_responsibility` is set.
+    pub fn isr<const LEN: usize>(&mut self, dma_buffer: &mut [u32; LEN]) -> bool {
+        let frames = DmaLen::<LEN>::PACKED_FRAMES;
+        let range = self.frames_sent..self.frames_sent + frames;
+
+        interleave_packed(
+            dma_buffer,
+            self.block_left_1st.as_ref().map(|b| &b[range.clone()]),
+            self.block_right_1st.as_ref().map(|b| &b[range]),
+        );
+
+        self.frames_sent += frames;
+        if self.frames_sent < AUDIO_BLOCK_SAMPLES {
+            return false;
+        }
+        self.frames_sent = 0;
+
+        // Rotate: consume 1st block, promote 2nd → 1st
+        self.block_left_1st = self.block_left_2nd.take();
+        self.block_right_1st = self.block_right_2nd.take();
+
+        if self.update_responsibility {
+            self.update_cycles = self.update_cycles.wrapping_add(1);
+        }
+        self.update_responsibility
+    }
+
+    /// Number of ISR calls so far that signalled a graph update.
+    pub fn update_cycles(&self) -> u64 {
+        self.update_cycles
+    }
+
+    /// Whether this output is responsible for triggering graph updates.
+    pub fn has_update_responsibility(&self) -> bool {
+        self.update_responsibility
+    }
+
+    /// Check if the output has a left channel block queued.
+    pub fn has_left_block(&self) -> bool {
+        self.block_left_1st.is_some()
+    }
+
+    /// Check if the output has a right channel block queued.
+    pub fn has_right_block(&self) -> bool {
+        self.block_right_1st.is_some()
+    }
+}
+
+impl DmaOutput for AudioOutputMQS {
+    fn new_responsible() -> Self {
+        AudioOutputMQS::new(true)
+    }
+
+    fn isr<const LEN: usize>(&mut self, dma_buffer: &mut [u32; LEN]) -> bool {
+        AudioOutputMQS::isr(self, dma_buffer)
+    }
+}
+
+impl AudioNode for AudioOutputMQS {
+    const NUM_INPUTS: usize = 2;
+    const NUM_OUTPUTS: usize = 0;
+    const NAME: &'static str = "AudioOutputMQS";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], _outputs: &mut [Option<AudioBlockMut>]) {
+        // Input 0 = left channel
+        if let Some(ref block) = inputs[0] {
+            if self.block_left_1st.is_none() {
+                self.block_left_1st = Some(block.clone());
+            } else if self.block_left_2nd.is_none() {
+                self.block_left_2nd = Some(block.clone());
+            } else {
+                // Both slots full — drop oldest, shift, add new
+                self.block_left_1st = self.block_left_2nd.take();
+                self.block_left_2nd = Some(block.clone());
+            }
+        }
+
+        // Input 1 = right channel
+        if let Some(ref block) = inputs[1] {
+            if self.block_right_1st.is_none() {
+                self.block_right_1st = Some(block.clone());
+            } else if self.block_right_2nd.is_none() {
+                self.block_right_2nd = Some(block.clone());
+            } else {
+                // Both slots full — drop oldest, shift, add new
+                self.block_right_1st = self.block_right_2nd.take();
+                self.block_right_2nd = Some(block.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::block::AudioBlockMut;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Helper: allocate a block filled with a constant value.
+    fn make_block(value: i16) -> AudioBlockRef {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block.fill(value);
+        block.into_shared()
+    }
+
+    /// Helper: allocate a block with a linear ramp.
+    fn make_ramp_block(start: i16) -> AudioBlockRef {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        for (i, sample) in block.iter_mut().enumerate() {
+            *sample = start.wrapping_add(i as i16);
+        }
+        block.into_shared()
+    }
+
+    fn left(word: u32) -> i16 {
+        word as u16 as i16
+    }
+
+    fn right(word: u32) -> i16 {
+        (word >> 16) as i16
+    }
+
+    #[test]
+    fn new_has_no_blocks() {
+        let output = AudioOutputMQS::new(true);
+        assert!(!output.has_left_block());
+        assert!(!output.has_right_block());
+        assert!(output.has_update_responsibility());
+    }
+
+    #[test]
+    fn update_overflow_rotates() {
+        reset_pool();
+        let mut output = AudioOutputMQS::new(false);
+        output.update(&[Some(make_block(10)), None], &mut []);
+        output.update(&[Some(make_block(20)), None], &mut []);
+        output.update(&[Some(make_block(30)), None], &mut []);
+
+        // After overflow: 1st = 20, 2nd = 30
+        let mut dma_buf = [0u32; MQS_DMA_BUFFER_WORDS];
+        output.isr(&mut dma_buf);
+        assert_eq!(left(dma_buf[0]), 20);
+        output.isr(&mut dma_buf);
+        assert_eq!(left(dma_buf[0]), 30);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn isr_silence_when_no_blocks() {
+        let mut output = AudioOutputMQS::new(true);
+        let mut dma_buf = [0xDEAD_BEEFu32; MQS_DMA_BUFFER_WORDS];
+        output.isr(&mut dma_buf);
+        assert!(dma_buf.iter().all(|&w| w == 0), "expected silence");
+    }
+
+    #[test]
+    fn isr_packs_both_channels() {
+        reset_pool();
+        let mut output = AudioOutputMQS::new(false);
+        output.update(
+            &[Some(make_ramp_block(0)), Some(make_ramp_block(-1000))],
+            &mut [],
+        );
+
+        let mut dma_buf = [0u32; MQS_DMA_BUFFER_WORDS];
+        output.isr(&mut dma_buf);
+
+        for (i, &word) in dma_buf.iter().enumerate() {
+            assert_eq!(left(word), i as i16, "left mismatch at frame {i}");
+            assert_eq!(right(word), -1000 + i as i16, "right mismatch at frame {i}");
+        }
+    }
+
+    #[test]
+    fn isr_right_only_zeroes_left() {
+        reset_pool();
+        let mut output = AudioOutputMQS::new(false);
+        output.update(&[None, Some(make_block(-500))], &mut []);
+
+        let mut dma_buf = [0xFFFF_FFFFu32; MQS_DMA_BUFFER_WORDS];
+        output.isr(&mut dma_buf);
+
+        for &word in dma_buf.iter() {
+            assert_eq!(left(word), 0);
+            assert_eq!(right(word), -500);
+        }
+    }
+
+    #[test]
+    fn isr_rotates_blocks_after_consumption() {
+        reset_pool();
+        let mut output = AudioOutputMQS::new(false);
+        output.update(&[Some(make_block(10)), None], &mut []);
+        output.update(&[Some(make_block(20)), None], &mut []);
+
+        let mut dma_buf = [0u32; MQS_DMA_BUFFER_WORDS];
+
+        output.isr(&mut dma_buf);
+        assert_eq!(left(dma_buf[0]), 10);
+        assert!(output.has_left_block());
+
+        output.isr(&mut dma_buf);
+        assert_eq!(left(dma_buf[0]), 20);
+        assert!(!output.has_left_block());
+    }
+
+    #[test]
+    fn isr_signals_update_correctly() {
+        let mut output_responsible = AudioOutputMQS::new(true);
+        let mut output_not = AudioOutputMQS::new(false);
+        let mut dma_buf = [0u32; MQS_DMA_BUFFER_WORDS];
+
+        assert!(output_responsible.isr(&mut dma_buf));
+        assert!(!output_not.isr(&mut dma_buf));
+        assert_eq!(output_responsible.update_cycles(), 1);
+        assert_eq!(output_not.update_cycles(), 0);
+    }
+
+    #[test]
+    fn isr_half_size_buffer_splits_block() {
+        reset_pool();
+        let mut output = AudioOutputMQS::new(true);
+        output.update(
+            &[Some(make_ramp_block(0)), Some(make_ramp_block(1000))],
+            &mut [],
+        );
+        output.update(&[Some(make_block(7)), Some(make_block(8))], &mut []);
+
+        const HALF: usize = AUDIO_BLOCK_SAMPLES / 2;
+        let mut dma_buf = [0u32; HALF];
+
+        assert!(!output.isr(&mut dma_buf));
+        assert_eq!(left(dma_buf[HALF - 1]), (HALF - 1) as i16);
+
+        // Second half continues the same block, then rotates
+        assert!(output.isr(&mut dma_buf));
+        assert_eq!(left(dma_buf[0]), HALF as i16);
+        assert_eq!(right(dma_buf[0]), 1000 + HALF as i16);
+
+        assert!(!output.isr(&mut dma_buf));
+        assert_eq!(left(dma_buf[0]), 7);
+        assert_eq!(right(dma_buf[0]), 8);
+    }
+}
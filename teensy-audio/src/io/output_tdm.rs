@@ -0,0 +1,249 @@
+//! DMA-driven 8-channel TDM output.
+//!
+//! [`AudioOutputTDM`] sends eight audio channels over a single SAI1 TDM
+//! frame, for multi-channel codecs such as the CS42448. It works like
+//! [`AudioOutputI2S`](super::AudioOutputI2S) with eight inputs instead of
+//! two.
+//!
+//! ## DMA Buffer Layout
+//!
+//! - `[u32; AUDIO_BLOCK_SAMPLES * 4]` — 128 frames, 4 words each
+//! - Each word carries two 16-bit channels: word `k` of a frame holds
+//!   channel `2k` in bits 31–16 and channel `2k + 1` in bits 15–0
+//!   (see [`channel_interleave()`])
+//! - DMA runs in one-shot mode: the ISR fills the buffer and re-arms DMA
+//!
+//! Shorter buffers are accepted for lower latency: [`isr()`] is generic
+//! over the buffer length, which must be a multiple of 4 words whose frame
+//! count divides 128 (checked at compile time).
+//!
+//! [`isr()`]: AudioOutputTDM::isr
+//!
+//! ## Reference
+//!
+//! Ported from `TeensyAudio/output_tdm.cpp`.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::node::AudioNode;
+
+use super::interleave::{channel_interleave, DmaLen, TDM_CHANNELS, TDM_WORDS_PER_FRAME};
+use super::system::DmaOutput;
+
+/// DMA buffer size in `u32` words: 4 words per 8-channel frame.
+pub const TDM_DMA_BUFFER_WORDS: usize = AUDIO_BLOCK_SAMPLES * TDM_WORDS_PER_FRAME;
+
+/// DMA-driven 8-channel TDM output node.
+///
+/// Implements [`AudioNode`] with 8 inputs and 0 outputs. Each channel has
+/// its own double-buffered queue; a channel with no queued block is sent
+/// as silence.
+pub struct AudioOutputTDM {
+    /// Blocks being actively transmitted, per channel.
+    blocks_1st: [Option<AudioBlockRef>; TDM_CHANNELS],
+    /// Blocks queued for transmission, per channel.
+    blocks_2nd: [Option<AudioBlockRef>; TDM_CHANNELS],
+    /// If `true`, this node's ISR triggers the audio graph update cycle.
+    update_responsibility: bool,
+    /// Number of ISR calls that signalled a graph update.
+    update_cycles: u64,
+    /// Frames of the current blocks already sent to DMA.
+    frames_sent: usize,
+}
+
+impl AudioOutputTDM {
+    /// Create a new TDM output node.
+    ///
+    /// # Arguments
+    ///
+    /// - `update_responsibility`: If `true`, this node's ISR will signal that
+    ///   the audio graph should be updated. Typically only one output node
+    ///   in the system has this responsibility.
+    pub const fn new(update_responsibility: bool) -> Self {
+        AudioOutputTDM {
+            blocks_1st: [const { None }; TDM_CHANNELS],
+            blocks_2nd: [const { None }; TDM_CHANNELS],
+            update_responsibility,
+            update_cycles: 0,
+            frames_sent: 0,
+        }
+    }
+
+    /// Handle DMA interrupt — fill the DMA buffer from the current audio blocks.
+    ///
+    /// Call this from the DMA completion ISR. It interleaves the next
+    /// `LEN / 4` frames of all eight channels into the DMA buffer. Once a
+    /// whole block has been sent every channel's queue rotates.
+    ///
+    /// # Returns
+    ///
+    /// `true` if the audio graph should be updated. This happens once per
+    /// block, on the call that finishes it, and only when
+    /// `update_responsibility` is set.
+    pub fn isr<const LEN: usize>(&mut self, dma_buffer: &mut [u32; LEN]) -> bool {
+        let frames = DmaLen::<LEN>::FOUR_WORD_FRAMES;
+        let range = self.frames_sent..self.frames_sent + frames;
+
+        let channels: [Option<&[i16]>; TDM_CHANNELS] =
+            core::array::from_fn(|ch| self.blocks_1st[ch].as_ref().map(|b| &b[range.clone()]));
+        channel_interleave(dma_buffer, &channels);
+
+        self.frames_sent += frames;
+        if self.frames_sent < AUDIO_BLOCK_SAMPLES {
+            return false;
+        }
+        self.frames_sent = 0;
+
+        // Rotate: consume 1st blocks, promote 2nd → 1st
+        for (first, second) in self.blocks_1st.iter_mut().zip(self.blocks_2nd.iter_mut()) {
+            *first = second.take();
+        }
+
+        if self.update_responsibility {
+            self.update_cycles = self.update_cycles.wrapping_add(1);
+        }
+        self.update_responsibility
+    }
+
+    /// Number of ISR calls so far that signalled a graph update.
+    pub fn update_cycles(&self) -> u64 {
+        self.update_cycles
+    }
+
+    /// Whether this output is responsible for triggering graph updates.
+    pub fn has_update_responsibility(&self) -> bool {
+        self.update_responsibility
+    }
+
+    /// Check if `channel` has a block queued. Out-of-range channels have none.
+    pub fn has_block(&self, channel: usize) -> bool {
+        self.blocks_1st.get(channel).is_some_and(|b| b.is_some())
+    }
+}
+
+impl DmaOutput for AudioOutputTDM {
+    fn new_responsible() -> Self {
+        AudioOutputTDM::new(true)
+    }
+
+    fn isr<const LEN: usize>(&mut self, dma_buffer: &mut [u32; LEN]) -> bool {
+        AudioOutputTDM::isr(self, dma_buffer)
+    }
+}
+
+impl AudioNode for AudioOutputTDM {
+    const NUM_INPUTS: usize = TDM_CHANNELS;
+    const NUM_OUTPUTS: usize = 0;
+    const NAME: &'static str = "AudioOutputTDM";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], _outputs: &mut [Option<AudioBlockMut>]) {
+        for (ch, input) in inputs.iter().enumerate().take(TDM_CHANNELS) {
+            let Some(block) = input else { continue };
+            if self.blocks_1st[ch].is_none() {
+                self.blocks_1st[ch] = Some(block.clone());
+            } else if self.blocks_2nd[ch].is_none() {
+                self.blocks_2nd[ch] = Some(block.clone());
+            } else {
+                // Both slots full — drop oldest, shift, add new
+                self.blocks_1st[ch] = self.blocks_2nd[ch].take();
+                self.blocks_2nd[ch] = Some(block.clone());
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::block::AudioBlockMut;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Helper: allocate a block filled with a constant value.
+    fn make_block(value: i16) -> AudioBlockRef {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block.fill(value);
+        block.into_shared()
+    }
+
+    /// Sample of `channel` in `frame` of a TDM DMA buffer.
+    fn slot(dma_buf: &[u32], frame: usize, channel: usize) -> i16 {
+        let word = dma_buf[frame * TDM_WORDS_PER_FRAME + channel / 2];
+        if channel.is_multiple_of(2) {
+            (word >> 16) as i16
+        } else {
+            word as u16 as i16
+        }
+    }
+
+    #[test]
+    fn isr_silence_when_no_blocks() {
+        let mut output = AudioOutputTDM::new(true);
+        let mut dma_buf = [0xDEAD_BEEFu32; TDM_DMA_BUFFER_WORDS];
+        assert!(output.isr(&mut dma_buf));
+        assert!(dma_buf.iter().all(|&w| w == 0), "expected silence");
+    }
+
+    #[test]
+    fn isr_places_every_channel() {
+        reset_pool();
+        let mut output = AudioOutputTDM::new(false);
+        let inputs: [Option<AudioBlockRef>; TDM_CHANNELS] =
+            core::array::from_fn(|ch| Some(make_block(ch as i16 * 1000 - 3000)));
+        output.update(&inputs, &mut []);
+
+        let mut dma_buf = [0u32; TDM_DMA_BUFFER_WORDS];
+        output.isr(&mut dma_buf);
+
+        for frame in 0..AUDIO_BLOCK_SAMPLES {
+            for ch in 0..TDM_CHANNELS {
+                assert_eq!(slot(&dma_buf, frame, ch), ch as i16 * 1000 - 3000);
+            }
+        }
+    }
+
+    #[test]
+    fn isr_rotates_single_channel_and_zeroes_others() {
+        reset_pool();
+        let mut output = AudioOutputTDM::new(false);
+        let mut inputs: [Option<AudioBlockRef>; TDM_CHANNELS] = Default::default();
+        inputs[5] = Some(make_block(10));
+        output.update(&inputs, &mut []);
+        inputs[5] = Some(make_block(20));
+        output.update(&inputs, &mut []);
+        drop(inputs);
+
+        let mut dma_buf = [0xFFFF_FFFFu32; TDM_DMA_BUFFER_WORDS];
+
+        output.isr(&mut dma_buf);
+        for ch in 0..TDM_CHANNELS {
+            assert_eq!(
+                slot(&dma_buf, 0, ch),
+                if ch == 5 { 10 } else { 0 },
+                "channel {ch}"
+            );
+        }
+        assert!(output.has_block(5));
+
+        output.isr(&mut dma_buf);
+        assert_eq!(slot(&dma_buf, AUDIO_BLOCK_SAMPLES - 1, 5), 20);
+        assert_eq!(slot(&dma_buf, AUDIO_BLOCK_SAMPLES - 1, 4), 0);
+        assert!(!output.has_block(5));
+        assert_eq!(POOL.allocated_count(), 0);
+
+        output.isr(&mut dma_buf);
+        assert!(dma_buf.iter().all(|&w| w == 0));
+    }
+
+    #[test]
+    fn isr_quarter_buffer_signals_once_per_block() {
+        let mut output = AudioOutputTDM::new(true);
+        let mut dma_buf = [0u32; TDM_DMA_BUFFER_WORDS / 4];
+        let signals = (0..12).filter(|_| output.isr(&mut dma_buf)).count();
+        assert_eq!(signals, 3);
+        assert_eq!(output.update_cycles(), 3);
+    }
+}
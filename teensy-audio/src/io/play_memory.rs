@@ -0,0 +1,204 @@
+//! Play a raw PCM clip from memory.
+//!
+//! [`AudioPlayMemory`] streams a `&'static [i16]` (typically a table in
+//! flash) into the graph once, 128 samples per update. Conceptually the same
+//! as `TeensyAudio/play_memory.cpp`, but the data is plain 16-bit PCM at the
+//! audio sample rate rather than PJRC's packed/compressed format.
+//!
+//! ## Usage
+//!
+//! ```ignore
+//! static CLICK: [i16; 300] = [/* ... */];
+//!
+//! let mut player = AudioPlayMemory::new();
+//! player.play(&CLICK);
+//!
+//! // In audio update task:
+//! let mut outputs = [AudioBlockMut::alloc()];
+//! player.update(&[], &mut outputs);
+//! ```
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::node::AudioNode;
+
+/// One-shot player for a raw `i16` clip.
+///
+/// Implements [`AudioNode`] with 0 inputs and 1 output.
+///
+/// Each update copies the next 128 samples of the clip into the output
+/// block. If the clip ends partway through a block, the rest of that block
+/// is zero-filled and playback stops. While stopped the output block is
+/// dropped (silence).
+pub struct AudioPlayMemory {
+    clip: &'static [i16],
+    /// Samples of `clip` already played.
+    position: usize,
+    playing: bool,
+}
+
+impl AudioPlayMemory {
+    /// Create a new, stopped player.
+    pub const fn new() -> Self {
+        AudioPlayMemory {
+            clip: &[],
+            position: 0,
+            playing: false,
+        }
+    }
+
+    /// Start playing `clip` from the beginning, replacing any clip in
+    /// progress. An empty clip does not start playback.
+    pub fn play(&mut self, clip: &'static [i16]) {
+        self.clip = clip;
+        self.position = 0;
+        self.playing = !clip.is_empty();
+    }
+
+    /// Stop playback. The next update outputs silence.
+    pub fn stop(&mut self) {
+        self.playing = false;
+    }
+
+    /// Whether a clip is currently playing.
+    pub fn is_playing(&self) -> bool {
+        self.playing
+    }
+
+    /// Number of samples played from the current (or last) clip.
+    pub fn position_samples(&self) -> u32 {
+        self.position as u32
+    }
+}
+
+impl Default for AudioPlayMemory {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioPlayMemory {
+    const NUM_INPUTS: usize = 0;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioPlayMemory";
+
+    fn update(
+        &mut self,
+        _inputs: &[Option<AudioBlockRef>],
+        outputs: &mut [Option<AudioBlockMut>],
+    ) {
+        if !self.playing {
+            outputs[0] = None;
+            return;
+        }
+        let mut out = match outputs[0].take() {
+            Some(b) => b,
+            None => return,
+        };
+
+        let remaining = &self.clip[self.position..];
+        let n = remaining.len().min(AUDIO_BLOCK_SAMPLES);
+        out[..n].copy_from_slice(&remaining[..n]);
+        // Ragged tail: pad the final block with silence
+        out[n..].fill(0);
+
+        self.position += n;
+        if self.position == self.clip.len() {
+            self.playing = false;
+        }
+        outputs[0] = Some(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Clip whose sample `i` is `i + 1`, so zero padding is unambiguous.
+    const fn ramp_clip<const N: usize>() -> [i16; N] {
+        let mut clip = [0i16; N];
+        let mut i = 0;
+        while i < N {
+            clip[i] = i as i16 + 1;
+            i += 1;
+        }
+        clip
+    }
+
+    static CLIP_256: [i16; 256] = ramp_clip();
+    static CLIP_300: [i16; 300] = ramp_clip();
+    static CLIP_500: [i16; 500] = ramp_clip();
+
+    fn step(player: &mut AudioPlayMemory) -> Option<AudioBlockMut> {
+        let mut outputs = [AudioBlockMut::alloc()];
+        player.update(&[], &mut outputs);
+        outputs[0].take()
+    }
+
+    #[test]
+    fn play_exact_multiple_of_block() {
+        reset_pool();
+        let mut player = AudioPlayMemory::new();
+        player.play(&CLIP_256);
+        assert!(player.is_playing());
+
+        for block in 0..2 {
+            let out = step(&mut player).expect("block while playing");
+            for i in 0..AUDIO_BLOCK_SAMPLES {
+                assert_eq!(out[i], (block * AUDIO_BLOCK_SAMPLES + i + 1) as i16);
+            }
+        }
+        assert!(!player.is_playing());
+        assert_eq!(player.position_samples(), 256);
+        assert!(step(&mut player).is_none());
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn play_ragged_clip_zero_fills_last_block() {
+        reset_pool();
+        let mut player = AudioPlayMemory::new();
+        player.play(&CLIP_300);
+
+        step(&mut player).unwrap();
+        step(&mut player).unwrap();
+        assert!(player.is_playing());
+        assert_eq!(player.position_samples(), 256);
+
+        let last = step(&mut player).unwrap();
+        for i in 0..44 {
+            assert_eq!(last[i], 257 + i as i16);
+        }
+        assert!(last[44..].iter().all(|&s| s == 0), "tail not zero-filled");
+        assert!(!player.is_playing());
+        assert_eq!(player.position_samples(), 300);
+        assert!(step(&mut player).is_none());
+    }
+
+    #[test]
+    fn stop_silences_and_play_restarts() {
+        reset_pool();
+        let mut player = AudioPlayMemory::new();
+        player.play(&CLIP_500);
+        step(&mut player);
+        player.stop();
+        assert!(!player.is_playing());
+        assert!(step(&mut player).is_none());
+
+        player.play(&CLIP_500);
+        assert_eq!(player.position_samples(), 0);
+        assert_eq!(step(&mut player).unwrap()[0], 1);
+    }
+
+    #[test]
+    fn empty_clip_does_not_play() {
+        let mut player = AudioPlayMemory::new();
+        player.play(&[]);
+        assert!(!player.is_playing());
+    }
+}
@@ -21,6 +21,7 @@
 //! ```
 
 use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
 use crate::node::AudioNode;
 
 use super::spsc::SpscQueue;
@@ -40,6 +41,12 @@ const QUEUE_SIZE: usize = 5;
 /// The consumer (audio graph) calls `update()` to dequeue one block per cycle.
 pub struct AudioPlayQueue {
     queue: SpscQueue<AudioBlockMut, QUEUE_SIZE>,
+    /// Block being assembled by `play_samples()` or `push_bytes_le()`.
+    staged: Option<AudioBlockMut>,
+    /// Number of samples written into `staged`.
+    staged_len: usize,
+    /// Low byte of a sample split across `push_bytes_le()` calls.
+    leftover: Option<u8>,
 }
 
 impl AudioPlayQueue {
@@ -47,6 +54,9 @@ impl AudioPlayQueue {
     pub const fn new() -> Self {
         AudioPlayQueue {
             queue: SpscQueue::new(),
+            staged: None,
+            staged_len: 0,
+            leftover: None,
         }
     }
 
@@ -61,6 +71,113 @@ impl AudioPlayQueue {
         self.queue.push(block)
     }
 
+    /// Append samples, assembling them into blocks.
+    ///
+    /// Samples are copied into a partially filled block; each time 128 have
+    /// accumulated the block is enqueued as if by [`play()`](Self::play).
+    /// Call [`flush()`](Self::flush) after the last samples to send a final
+    /// partial block.
+    ///
+    /// Returns the number of samples consumed. This is less than
+    /// `data.len()` when the queue is full or the pool is exhausted;
+    /// resubmit the rest later.
+    pub fn play_samples(&mut self, data: &[i16]) -> usize {
+        let mut consumed = 0;
+        while consumed < data.len() {
+            if !self.enqueue_staged() {
+                break;
+            }
+            if self.staged.is_none() {
+                match AudioBlockMut::alloc() {
+                    Some(block) => self.staged = Some(block),
+                    None => break,
+                }
+            }
+            let n = (AUDIO_BLOCK_SAMPLES - self.staged_len).min(data.len() - consumed);
+            if let Some(block) = self.staged.as_mut() {
+                block[self.staged_len..self.staged_len + n]
+                    .copy_from_slice(&data[consumed..consumed + n]);
+            }
+            self.staged_len += n;
+            consumed += n;
+        }
+        self.enqueue_staged();
+        consumed
+    }
+
+    /// Zero-pad and enqueue a partially filled block left by
+    /// [`play_samples()`](Self::play_samples) or
+    /// [`push_bytes_le()`](Self::push_bytes_le).
+    ///
+    /// Returns `false` if a block is still waiting because the queue is
+    /// full; call again once there is room. A held odd byte from
+    /// `push_bytes_le()` is discarded.
+    pub fn flush(&mut self) -> bool {
+        self.leftover = None;
+        if self.staged_len > 0 && self.staged_len < AUDIO_BLOCK_SAMPLES {
+            if let Some(block) = self.staged.as_mut() {
+                block[self.staged_len..].fill(0);
+            }
+            self.staged_len = AUDIO_BLOCK_SAMPLES;
+        }
+        self.enqueue_staged()
+    }
+
+    /// Append little-endian `i16` byte data, assembling it into blocks.
+    ///
+    /// Each time 128 samples have accumulated the block is enqueued as if
+    /// by [`play()`](Self::play). Input need not be sample-aligned: an odd
+    /// trailing byte is held and paired with the first byte of the next call.
+    ///
+    /// Returns the number of bytes consumed. This is less than `bytes.len()`
+    /// when the queue is full or the pool is exhausted; resubmit the rest
+    /// later.
+    pub fn push_bytes_le(&mut self, bytes: &[u8]) -> usize {
+        let mut consumed = 0;
+        while consumed < bytes.len() {
+            if !self.enqueue_staged() {
+                break;
+            }
+            let byte = bytes[consumed];
+            match self.leftover {
+                None => self.leftover = Some(byte),
+                Some(lo) => {
+                    if self.staged.is_none() {
+                        match AudioBlockMut::alloc() {
+                            Some(block) => self.staged = Some(block),
+                            None => break,
+                        }
+                    }
+                    if let Some(block) = self.staged.as_mut() {
+                        block[self.staged_len] = i16::from_le_bytes([lo, byte]);
+                    }
+                    self.staged_len += 1;
+                    self.leftover = None;
+                }
+            }
+            consumed += 1;
+        }
+        self.enqueue_staged();
+        consumed
+    }
+
+    /// Enqueue the staged block if it is complete.
+    ///
+    /// Returns `false` if a complete block is still waiting for queue space.
+    fn enqueue_staged(&mut self) -> bool {
+        if self.staged_len < AUDIO_BLOCK_SAMPLES {
+            return true;
+        }
+        if let Some(block) = self.staged.take() {
+            if let Err(block) = self.queue.push(block) {
+                self.staged = Some(block);
+                return false;
+            }
+        }
+        self.staged_len = 0;
+        true
+    }
+
     /// Check if the queue has blocks waiting for playback.
     pub fn is_empty(&self) -> bool {
         self.queue.is_empty()
@@ -75,6 +192,7 @@ impl AudioPlayQueue {
 impl AudioNode for AudioPlayQueue {
     const NUM_INPUTS: usize = 0;
     const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioPlayQueue";
 
     fn update(
         &mut self,
@@ -187,4 +305,99 @@ mod tests {
         let rejected = result.unwrap_err();
         assert_eq!(rejected[0], 99);
     }
+
+    #[test]
+    fn push_bytes_le_splits_odd_boundary() {
+        reset_pool();
+        let mut q = AudioPlayQueue::new();
+
+        let mut bytes = [0u8; AUDIO_BLOCK_SAMPLES * 2];
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            let sample = (i as i16 - 64) * 257;
+            bytes[i * 2..i * 2 + 2].copy_from_slice(&sample.to_le_bytes());
+        }
+
+        // Split mid-sample: 3 bytes, then the remaining 253.
+        assert_eq!(q.push_bytes_le(&bytes[..3]), 3);
+        assert!(q.is_empty());
+        assert_eq!(q.push_bytes_le(&bytes[3..]), bytes.len() - 3);
+        assert_eq!(q.len(), 1);
+
+        let mut outputs = [None];
+        q.update(&[], &mut outputs);
+        let out = outputs[0].as_ref().unwrap();
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            assert_eq!(out[i], (i as i16 - 64) * 257, "sample {}", i);
+        }
+    }
+
+    #[test]
+    fn push_bytes_le_stops_when_queue_full() {
+        reset_pool();
+        let mut q = AudioPlayQueue::new();
+
+        let bytes = [0x11u8; AUDIO_BLOCK_SAMPLES * 2];
+        for _ in 0..4 {
+            assert_eq!(q.push_bytes_le(&bytes), bytes.len());
+        }
+        assert_eq!(q.len(), 4);
+
+        // The fifth block is assembled but cannot be enqueued yet.
+        assert_eq!(q.push_bytes_le(&bytes), bytes.len());
+        assert_eq!(q.push_bytes_le(&bytes[..2]), 0);
+
+        let mut outputs = [None];
+        q.update(&[], &mut outputs);
+        assert_eq!(q.push_bytes_le(&bytes[..2]), 2);
+        assert_eq!(q.len(), 4);
+    }
+
+    #[test]
+    fn play_samples_streams_into_blocks() {
+        reset_pool();
+        let mut q = AudioPlayQueue::new();
+        let samples: [i16; 300] = core::array::from_fn(|i| i as i16 * 7 - 1000);
+
+        // Uneven chunks, crossing block boundaries mid-call
+        assert_eq!(q.play_samples(&samples[..100]), 100);
+        assert!(q.is_empty());
+        assert_eq!(q.play_samples(&samples[100..290]), 190);
+        assert_eq!(q.len(), 2);
+        assert_eq!(q.play_samples(&samples[290..]), 10);
+        assert_eq!(q.len(), 2, "partial block held back until flush");
+        assert!(q.flush());
+        assert_eq!(q.len(), 3);
+        assert!(q.flush(), "flushing with nothing staged is a no-op");
+        assert_eq!(q.len(), 3);
+
+        let mut outputs = [None];
+        for block in 0..3 {
+            q.update(&[], &mut outputs);
+            let out = outputs[0].take().unwrap();
+            for (i, &s) in out.iter().enumerate() {
+                let n = block * AUDIO_BLOCK_SAMPLES + i;
+                let expected = if n < samples.len() { samples[n] } else { 0 };
+                assert_eq!(s, expected, "block {} sample {}", block, i);
+            }
+        }
+        assert!(q.is_empty());
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn play_samples_stops_when_queue_full() {
+        reset_pool();
+        let mut q = AudioPlayQueue::new();
+        let samples = [5i16; AUDIO_BLOCK_SAMPLES * 5 + 10];
+
+        // Four blocks queue up, the fifth is assembled and held
+        assert_eq!(q.play_samples(&samples), AUDIO_BLOCK_SAMPLES * 5);
+        assert_eq!(q.len(), 4);
+        assert!(!q.flush());
+
+        let mut outputs = [None];
+        q.update(&[], &mut outputs);
+        assert_eq!(q.play_samples(&samples[..10]), 10);
+        assert_eq!(q.len(), 4);
+    }
 }
@@ -22,6 +22,8 @@
 //! record_queue.stop(); // Stop recording
 //! ```
 
+use core::sync::atomic::{AtomicU32, Ordering};
+
 use crate::block::{AudioBlockMut, AudioBlockRef};
 use crate::node::AudioNode;
 
@@ -39,10 +41,14 @@ const QUEUE_SIZE: usize = 5;
 /// [`update()`](AudioNode::update).
 ///
 /// Recording must be explicitly started with [`start()`](Self::start).
-/// When not recording, incoming blocks are silently discarded.
+/// When not recording, incoming blocks are silently discarded. While
+/// recording, blocks that arrive with the queue full are dropped and
+/// counted by [`overflows()`](Self::overflows).
 pub struct AudioRecordQueue {
     queue: SpscQueue<AudioBlockRef, QUEUE_SIZE>,
     recording: bool,
+    /// Blocks dropped because the queue was full.
+    overflows: AtomicU32,
 }
 
 impl AudioRecordQueue {
@@ -51,6 +57,7 @@ impl AudioRecordQueue {
         AudioRecordQueue {
             queue: SpscQueue::new(),
             recording: false,
+            overflows: AtomicU32::new(0),
         }
     }
 
@@ -81,9 +88,22 @@ impl AudioRecordQueue {
         self.queue.pop()
     }
 
-    /// Check if there are captured blocks waiting to be read.
-    pub fn available(&self) -> bool {
-        !self.queue.is_empty()
+    /// Return the number of captured blocks waiting to be read, like the
+    /// C++ `available()`.
+    ///
+    /// A consumer that regularly finds this near the queue capacity is
+    /// about to fall behind; see also [`overflows()`](Self::overflows).
+    pub fn available(&self) -> usize {
+        self.queue.len()
+    }
+
+    /// Return how many blocks have been dropped because the queue was full
+    /// when they arrived.
+    ///
+    /// A rising count means the reader (e.g. an SD card writer) isn't
+    /// keeping up and the recording has gaps.
+    pub fn overflows(&self) -> u32 {
+        self.overflows.load(Ordering::Relaxed)
     }
 
     /// Return the number of captured blocks waiting to be read.
@@ -95,6 +115,7 @@ impl AudioRecordQueue {
 impl AudioNode for AudioRecordQueue {
     const NUM_INPUTS: usize = 1;
     const NUM_OUTPUTS: usize = 0;
+    const NAME: &'static str = "AudioRecordQueue";
 
     fn update(
         &mut self,
@@ -105,8 +126,10 @@ impl AudioNode for AudioRecordQueue {
             return;
         }
         if let Some(ref block) = inputs[0] {
-            // Enqueue the block. If the queue is full, the block is silently dropped.
-            let _ = self.queue.push(block.clone());
+            // Enqueue the block. If the queue is full, the block is dropped and counted.
+            if self.queue.push(block.clone()).is_err() {
+                self.overflows.fetch_add(1, Ordering::Relaxed);
+            }
         }
     }
 }
@@ -131,7 +154,7 @@ mod tests {
     fn new_is_stopped_and_empty() {
         let q = AudioRecordQueue::new();
         assert!(!q.is_recording());
-        assert!(!q.available());
+        assert_eq!(q.available(), 0);
         assert_eq!(q.len(), 0);
     }
 
@@ -163,7 +186,7 @@ mod tests {
         let block = make_block(77);
         q.update(&[Some(block)], &mut []);
 
-        assert!(q.available());
+        assert_eq!(q.available(), 1);
         assert_eq!(q.len(), 1);
 
         let recorded = q.read().unwrap();
@@ -242,6 +265,29 @@ mod tests {
         q.start();
 
         q.update(&[None], &mut []);
-        assert!(!q.available());
+        assert_eq!(q.available(), 0);
+    }
+
+    #[test]
+    fn overflows_counted() {
+        reset_pool();
+        let mut q = AudioRecordQueue::new();
+        q.start();
+
+        for i in 0..7 {
+            q.update(&[Some(make_block(i))], &mut []);
+        }
+        assert_eq!(q.available(), 4);
+        assert_eq!(q.overflows(), 3);
+
+        // Draining makes room again; no further drops
+        assert_eq!(q.read().unwrap()[0], 0);
+        q.update(&[Some(make_block(7))], &mut []);
+        assert_eq!(q.overflows(), 3);
+
+        // Blocks discarded while stopped are not overflows
+        q.stop();
+        q.update(&[Some(make_block(8))], &mut []);
+        assert_eq!(q.overflows(), 3);
     }
 }
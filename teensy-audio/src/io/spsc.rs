@@ -99,6 +99,51 @@ impl<T, const N: usize> SpscQueue<T, N> {
         Some(val)
     }
 
+    /// Pop up to `out.len()` values into `out` (consumer side), oldest
+    /// first. Returns how many were written; the rest of `out` is untouched.
+    ///
+    /// Equivalent to repeated [`pop()`](Self::pop) calls, but loads `head`
+    /// and publishes `tail` once for the whole batch.
+    pub fn pop_into(&self, out: &mut [T]) -> usize {
+        let tail = self.tail.load(Ordering::Relaxed);
+        let head = self.head.load(Ordering::Acquire);
+        let count = ((head + N - tail) % N).min(out.len());
+
+        for (i, dst) in out[..count].iter_mut().enumerate() {
+            // SAFETY: Sole consumer; the `count` slots from `tail` were
+            // published by the producer before the `head` load above.
+            *dst = unsafe { (*self.buffer[(tail + i) % N].get()).assume_init_read() };
+        }
+
+        // Release ordering ensures the reads complete before the slots are
+        // handed back to the producer.
+        self.tail.store((tail + count) % N, Ordering::Release);
+        count
+    }
+
+    /// Borrow the oldest value without removing it (consumer side).
+    ///
+    /// Returns `None` if the queue is empty.
+    ///
+    /// # Safety
+    ///
+    /// Must be called from the consumer context, and the returned reference
+    /// must be dropped before the next [`pop()`](Self::pop) or
+    /// [`pop_into()`](Self::pop_into). Popping frees the slot for the
+    /// producer, which may then overwrite it under the reference; since
+    /// both sides take `&self`, the borrow checker can't enforce this.
+    pub unsafe fn peek(&self) -> Option<&T> {
+        let tail = self.tail.load(Ordering::Relaxed);
+
+        if tail == self.head.load(Ordering::Acquire) {
+            return None; // Queue is empty
+        }
+
+        // SAFETY: `tail != head`, so the slot holds a published value that
+        // the producer won't touch until the consumer advances `tail`.
+        Some(unsafe { (*self.buffer[tail].get()).assume_init_ref() })
+    }
+
     /// Check if the queue is empty.
     pub fn is_empty(&self) -> bool {
         self.tail.load(Ordering::Acquire) == self.head.load(Ordering::Acquire)
@@ -247,4 +292,45 @@ mod tests {
         }
         assert_eq!(DROP_COUNT.load(Ordering::Relaxed), 2);
     }
+
+    #[test]
+    fn pop_into_partial_and_full_drain() {
+        let q: SpscQueue<i32, 6> = SpscQueue::new(); // capacity 5
+        for v in 1..=5 {
+            q.push(v).unwrap();
+        }
+
+        // Partial: the buffer is smaller than the queue
+        let mut out = [0; 2];
+        assert_eq!(q.pop_into(&mut out), 2);
+        assert_eq!(out, [1, 2]);
+        assert_eq!(q.len(), 3);
+
+        // Wrap the indices, then drain everything into a larger buffer
+        q.push(6).unwrap();
+        q.push(7).unwrap();
+        let mut out = [-1; 8];
+        assert_eq!(q.pop_into(&mut out), 5);
+        assert_eq!(out, [3, 4, 5, 6, 7, -1, -1, -1]);
+        assert!(q.is_empty());
+
+        assert_eq!(q.pop_into(&mut out), 0);
+        assert_eq!(q.pop_into(&mut []), 0);
+    }
+
+    #[test]
+    fn peek_then_pop_same_element() {
+        let q: SpscQueue<i32, 4> = SpscQueue::new();
+        assert_eq!(unsafe { q.peek() }, None);
+
+        q.push(11).unwrap();
+        q.push(22).unwrap();
+        assert_eq!(unsafe { q.peek() }, Some(&11));
+        assert_eq!(unsafe { q.peek() }, Some(&11), "peek doesn't consume");
+        assert_eq!(q.len(), 2);
+        assert_eq!(q.pop(), Some(11));
+        assert_eq!(unsafe { q.peek() }, Some(&22));
+        assert_eq!(q.pop(), Some(22));
+        assert_eq!(unsafe { q.peek() }, None);
+    }
 }
@@ -0,0 +1,217 @@
+//! One-stop facade over codec, graph and output ISR.
+//!
+//! Without it, every application repeats the same wiring: enable the
+//! codec, call the output node's `isr()` from the DMA interrupt, and run
+//! the graph whenever that returns `true`. [`AudioSystem`] holds those
+//! pieces and does the dispatch, so the interrupt handler shrinks to one
+//! call plus re-arming the DMA channel.
+//!
+//! ## Usage
+//!
+//! Tag the output node in the graph with `#[output]`; `audio_graph!` then
+//! implements [`AudioGraph`] for the graph, and the system needs no
+//! further wiring:
+//!
+//! ```ignore
+//! audio_graph! {
+//!     pub struct Synth {
+//!         sine: AudioSynthSine {},
+//!         amp: AudioAmplifier { (sine, 0) },
+//!         #[output]
+//!         out: AudioOutputI2S { (amp, 0), (amp, 0) },
+//!     }
+//! }
+//!
+//! let mut system = AudioSystem::new(codec, Synth::new());
+//! system.begin()?;
+//! system.graph_mut().amp.gain(0.5);
+//!
+//! // In the DMA ISR:
+//! system.process_isr(&mut DMA_TX_BUFFER);
+//! // ... re-arm DMA ...
+//! ```
+//!
+//! The block pool is a static (`POOL`) and needs no setup;
+//! [`blocks_allocated()`](AudioSystem::blocks_allocated) reports its usage.
+
+use core::marker::PhantomData;
+
+use crate::block::pool::POOL;
+use crate::control::AudioControl;
+
+/// An output node driven from a DMA completion interrupt.
+///
+/// Implemented by [`AudioOutputI2S`](super::AudioOutputI2S),
+/// [`AudioOutputMQS`](super::AudioOutputMQS) and
+/// [`AudioOutputTDM`](super::AudioOutputTDM) so [`AudioSystem`] can drive
+/// any of them.
+pub trait DmaOutput {
+    /// Create the output with update responsibility, as the one output of
+    /// an [`AudioSystem`] needs. Used for `#[output]` nodes in
+    /// [`audio_graph!`](crate::audio_graph).
+    fn new_responsible() -> Self;
+
+    /// Fill `dma_buffer` with the next slice of audio; returns `true` when
+    /// the audio graph should be updated. See the node's inherent `isr()`.
+    fn isr<const LEN: usize>(&mut self, dma_buffer: &mut [u32; LEN]) -> bool;
+}
+
+/// An audio graph that owns its output node `O`.
+///
+/// [`audio_graph!`](crate::audio_graph) implements this for every node
+/// tagged `#[output]`; a hand-wired graph can implement it directly.
+pub trait AudioGraph<O> {
+    /// Process one block cycle, ending with the output node's `update()`.
+    fn update_all(&mut self);
+
+    /// The output node inside the graph.
+    fn output(&mut self) -> &mut O;
+}
+
+/// Codec, audio graph and output ISR dispatch in one place.
+///
+/// `G` is the audio graph and `O` its output node, which the graph holds
+/// (see [`AudioGraph`]).
+pub struct AudioSystem<C, G, O> {
+    codec: C,
+    graph: G,
+    _output: PhantomData<fn() -> O>,
+}
+
+impl<C: AudioControl, G: AudioGraph<O>, O: DmaOutput> AudioSystem<C, G, O> {
+    /// Assemble a system. Nothing touches the hardware until
+    /// [`begin()`](Self::begin).
+    pub fn new(codec: C, graph: G) -> Self {
+        AudioSystem {
+            codec,
+            graph,
+            _output: PhantomData,
+        }
+    }
+
+    /// Enable the codec. Call once, before starting DMA.
+    pub fn begin(&mut self) -> Result<(), C::Error> {
+        self.codec.enable()
+    }
+
+    /// Handle the output DMA interrupt: fill `dma_buffer` and, when a block
+    /// has been consumed, run the graph to queue the next one.
+    ///
+    /// Returns `true` if the graph was updated.
+    pub fn process_isr<const LEN: usize>(&mut self, dma_buffer: &mut [u32; LEN]) -> bool {
+        let should_update = self.graph.output().isr(dma_buffer);
+        if should_update {
+            self.graph.update_all();
+        }
+        should_update
+    }
+
+    /// The codec, for volume and input control.
+    pub fn codec_mut(&mut self) -> &mut C {
+        &mut self.codec
+    }
+
+    /// The audio graph.
+    pub fn graph(&self) -> &G {
+        &self.graph
+    }
+
+    /// The audio graph, for configuring nodes.
+    pub fn graph_mut(&mut self) -> &mut G {
+        &mut self.graph
+    }
+
+    /// The output node, for its own options (e.g. a DC blocker).
+    pub fn output_mut(&mut self) -> &mut O {
+        self.graph.output()
+    }
+
+    /// Number of audio blocks currently allocated from the pool.
+    pub fn blocks_allocated(&self) -> u32 {
+        POOL.allocated_count()
+    }
+}
+
+#[cfg(all(test, feature = "dsp"))]
+mod tests {
+    use super::*;
+    use crate::constants::AUDIO_BLOCK_SAMPLES;
+    use crate::io::AudioOutputI2S;
+    use crate::nodes::AudioSynthSine;
+
+    /// Codec stand-in that records whether it was enabled.
+    struct MockCodec {
+        enabled: bool,
+    }
+
+    impl AudioControl for MockCodec {
+        type Error = ();
+
+        fn enable(&mut self) -> Result<(), ()> {
+            self.enabled = true;
+            Ok(())
+        }
+
+        fn disable(&mut self) -> Result<(), ()> {
+            self.enabled = false;
+            Ok(())
+        }
+
+        fn volume(&mut self, _level: f32) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+
+    crate::audio_graph! {
+        struct SineOut {
+            sine: AudioSynthSine {},
+            #[output]
+            out: AudioOutputI2S { (sine, 0), (sine, 0) },
+        }
+    }
+
+    fn sine_system() -> AudioSystem<MockCodec, SineOut, AudioOutputI2S> {
+        AudioSystem::new(MockCodec { enabled: false }, SineOut::new())
+    }
+
+    #[test]
+    fn system_drives_graph_from_isr() {
+        POOL.reset();
+        let mut system = sine_system();
+        system.begin().unwrap();
+        assert!(system.codec_mut().enabled);
+        assert!(system.output_mut().has_update_responsibility());
+        system.graph_mut().sine.frequency(1000.0);
+        system.graph_mut().sine.amplitude(0.5);
+
+        let mut dma_buf = [0u32; AUDIO_BLOCK_SAMPLES * 2];
+        // First interrupt sends silence and runs the graph; the second
+        // sends the block that update produced
+        assert!(system.process_isr(&mut dma_buf));
+        assert!(dma_buf.iter().all(|&w| w == 0));
+        assert!(system.process_isr(&mut dma_buf));
+        assert!(
+            dma_buf.iter().any(|&w| w != 0),
+            "sine reached the DMA buffer"
+        );
+        for frame in dma_buf.chunks_exact(2) {
+            assert_eq!(frame[0], frame[1], "same block on both channels");
+        }
+        assert_eq!(
+            system.blocks_allocated(),
+            1,
+            "one block queued at the output"
+        );
+    }
+
+    #[test]
+    fn system_without_update_responsibility_never_updates() {
+        POOL.reset();
+        let mut system = sine_system();
+        *system.output_mut() = AudioOutputI2S::new(false);
+        system.graph_mut().sine.amplitude(1.0);
+        let mut dma_buf = [0u32; AUDIO_BLOCK_SAMPLES * 2];
+        assert!(!system.process_isr(&mut dma_buf));
+        assert!(!system.output_mut().has_left_block());
+    }
+}
@@ -13,7 +13,7 @@
 //! | Memory | [`block`] | Fixed-size audio block pool with refcounted handles |
 //! | Trait | [`node`] / [`control`] | `AudioNode` and `AudioControl` traits |
 //! | I/O | [`io`] | I²S input/output, play/record queues |
-//! | Codec | [`codec`] | SGTL5000 codec driver (feature-gated) |
+//! | Codec | [`codec`] | SGTL5000 / WM8731 codec drivers (feature-gated) |
 //! | DSP | [`dsp`] / [`nodes`] | Synthesis, effects, analysis (feature-gated) |
 //! | Graph | [`graph`] | [`audio_graph!`] macro for declarative wiring |
 //!
@@ -51,13 +51,17 @@
 //! |---------|---------|---------|
 //! | `dsp` | yes | DSP math utilities, synthesis/effect/analysis nodes |
 //! | `sgtl5000` | yes | SGTL5000 codec driver (requires `embedded-hal`) |
+//! | `wm8731` | no | WM8731 codec driver (requires `embedded-hal`) |
+//! | `metrics` | no | DWT cycle counts for `#[instrument]` graphs (see [`graph`]) |
+//! | `pool-64` | no | 64-block audio pool instead of 32 |
+//! | `pool-128` | no | 128-block audio pool |
 //!
 //! ## Audio parameters
 //!
 //! - **Block size:** 128 samples ([`constants::AUDIO_BLOCK_SAMPLES`])
 //! - **Sample rate:** 44 117.647 Hz ([`constants::AUDIO_SAMPLE_RATE`])
 //! - **Sample format:** `i16` (signed 16-bit)
-//! - **Block pool:** 32 blocks ([`constants::AUDIO_MEMORY_BLOCKS`])
+//! - **Block pool:** 32 blocks, or 64/128 with `pool-64`/`pool-128` ([`constants::POOL_SIZE`])
 
 #![no_std]
 
@@ -68,7 +72,7 @@ pub mod control;
 pub mod io;
 pub mod graph;
 
-#[cfg(feature = "sgtl5000")]
+#[cfg(any(feature = "sgtl5000", feature = "wm8731"))]
 pub mod codec;
 
 #[cfg(feature = "dsp")]
@@ -76,3 +80,7 @@ pub mod dsp;
 
 #[cfg(feature = "dsp")]
 pub mod nodes;
+
+// Identifier pasting for `audio_graph!` (e.g. `INDEX_<NODE>` consts)
+#[doc(hidden)]
+pub use paste;
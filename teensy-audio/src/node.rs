@@ -1,4 +1,6 @@
 use crate::block::{AudioBlockMut, AudioBlockRef};
+#[cfg(feature = "dsp")]
+use crate::dsp::intrinsics::saturate16;
 
 /// Core trait for all audio processing nodes.
 ///
@@ -11,6 +13,16 @@ pub trait AudioNode {
     /// Number of output channels this node produces.
     const NUM_OUTPUTS: usize;
 
+    /// Type name of the node (e.g. `"AudioMixer"`), for diagnostics and
+    /// metrics. By convention the type's name without generic parameters.
+    const NAME: &'static str;
+
+    /// Optional names for the output ports, indexed by port number.
+    ///
+    /// Lets `audio_graph!` accept `(node.name)` in place of `(node, port)`.
+    /// Nodes without named outputs leave this empty.
+    const OUTPUT_NAMES: &'static [&'static str] = &[];
+
     /// Process one block of audio.
     ///
     /// `inputs` contains `NUM_INPUTS` slots, each optionally holding a shared audio block.
@@ -21,4 +33,157 @@ pub trait AudioNode {
         inputs: &[Option<AudioBlockRef>],
         outputs: &mut [Option<AudioBlockMut>],
     );
+
+    /// Clear internal state (filter history, delay lines, envelope phase)
+    /// as if the node had just been created, keeping its configuration.
+    ///
+    /// Stateless nodes keep this default, which does nothing.
+    fn reset(&mut self) {}
+}
+
+/// Fixed-point unity gain for [`sum_blocks()`]: 1.0 in Q16.16 = 65536.
+#[cfg(feature = "dsp")]
+pub const UNITY_GAIN: i32 = 65536;
+
+/// Saturating weighted sum of a runtime number of blocks into `out`.
+///
+/// Each present input is scaled by its gain (Q16.16, [`UNITY_GAIN`] = 1.0),
+/// saturated, then saturating-added into the sum; `None` inputs are
+/// skipped, and with no inputs at all `out` is silence. This is the mixing
+/// core of [`AudioMixer`](crate::nodes::AudioMixer), exposed for custom
+/// nodes and ISR code whose channel count isn't known at compile time.
+///
+/// # Panics
+///
+/// Debug-asserts that `gains.len() == inputs.len()`.
+#[cfg(feature = "dsp")]
+pub fn sum_blocks(inputs: &[Option<AudioBlockRef>], gains: &[i32], out: &mut AudioBlockMut) {
+    debug_assert_eq!(inputs.len(), gains.len());
+
+    out.fill(0);
+    for (input, &gain) in inputs.iter().zip(gains) {
+        let Some(input) = input else { continue };
+        if gain == UNITY_GAIN {
+            // Fast path: just saturating-add
+            for (d, &s) in out.iter_mut().zip(input.iter()) {
+                *d = saturate16(*d as i32 + s as i32);
+            }
+        } else {
+            for (d, &s) in out.iter_mut().zip(input.iter()) {
+                let gained = saturate16((((s as i64) * (gain as i64)) >> 16) as i32);
+                *d = saturate16(*d as i32 + gained as i32);
+            }
+        }
+    }
+}
+
+/// Resolve an output port name to its index in `names`.
+///
+/// Used by `audio_graph!` in const context, so an unknown name is a
+/// compile-time error rather than a runtime panic.
+pub const fn output_port_index(names: &[&str], name: &str) -> usize {
+    let mut i = 0;
+    while i < names.len() {
+        if const_str_eq(names[i], name) {
+            return i;
+        }
+        i += 1;
+    }
+    panic!("audio_graph!: source node has no output port with that name");
+}
+
+/// Check that numeric output `port` exists on a node with `num_outputs`
+/// outputs, returning it unchanged.
+///
+/// Used by `audio_graph!` in const context, so wiring `(src, port)` past
+/// the source's `NUM_OUTPUTS` is a compile-time error.
+pub const fn checked_output_port(port: usize, num_outputs: usize) -> usize {
+    if port >= num_outputs {
+        panic!("audio_graph!: output port is out of range for the source node's NUM_OUTPUTS");
+    }
+    port
+}
+
+const fn const_str_eq(a: &str, b: &str) -> bool {
+    let a = a.as_bytes();
+    let b = b.as_bytes();
+    if a.len() != b.len() {
+        return false;
+    }
+    let mut i = 0;
+    while i < a.len() {
+        if a[i] != b[i] {
+            return false;
+        }
+        i += 1;
+    }
+    true
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn output_port_index_finds_name() {
+        const NAMES: &[&str] = &["lowpass", "bandpass", "highpass"];
+        assert_eq!(output_port_index(NAMES, "lowpass"), 0);
+        assert_eq!(output_port_index(NAMES, "highpass"), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn output_port_index_rejects_unknown_name() {
+        output_port_index(&["left", "right"], "centre");
+    }
+
+    #[test]
+    fn checked_output_port_passes_valid_port() {
+        assert_eq!(checked_output_port(0, 1), 0);
+        assert_eq!(checked_output_port(2, 3), 2);
+    }
+
+    #[test]
+    #[should_panic]
+    fn checked_output_port_rejects_out_of_range() {
+        checked_output_port(1, 1);
+    }
+
+    #[cfg(feature = "dsp")]
+    #[test]
+    fn sum_blocks_matches_mixer() {
+        use crate::block::pool::POOL;
+        use crate::nodes::AudioMixer;
+
+        POOL.reset();
+        let mut inputs: [Option<AudioBlockRef>; 3] = Default::default();
+        for (ch, input) in inputs.iter_mut().enumerate() {
+            let mut block = AudioBlockMut::alloc().unwrap();
+            for (i, s) in block.iter_mut().enumerate() {
+                *s = ((i as i32 * 397 * (ch as i32 + 1)) % 60000 - 30000) as i16;
+            }
+            *input = Some(block.into_shared());
+        }
+        let levels = [1.0f32, 0.5, -1.25];
+        let gains = levels.map(|level| (level * 65536.0) as i32);
+
+        let mut mixer = AudioMixer::<3>::new();
+        for (ch, &level) in levels.iter().enumerate() {
+            mixer.gain(ch, level);
+        }
+        let mut outputs = [AudioBlockMut::alloc()];
+        mixer.update(&inputs, &mut outputs);
+
+        let mut out = AudioBlockMut::alloc().unwrap();
+        out.fill(1234);
+        sum_blocks(&inputs, &gains, &mut out);
+        assert_eq!(&out[..], &outputs[0].as_ref().unwrap()[..]);
+        assert!(out.iter().any(|&s| s == i16::MAX || s == i16::MIN), "exercises saturation");
+
+        // Missing inputs are skipped
+        inputs[0] = None;
+        sum_blocks(&inputs[..2], &gains[..2], &mut out);
+        let expected = ((inputs[1].as_ref().unwrap()[5] as i64 * gains[1] as i64) >> 16) as i16;
+        assert_eq!(out[5], expected);
+    }
 }
@@ -8,7 +8,10 @@ use crate::dsp::intrinsics::saturate16;
 use crate::node::AudioNode;
 
 /// Fixed-point unity gain: 1.0 in Q16.16 format.
-const MULTI_UNITYGAIN: i32 = 65536;
+pub(super) const MULTI_UNITYGAIN: i32 = 65536;
+
+/// Default level at or below which [`AudioAmplifier::gain_db`] mutes.
+const DEFAULT_SILENCE_FLOOR_DB: f32 = -100.0;
 
 /// Single-channel amplifier. One input, one output.
 ///
@@ -16,10 +19,13 @@ const MULTI_UNITYGAIN: i32 = 65536;
 /// ```ignore
 /// let mut amp = AudioAmplifier::new();
 /// amp.gain(0.75); // 75% volume
+/// amp.gain_db(-12.0); // or in decibels
 /// ```
 pub struct AudioAmplifier {
     /// Gain in Q16.16 fixed-point. 65536 = unity (1.0).
     multiplier: i32,
+    /// `gain_db()` levels at or below this are exact silence.
+    silence_floor_db: f32,
 }
 
 impl AudioAmplifier {
@@ -27,6 +33,7 @@ impl AudioAmplifier {
     pub const fn new() -> Self {
         AudioAmplifier {
             multiplier: MULTI_UNITYGAIN,
+            silence_floor_db: DEFAULT_SILENCE_FLOOR_DB,
         }
     }
 
@@ -34,56 +41,93 @@ impl AudioAmplifier {
     ///
     /// 0.0 = silence, 1.0 = unity, >1.0 = boost. Clamped to ±32767.0.
     pub fn gain(&mut self, level: f32) {
-        let clamped = if level > 32767.0 {
-            32767.0
-        } else if level < -32767.0 {
-            -32767.0
+        self.multiplier = gain_to_multiplier(level);
+    }
+
+    /// Set amplification in decibels.
+    ///
+    /// 0 dB = unity, −6.02 dB ≈ half, +6.02 dB ≈ double. Levels at or
+    /// below the [silence floor](Self::silence_floor_db) (−100 dB by
+    /// default) mute exactly, so the output block is dropped as for
+    /// `gain(0.0)` instead of being processed at an inaudible level.
+    pub fn gain_db(&mut self, db: f32) {
+        self.multiplier = if db <= self.silence_floor_db {
+            0
         } else {
-            level
+            gain_to_multiplier(libm::powf(10.0, db / 20.0))
         };
-        self.multiplier = (clamped * 65536.0) as i32;
     }
+
+    /// Set the level at or below which [`gain_db()`](Self::gain_db) mutes.
+    ///
+    /// Takes effect from the next `gain_db()` call.
+    pub fn silence_floor_db(&mut self, db: f32) {
+        self.silence_floor_db = db;
+    }
+}
+
+/// Convert a gain level to Q16.16, clamped to ±32767.0.
+pub(super) fn gain_to_multiplier(level: f32) -> i32 {
+    let clamped = if level > 32767.0 {
+        32767.0
+    } else if level < -32767.0 {
+        -32767.0
+    } else {
+        level
+    };
+    (clamped * 65536.0) as i32
+}
+
+/// Apply a Q16.16 gain to one channel.
+///
+/// With no input the output slot is left untouched; with zero gain the
+/// output block is dropped (silence).
+pub(super) fn amplify(
+    mult: i32,
+    input: Option<&AudioBlockRef>,
+    output: &mut Option<AudioBlockMut>,
+) {
+    let input = match input {
+        Some(b) => b,
+        None => return, // No input, leave output as None (silence)
+    };
+
+    let mut out = match output.take() {
+        Some(b) => b,
+        None => return,
+    };
+
+    if mult == 0 {
+        // Zero gain: discard input and output block (silence)
+        drop(out);
+        return;
+    }
+
+    if mult == MULTI_UNITYGAIN {
+        // Unity gain: pass through (copy)
+        out.copy_from_slice(&input[..]);
+    } else {
+        // Apply gain: Q16.16 multiply with saturation
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            let val = ((input[i] as i64) * (mult as i64)) >> 16;
+            out[i] = saturate16(val as i32);
+        }
+    }
+
+    *output = Some(out);
 }
 
 impl AudioNode for AudioAmplifier {
     const NUM_INPUTS: usize = 1;
     const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioAmplifier";
 
     fn update(
         &mut self,
         inputs: &[Option<AudioBlockRef>],
         outputs: &mut [Option<AudioBlockMut>],
     ) {
-        let input = match inputs[0] {
-            Some(ref b) => b,
-            None => return, // No input, leave output as None (silence)
-        };
-
-        let mult = self.multiplier;
-
-        let mut out = match outputs[0].take() {
-            Some(b) => b,
-            None => return,
-        };
-
-        if mult == 0 {
-            // Zero gain: discard input and output block (silence)
-            drop(out);
-            return;
-        }
-
-        if mult == MULTI_UNITYGAIN {
-            // Unity gain: pass through (copy)
-            out.copy_from_slice(&input[..]);
-        } else {
-            // Apply gain: Q16.16 multiply with saturation
-            for i in 0..AUDIO_BLOCK_SAMPLES {
-                let val = ((input[i] as i64) * (mult as i64)) >> 16;
-                out[i] = saturate16(val as i32);
-            }
-        }
-
-        outputs[0] = Some(out);
+        amplify(self.multiplier, inputs[0].as_ref(), &mut outputs[0]);
     }
 }
 
@@ -167,6 +211,37 @@ mod tests {
         assert!(outputs[0].is_none());
     }
 
+    #[test]
+    fn amplifier_gain_db() {
+        let mut amp = AudioAmplifier::new();
+        amp.gain_db(0.0);
+        assert_eq!(amp.multiplier, MULTI_UNITYGAIN);
+        amp.gain_db(-6.02);
+        assert!((amp.multiplier - 32768).abs() < 8, "{}", amp.multiplier);
+        amp.gain_db(6.02);
+        assert!((amp.multiplier - 131072).abs() < 16, "{}", amp.multiplier);
+
+        // Above the floor a quiet level keeps a (small) multiplier
+        amp.gain_db(-80.0);
+        assert_eq!(amp.multiplier, 6);
+        amp.silence_floor_db(-60.0);
+        amp.gain_db(-80.0);
+        assert_eq!(amp.multiplier, 0);
+    }
+
+    #[test]
+    fn amplifier_gain_db_floor_is_exact_silence() {
+        reset_pool();
+        let mut amp = AudioAmplifier::new();
+        amp.gain_db(-120.0);
+        assert_eq!(amp.multiplier, 0);
+
+        let inputs = [Some(alloc_block_with(&[32767, -32768]).into_shared())];
+        let mut outputs = [AudioBlockMut::alloc()];
+        amp.update(&inputs, &mut outputs);
+        assert!(outputs[0].is_none());
+    }
+
     #[test]
     fn amplifier_boost() {
         reset_pool();
@@ -0,0 +1,117 @@
+//! Two-channel amplifier (stereo volume control).
+//!
+//! Applies the same Q16.16 gain math as [`AudioAmplifier`](super::AudioAmplifier)
+//! to a left/right pair, with linked or independent gains.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::node::AudioNode;
+
+use super::amplifier::{amplify, gain_to_multiplier, MULTI_UNITYGAIN};
+
+/// Stereo amplifier. Two inputs (left, right), two outputs (left, right).
+///
+/// Each channel behaves exactly like an [`AudioAmplifier`](super::AudioAmplifier):
+/// unity gain copies, zero gain drops that channel's output block.
+///
+/// # Example
+/// ```ignore
+/// let mut amp = AudioAmplifierStereo::new();
+/// amp.gain(0.75);         // both channels
+/// amp.gain_lr(1.0, 0.5);  // balance towards the left
+/// ```
+pub struct AudioAmplifierStereo {
+    /// Left and right gains in Q16.16 fixed-point. 65536 = unity (1.0).
+    multipliers: [i32; 2],
+}
+
+impl AudioAmplifierStereo {
+    /// Create a new stereo amplifier at unity gain.
+    pub const fn new() -> Self {
+        AudioAmplifierStereo {
+            multipliers: [MULTI_UNITYGAIN; 2],
+        }
+    }
+
+    /// Set the same amplification level on both channels.
+    ///
+    /// 0.0 = silence, 1.0 = unity, >1.0 = boost. Clamped to ±32767.0.
+    pub fn gain(&mut self, level: f32) {
+        self.gain_lr(level, level);
+    }
+
+    /// Set independent left and right amplification levels.
+    pub fn gain_lr(&mut self, left: f32, right: f32) {
+        self.multipliers = [gain_to_multiplier(left), gain_to_multiplier(right)];
+    }
+}
+
+impl Default for AudioAmplifierStereo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioAmplifierStereo {
+    const NUM_INPUTS: usize = 2;
+    const NUM_OUTPUTS: usize = 2;
+    const NAME: &'static str = "AudioAmplifierStereo";
+    const OUTPUT_NAMES: &'static [&'static str] = &["left", "right"];
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        for (ch, output) in outputs.iter_mut().enumerate() {
+            amplify(self.multipliers[ch], inputs[ch].as_ref(), output);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    fn dc_block(value: i16) -> AudioBlockRef {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block.fill(value);
+        block.into_shared()
+    }
+
+    #[test]
+    fn stereo_gains_are_independent() {
+        reset_pool();
+        let mut amp = AudioAmplifierStereo::new();
+        amp.gain_lr(0.5, 2.0);
+
+        let inputs = [Some(dc_block(10000)), Some(dc_block(10000))];
+        let mut outputs = [AudioBlockMut::alloc(), AudioBlockMut::alloc()];
+        amp.update(&inputs, &mut outputs);
+
+        assert!((outputs[0].as_ref().unwrap()[0] - 5000).abs() <= 1);
+        assert!((outputs[1].as_ref().unwrap()[0] - 20000).abs() <= 1);
+
+        amp.gain(0.25);
+        let mut outputs = [AudioBlockMut::alloc(), AudioBlockMut::alloc()];
+        amp.update(&inputs, &mut outputs);
+        assert!((outputs[0].as_ref().unwrap()[0] - 2500).abs() <= 1);
+        assert!((outputs[1].as_ref().unwrap()[0] - 2500).abs() <= 1);
+    }
+
+    #[test]
+    fn stereo_zero_gain_drops_only_that_channel() {
+        reset_pool();
+        let mut amp = AudioAmplifierStereo::new();
+        amp.gain_lr(1.0, 0.0);
+
+        let inputs = [Some(dc_block(1234)), Some(dc_block(4321))];
+        let mut outputs = [AudioBlockMut::alloc(), AudioBlockMut::alloc()];
+        assert_eq!(POOL.allocated_count(), 4);
+        amp.update(&inputs, &mut outputs);
+
+        assert_eq!(outputs[0].as_ref().unwrap()[0], 1234);
+        assert!(outputs[1].is_none());
+        assert_eq!(POOL.allocated_count(), 3, "muted channel's output freed");
+    }
+}
@@ -0,0 +1,231 @@
+//! 256-point FFT spectrum analyzer.
+//!
+//! Port of `TeensyAudio/analyze_fft256.cpp`. Like the C++ version the
+//! analysis is 50% overlapped: every incoming block is joined with the
+//! previous one to form a 256-sample window, so a fresh spectrum is produced
+//! once per block (~344 per second) after the first.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::dsp::fft::{fft_q15, magnitude_q15};
+use crate::dsp::wavetables::SINE_TABLE;
+use crate::node::AudioNode;
+
+/// FFT length.
+const FFT_SIZE: usize = 256;
+
+/// Number of output bins (DC up to just below Nyquist).
+pub const FFT256_BINS: usize = FFT_SIZE / 2;
+
+/// 256-point spectrum analyzer. Analyzer node: 1 input, 0 outputs.
+///
+/// Each window is Hann-weighted and transformed with
+/// [`fft_q15`](crate::dsp::fft::fft_q15). Bin `n` covers
+/// `n × AUDIO_SAMPLE_RATE_EXACT / 256` Hz (~172 Hz per bin). Magnitudes are
+/// scaled so a full-scale sine centred on a bin reads about 1.0.
+///
+/// # Example
+/// ```ignore
+/// if fft.available() {
+///     let bass = fft.read_range(1, 3);
+///     let a440 = fft.read(3);
+/// }
+/// ```
+pub struct AudioAnalyzeFFT256 {
+    /// Previous input block (first half of the next window).
+    prev: [i16; AUDIO_BLOCK_SAMPLES],
+    /// Whether `prev` holds real audio yet.
+    have_prev: bool,
+    /// FFT working buffers.
+    real: [i16; FFT_SIZE],
+    imag: [i16; FFT_SIZE],
+    /// Latest bin magnitudes, 16384 ≈ full-scale sine.
+    output: [u16; FFT256_BINS],
+    /// A new spectrum has been computed since the last read.
+    new_output: bool,
+}
+
+impl AudioAnalyzeFFT256 {
+    /// Create a new analyzer.
+    pub const fn new() -> Self {
+        AudioAnalyzeFFT256 {
+            prev: [0; AUDIO_BLOCK_SAMPLES],
+            have_prev: false,
+            real: [0; FFT_SIZE],
+            imag: [0; FFT_SIZE],
+            output: [0; FFT256_BINS],
+            new_output: false,
+        }
+    }
+
+    /// Returns `true` if a new spectrum is ready since the last read.
+    pub fn available(&self) -> bool {
+        self.new_output
+    }
+
+    /// Magnitude of bin `bin` (0–127) from the latest spectrum.
+    ///
+    /// Returns 0.0 for bins past 127.
+    pub fn read(&mut self, bin: usize) -> f32 {
+        self.new_output = false;
+        match self.output.get(bin) {
+            Some(&m) => m as f32 / 16384.0,
+            None => 0.0,
+        }
+    }
+
+    /// Sum of bin magnitudes from `from` to `to` inclusive (either order,
+    /// clamped to 0–127).
+    pub fn read_range(&mut self, from: usize, to: usize) -> f32 {
+        self.new_output = false;
+        let (lo, hi) = if from <= to { (from, to) } else { (to, from) };
+        let hi = hi.min(FFT256_BINS - 1);
+        if lo > hi {
+            return 0.0;
+        }
+        let sum: u32 = self.output[lo..=hi].iter().map(|&m| m as u32).sum();
+        sum as f32 / 16384.0
+    }
+
+    /// Window `prev` + `current` into the work buffers.
+    fn load_window(&mut self, current: &[i16; AUDIO_BLOCK_SAMPLES]) {
+        for n in 0..FFT_SIZE {
+            let sample = if n < AUDIO_BLOCK_SAMPLES {
+                self.prev[n]
+            } else {
+                current[n - AUDIO_BLOCK_SAMPLES]
+            } as i32;
+            // Hann: (1 - cos(2πn/N)) / 2, in Q15
+            let window = (32767 - SINE_TABLE[(n + 64) & 0xFF] as i32) >> 1;
+            self.real[n] = ((sample * window) >> 15) as i16;
+            self.imag[n] = 0;
+        }
+    }
+
+    /// Transform the window and convert the first half of the spectrum to
+    /// magnitudes.
+    fn analyze(&mut self) {
+        fft_q15(&mut self.real, &mut self.imag);
+        magnitude_q15(&self.real, &self.imag, &mut self.output);
+        // Peak bin of a Hann-windowed sine of amplitude A is A/4 after the
+        // FFT's 1/256; ×2 puts a full-scale sine near 16384.
+        for mag in self.output.iter_mut() {
+            *mag = mag.saturating_mul(2);
+        }
+    }
+}
+
+impl Default for AudioAnalyzeFFT256 {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioAnalyzeFFT256 {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = 0;
+    const NAME: &'static str = "AudioAnalyzeFFT256";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], _outputs: &mut [Option<AudioBlockMut>]) {
+        let input = match inputs[0] {
+            Some(ref b) => b,
+            None => return,
+        };
+
+        if self.have_prev {
+            self.load_window(input);
+            self.analyze();
+            self.new_output = true;
+        }
+        self.prev.copy_from_slice(&input[..]);
+        self.have_prev = true;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::constants::AUDIO_SAMPLE_RATE_EXACT;
+    use crate::nodes::AudioSynthSine;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    fn feed_sine(fft: &mut AudioAnalyzeFFT256, hz: f32, amplitude: f32, blocks: usize) {
+        let mut sine = AudioSynthSine::new();
+        sine.frequency(hz);
+        sine.amplitude(amplitude);
+        for _ in 0..blocks {
+            let mut src = [AudioBlockMut::alloc()];
+            sine.update(&[], &mut src);
+            let inputs = [src[0].take().map(|b| b.into_shared())];
+            fft.update(&inputs, &mut []);
+        }
+    }
+
+    fn peak_bin(fft: &mut AudioAnalyzeFFT256) -> usize {
+        (0..FFT256_BINS)
+            .max_by(|&a, &b| fft.read(a).partial_cmp(&fft.read(b)).unwrap())
+            .unwrap()
+    }
+
+    #[test]
+    fn fft_1khz_peaks_in_expected_bin() {
+        reset_pool();
+        let mut fft = AudioAnalyzeFFT256::new();
+        feed_sine(&mut fft, 1000.0, 0.8, 4);
+        assert!(fft.available());
+
+        // 1000 / (44117.6 / 256) ≈ 5.8
+        let expected = libm::roundf(1000.0 * FFT_SIZE as f32 / AUDIO_SAMPLE_RATE_EXACT) as usize;
+        assert_eq!(expected, 6);
+        assert_eq!(peak_bin(&mut fft), expected);
+
+        let peak = fft.read(expected);
+        assert!(
+            fft.read(30) < peak / 100.0,
+            "far bin {} vs peak {}",
+            fft.read(30),
+            peak
+        );
+        assert!(
+            fft.read(0) < peak / 100.0,
+            "DC bin {} vs peak {}",
+            fft.read(0),
+            peak
+        );
+        assert!(!fft.available(), "read clears available");
+    }
+
+    #[test]
+    fn fft_full_scale_bin_centre_reads_near_one() {
+        reset_pool();
+        let mut fft = AudioAnalyzeFFT256::new();
+        let hz = 20.0 * AUDIO_SAMPLE_RATE_EXACT / FFT_SIZE as f32;
+        feed_sine(&mut fft, hz, 1.0, 4);
+        let level = fft.read(20);
+        assert!((level - 1.0).abs() < 0.05, "bin 20 = {}", level);
+        // Hann leakage: each neighbour gets half the centre
+        assert!((fft.read(19) - 0.5).abs() < 0.05);
+        assert!((fft.read_range(19, 21) - 2.0).abs() < 0.1);
+    }
+
+    #[test]
+    fn fft_first_block_only_primes_the_window() {
+        reset_pool();
+        let mut fft = AudioAnalyzeFFT256::new();
+        feed_sine(&mut fft, 1000.0, 0.8, 1);
+        assert!(!fft.available());
+        feed_sine(&mut fft, 1000.0, 0.8, 1);
+        assert!(fft.available());
+    }
+
+    #[test]
+    fn fft_read_out_of_range() {
+        let mut fft = AudioAnalyzeFFT256::new();
+        assert_eq!(fft.read(FFT256_BINS), 0.0);
+        assert_eq!(fft.read_range(200, 300), 0.0);
+    }
+}
@@ -0,0 +1,359 @@
+//! Fundamental-frequency (pitch) detector using the YIN algorithm.
+//!
+//! Port of `TeensyAudio/analyze_notefreq.cpp`. The input is collected into a
+//! ring buffer of `WINDOW` samples; every `WINDOW / 2` new samples a copy
+//! of the window is taken and analyzed a slice at a time over the following
+//! updates, as in the C++, so successive estimates overlap by half and no
+//! single update pays for the whole search.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::{AUDIO_BLOCK_SAMPLES, AUDIO_SAMPLE_RATE_EXACT};
+use crate::dsp::music::frequency_to_midi_note;
+use crate::node::AudioNode;
+
+/// Lags searched per update: enough to finish one window's search in the
+/// `WINDOW / 2` samples before the next begins.
+const TAUS_PER_UPDATE: usize = AUDIO_BLOCK_SAMPLES;
+
+/// Progress of a YIN search spread over several updates.
+#[derive(Clone, Copy)]
+struct YinSearch {
+    /// Next lag to evaluate.
+    tau: usize,
+    /// Sum of `d(tau)` so far, for the cumulative mean normalization.
+    running: i64,
+    /// Cumulative mean normalized difference at `tau - 2` and `tau - 1`.
+    prev: f32,
+    cur: f32,
+    /// The normalized difference has dropped below the threshold.
+    below: bool,
+}
+
+/// Pitch detector. Analyzer node: 1 input, 0 outputs.
+///
+/// `WINDOW` is the analysis length in samples and must be a multiple of
+/// `2 × AUDIO_BLOCK_SAMPLES`. Lag is searched up to `WINDOW / 2`, so the
+/// lowest detectable frequency is about `AUDIO_SAMPLE_RATE_EXACT / (WINDOW / 2)`
+/// (~43 Hz for a 2048-sample window).
+///
+/// An analysis costs roughly `(WINDOW / 2)²` multiply-accumulates, spread
+/// so that each update does at most `AUDIO_BLOCK_SAMPLES × WINDOW / 2`
+/// (about 131 000 for a 2048-sample window). The window being analyzed is
+/// a copy, so the detector holds `2 × WINDOW` samples.
+///
+/// A new estimate is only published when YIN finds a period whose
+/// normalized difference is below the threshold; unpitched input leaves
+/// [`available()`](Self::available) false.
+///
+/// # Example
+/// ```ignore
+/// let mut notefreq = AudioAnalyzeNoteFrequency::<2048>::new();
+/// notefreq.begin(0.15);
+/// // ... after processing ...
+/// if notefreq.available() {
+///     let hz = notefreq.read();
+///     let confidence = notefreq.probability();
+/// }
+/// ```
+pub struct AudioAnalyzeNoteFrequency<const WINDOW: usize> {
+    buffer: [i16; WINDOW],
+    /// The window under analysis, oldest sample first.
+    frame: [i16; WINDOW],
+    /// The search in progress over `frame`, if any.
+    search: Option<YinSearch>,
+    /// Next write position in `buffer`.
+    head: usize,
+    /// Samples written since the last analysis.
+    pending: usize,
+    /// The buffer has been filled at least once.
+    primed: bool,
+    /// Whether [`begin()`](Self::begin) has been called.
+    enabled: bool,
+    /// YIN threshold on the cumulative mean normalized difference.
+    threshold: f32,
+    /// Latest estimate in Hz.
+    frequency: f32,
+    /// Confidence of the latest estimate, 0.0–1.0.
+    probability: f32,
+    new_output: bool,
+}
+
+impl<const WINDOW: usize> AudioAnalyzeNoteFrequency<WINDOW> {
+    /// The window must split into two halves of whole blocks.
+    const CHECK_SIZE: () = assert!(
+        WINDOW >= 2 * AUDIO_BLOCK_SAMPLES && WINDOW.is_multiple_of(2 * AUDIO_BLOCK_SAMPLES),
+        "AudioAnalyzeNoteFrequency: WINDOW must be a multiple of 2 * AUDIO_BLOCK_SAMPLES"
+    );
+
+    /// Create a new, idle detector. Call [`begin()`](Self::begin) to start it.
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::CHECK_SIZE;
+        AudioAnalyzeNoteFrequency {
+            buffer: [0; WINDOW],
+            frame: [0; WINDOW],
+            search: None,
+            head: 0,
+            pending: 0,
+            primed: false,
+            enabled: false,
+            threshold: 0.15,
+            frequency: 0.0,
+            probability: 0.0,
+            new_output: false,
+        }
+    }
+
+    /// Start detecting with the given YIN threshold (typically 0.1–0.2).
+    ///
+    /// Lower thresholds reject more noisy or ambiguous input. Clears any
+    /// buffered audio.
+    pub fn begin(&mut self, threshold: f32) {
+        self.threshold = threshold.clamp(0.0, 1.0);
+        self.enabled = true;
+        self.head = 0;
+        self.pending = 0;
+        self.primed = false;
+        self.search = None;
+        self.new_output = false;
+    }
+
+    /// Returns `true` if a new estimate is ready since the last `read()`.
+    pub fn available(&self) -> bool {
+        self.new_output
+    }
+
+    /// Read the latest fundamental frequency estimate in Hz.
+    pub fn read(&mut self) -> f32 {
+        self.new_output = false;
+        self.frequency
+    }
+
+    /// Read the latest estimate as the nearest MIDI note and its deviation
+    /// in cents. See [`frequency_to_midi_note()`].
+    pub fn read_midi_note(&mut self) -> Option<(u8, f32)> {
+        frequency_to_midi_note(self.read())
+    }
+
+    /// Confidence of the latest estimate (0.0–1.0, higher is more certain).
+    pub fn probability(&self) -> f32 {
+        self.probability
+    }
+
+    /// Copy the window, oldest sample first, and start searching it.
+    fn start_analysis(&mut self) {
+        let (newer, older) = self.buffer.split_at(self.head);
+        self.frame[..older.len()].copy_from_slice(older);
+        self.frame[older.len()..].copy_from_slice(newer);
+        self.search = Some(YinSearch {
+            tau: 1,
+            running: 0,
+            prev: 1.0,
+            cur: 1.0,
+            below: false,
+        });
+    }
+
+    /// YIN difference function `d(tau)` over the first half of the window.
+    fn difference(&self, tau: usize) -> i64 {
+        let half = WINDOW / 2;
+        let mut sum = 0i64;
+        for (&a, &b) in self.frame[..half].iter().zip(&self.frame[tau..tau + half]) {
+            let delta = (a as i32 - b as i32) as i64;
+            sum += delta * delta;
+        }
+        sum
+    }
+
+    /// Search the next [`TAUS_PER_UPDATE`] lags, publishing an estimate if
+    /// a period is found.
+    fn continue_analysis(&mut self) {
+        let Some(mut search) = self.search else {
+            return;
+        };
+        let half = WINDOW / 2;
+        let end = (search.tau + TAUS_PER_UPDATE).min(half);
+
+        while search.tau < end {
+            let tau = search.tau;
+            let d = self.difference(tau);
+            search.running += d;
+            let next = if search.running == 0 {
+                1.0
+            } else {
+                (d as f64 * tau as f64 / search.running as f64) as f32
+            };
+
+            // Once under the threshold, walk down to the local minimum at
+            // tau - 1 and refine it with a parabola through its neighbours
+            if search.below && next >= search.cur {
+                let (prev, cur) = (search.prev, search.cur);
+                let denom = prev - 2.0 * cur + next;
+                let shift = if denom > 0.0 {
+                    0.5 * (prev - next) / denom
+                } else {
+                    0.0
+                };
+                let period = (tau - 1) as f32 + shift;
+                self.frequency = AUDIO_SAMPLE_RATE_EXACT / period;
+                self.probability = (1.0 - cur).clamp(0.0, 1.0);
+                self.new_output = true;
+                self.search = None;
+                return;
+            }
+            if next < self.threshold {
+                search.below = true;
+            }
+
+            search.prev = search.cur;
+            search.cur = next;
+            search.tau += 1;
+        }
+
+        self.search = if search.tau < half { Some(search) } else { None };
+    }
+}
+
+impl<const WINDOW: usize> Default for AudioAnalyzeNoteFrequency<WINDOW> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const WINDOW: usize> AudioNode for AudioAnalyzeNoteFrequency<WINDOW> {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = 0;
+    const NAME: &'static str = "AudioAnalyzeNoteFrequency";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], _outputs: &mut [Option<AudioBlockMut>]) {
+        if !self.enabled {
+            return;
+        }
+        let destination = &mut self.buffer[self.head..self.head + AUDIO_BLOCK_SAMPLES];
+        match inputs[0] {
+            Some(ref block) => destination.copy_from_slice(&block[..]),
+            None => destination.fill(0),
+        }
+        self.head += AUDIO_BLOCK_SAMPLES;
+        if self.head == WINDOW {
+            self.head = 0;
+            self.primed = true;
+        }
+
+        self.pending += AUDIO_BLOCK_SAMPLES;
+        if self.primed && self.pending >= WINDOW / 2 {
+            self.pending = 0;
+            self.start_analysis();
+        }
+        self.continue_analysis();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::nodes::AudioSynthSine;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    fn feed_sine<const WINDOW: usize>(
+        notefreq: &mut AudioAnalyzeNoteFrequency<WINDOW>,
+        sine: &mut AudioSynthSine,
+        blocks: usize,
+    ) {
+        for _ in 0..blocks {
+            let mut src = [AudioBlockMut::alloc()];
+            sine.update(&[], &mut src);
+            let inputs = [src[0].take().map(|b| b.into_shared())];
+            notefreq.update(&inputs, &mut []);
+        }
+    }
+
+    /// Feed one window of a sine at `hz`, then the half window over which
+    /// it is searched, and return the detector.
+    fn listen(hz: f32) -> AudioAnalyzeNoteFrequency<2048> {
+        reset_pool();
+        let mut notefreq = AudioAnalyzeNoteFrequency::<2048>::new();
+        notefreq.begin(0.15);
+        let mut sine = AudioSynthSine::new();
+        sine.frequency(hz);
+        sine.amplitude(0.7);
+        feed_sine(&mut notefreq, &mut sine, 3 * 1024 / AUDIO_BLOCK_SAMPLES);
+        assert!(notefreq.available(), "no estimate for {} Hz", hz);
+        notefreq
+    }
+
+    fn detect(hz: f32) -> (f32, f32) {
+        let mut notefreq = listen(hz);
+        (notefreq.read(), notefreq.probability())
+    }
+
+    #[test]
+    fn notefreq_detects_a440() {
+        let (hz, probability) = detect(440.0);
+        assert!((hz - 440.0).abs() < 2.0, "detected {} Hz", hz);
+        assert!(probability > 0.9, "probability {}", probability);
+    }
+
+    #[test]
+    fn notefreq_detects_a220() {
+        let (hz, _) = detect(220.0);
+        assert!((hz - 220.0).abs() < 2.0, "detected {} Hz", hz);
+    }
+
+    #[test]
+    fn notefreq_read_midi_note() {
+        let (note, cents) = listen(440.0).read_midi_note().unwrap();
+        assert_eq!(note, 69);
+        assert!(cents.abs() < 8.0, "A4 off by {} cents", cents);
+
+        // 445 Hz is 1200 * log2(445 / 440) ≈ +19.6 cents above A4
+        let (note, cents) = listen(445.0).read_midi_note().unwrap();
+        assert_eq!(note, 69);
+        assert!((cents - 19.6).abs() < 8.0, "445 Hz read as {} cents", cents);
+    }
+
+    #[test]
+    fn notefreq_search_spans_updates_on_a_copy() {
+        reset_pool();
+        let mut notefreq = AudioAnalyzeNoteFrequency::<2048>::new();
+        notefreq.begin(0.15);
+        let mut sine = AudioSynthSine::new();
+        sine.frequency(60.0);
+        sine.amplitude(0.7);
+        feed_sine(&mut notefreq, &mut sine, 2048 / AUDIO_BLOCK_SAMPLES);
+        // A 735-sample period lies beyond the first update's lags
+        assert!(!notefreq.available());
+
+        // Silence arriving meanwhile doesn't disturb the copied window
+        let mut blocks = 0;
+        while !notefreq.available() {
+            notefreq.update(&[None], &mut []);
+            blocks += 1;
+            assert!(blocks < 1024 / AUDIO_BLOCK_SAMPLES, "search overran");
+        }
+        let hz = notefreq.read();
+        assert!((hz - 60.0).abs() < 1.0, "detected {} Hz", hz);
+        assert!(blocks >= 735 / TAUS_PER_UPDATE, "finished after {}", blocks);
+    }
+
+    #[test]
+    fn notefreq_silence_gives_no_estimate() {
+        let mut notefreq = AudioAnalyzeNoteFrequency::<1024>::new();
+        notefreq.begin(0.15);
+        for _ in 0..16 {
+            notefreq.update(&[None], &mut []);
+        }
+        assert!(!notefreq.available());
+    }
+
+    #[test]
+    fn notefreq_idle_until_begin() {
+        let mut notefreq = AudioAnalyzeNoteFrequency::<256>::new();
+        notefreq.update(&[None], &mut []);
+        assert_eq!(notefreq.head, 0);
+    }
+}
@@ -1,17 +1,40 @@
 //! Peak level detector / analyzer.
 //!
 //! Port of `TeensyAudio/analyze_peak.cpp`. Tracks the minimum and maximum
-//! sample values seen since the last `read()`.
+//! sample values seen since the last `read()`, and optionally an estimate of
+//! the true (inter-sample) peak or a decaying peak-hold for meters.
 
 use crate::block::{AudioBlockMut, AudioBlockRef};
 use crate::constants::AUDIO_BLOCK_SAMPLES;
 use crate::node::AudioNode;
 
+/// Taps per phase of the true-peak interpolator.
+const TRUE_PEAK_TAPS: usize = 8;
+
+/// 4× interpolation filter: Hann-windowed sinc phases at 1/4, 1/2 and 3/4 of
+/// the way between the middle two taps, in Q15 (each phase sums to 1.0). The
+/// zero phase is the input sample itself.
+const TRUE_PEAK_PHASES: [[i16; TRUE_PEAK_TAPS]; 3] = [
+    [-191, 1317, -4582, 29171, 8991, -2514, 595, -19],
+    [-113, 1284, -4793, 20006, 20006, -4793, 1284, -113],
+    [-19, 595, -2514, 8991, 29171, -4582, 1317, -191],
+];
+
 /// Peak level detector. Analyzer node: 1 input, 0 outputs.
 ///
 /// Tracks the maximum absolute sample value and peak-to-peak range
 /// over one or more block periods.
 ///
+/// With [`true_peak()`](Self::true_peak) enabled, the input is also
+/// upsampled 4× through a short FIR and [`read()`](Self::read) reports the
+/// larger of the sample peak and the interpolated peak. A signal whose
+/// samples all stay below full scale can still swing past it between
+/// samples and clip at the DAC; in that case `read()` returns more than 1.0.
+///
+/// With [`peak_hold()`](Self::peak_hold) enabled, `read()` no longer snaps
+/// the level back to zero: the held peak falls by a fixed step each block
+/// until a louder block raises it again, the ballistics of a VU meter.
+///
 /// # Example
 /// ```ignore
 /// let mut peak = AudioAnalyzePeak::new();
@@ -24,6 +47,16 @@ pub struct AudioAnalyzePeak {
     min_val: i16,
     max_val: i16,
     new_output: bool,
+    /// Whether inter-sample peak estimation is enabled.
+    true_peak: bool,
+    /// Largest absolute interpolated value since the last `read()`.
+    true_max: i32,
+    /// Most recent input samples, oldest first.
+    history: [i16; TRUE_PEAK_TAPS],
+    /// Peak-hold decay per block in sample units; 0 = peak-hold off.
+    hold_decay: i16,
+    /// Held absolute peak in sample units (above 32767 only for true peaks).
+    held: i32,
 }
 
 impl AudioAnalyzePeak {
@@ -33,33 +66,78 @@ impl AudioAnalyzePeak {
             min_val: i16::MAX,
             max_val: i16::MIN,
             new_output: false,
+            true_peak: false,
+            true_max: 0,
+            history: [0; TRUE_PEAK_TAPS],
+            hold_decay: 0,
+            held: 0,
         }
     }
 
+    /// Enable or disable true-peak (inter-sample) estimation.
+    pub fn true_peak(&mut self, enable: bool) {
+        self.true_peak = enable;
+        self.true_max = 0;
+        self.history = [0; TRUE_PEAK_TAPS];
+    }
+
+    /// Enable peak-hold: the reported peak falls by `decay_per_block`
+    /// (a fraction of full scale, clamped to 1.0) every block instead of
+    /// resetting on `read()`. Zero or negative turns peak-hold off.
+    ///
+    /// At 44.1 kHz there are ~345 blocks per second, so `0.003` drops a
+    /// full-scale peak to zero in about a second.
+    pub fn peak_hold(&mut self, decay_per_block: f32) {
+        self.hold_decay = if decay_per_block > 0.0 {
+            // At least one LSB, or small rates would never decay
+            ((decay_per_block.min(1.0) * 32767.0) as i16).max(1)
+        } else {
+            0
+        };
+        self.held = 0;
+    }
+
     /// Returns `true` if new data has been accumulated since the last `read()`.
     pub fn available(&self) -> bool {
         self.new_output
     }
 
-    /// Read the peak level (0.0–1.0) and reset the accumulator.
+    /// Return the peak level that [`read()`](Self::read) would, without
+    /// resetting anything.
     ///
-    /// Returns the maximum absolute sample value normalized to [0.0, 1.0].
-    pub fn read(&mut self) -> f32 {
-        let min = self.min_val;
-        let max = self.max_val;
-        self.min_val = i16::MAX;
-        self.max_val = i16::MIN;
-        self.new_output = false;
+    /// Like `read()`, the value is only meaningful once
+    /// [`available()`](Self::available) has reported data.
+    pub fn peek(&self) -> f32 {
+        if self.hold_decay > 0 {
+            return self.held as f32 / 32767.0;
+        }
 
-        let abs_min = if min == i16::MIN {
+        let abs_min = if self.min_val == i16::MIN {
             // -32768 abs would overflow i16, handle specially
             32768i32
         } else {
-            (min as i32).abs()
+            (self.min_val as i32).abs()
         };
-        let abs_max = (max as i32).abs();
+        let abs_max = (self.max_val as i32).abs();
         let peak = if abs_min > abs_max { abs_min } else { abs_max };
-        peak as f32 / 32767.0
+        peak.max(self.true_max) as f32 / 32767.0
+    }
+
+    /// Read the peak level (0.0–1.0) and reset the accumulator.
+    ///
+    /// Returns the maximum absolute sample value normalized to [0.0, 1.0].
+    /// In true-peak mode the interpolated peak is included, which may
+    /// exceed 1.0. In peak-hold mode the held peak is returned and keeps
+    /// decaying on its own; only [`available()`](Self::available) is reset.
+    pub fn read(&mut self) -> f32 {
+        let level = self.peek();
+        self.new_output = false;
+        if self.hold_decay == 0 {
+            self.min_val = i16::MAX;
+            self.max_val = i16::MIN;
+            self.true_max = 0;
+        }
+        level
     }
 
     /// Read the peak-to-peak level (0.0–2.0) and reset the accumulator.
@@ -74,24 +152,53 @@ impl AudioAnalyzePeak {
 
         (max as i32 - min as i32) as f32 / 32767.0
     }
+
+    /// Run the block through the 4× interpolator, tracking the largest
+    /// absolute in-between value.
+    fn scan_true_peak(&mut self, input: &[i16; AUDIO_BLOCK_SAMPLES]) {
+        let mut true_max = self.true_max;
+        for &sample in input.iter() {
+            self.history.copy_within(1.., 0);
+            self.history[TRUE_PEAK_TAPS - 1] = sample;
+            for phase in TRUE_PEAK_PHASES.iter() {
+                let sum: i32 = phase
+                    .iter()
+                    .zip(self.history.iter())
+                    .map(|(&c, &x)| c as i32 * x as i32)
+                    .sum();
+                true_max = true_max.max((sum >> 15).abs());
+            }
+        }
+        self.true_max = true_max;
+    }
 }
 
 impl AudioNode for AudioAnalyzePeak {
     const NUM_INPUTS: usize = 1;
     const NUM_OUTPUTS: usize = 0;
+    const NAME: &'static str = "AudioAnalyzePeak";
 
     fn update(
         &mut self,
         inputs: &[Option<AudioBlockRef>],
         _outputs: &mut [Option<AudioBlockMut>],
     ) {
+        let hold = self.hold_decay > 0;
+        if hold {
+            self.held = (self.held - self.hold_decay as i32).max(0);
+        }
+
         let input = match inputs[0] {
             Some(ref b) => b,
-            None => return,
+            None => {
+                // No input: the held peak still falls like it would for silence
+                self.new_output |= hold;
+                return;
+            }
         };
 
-        let mut min = self.min_val;
-        let mut max = self.max_val;
+        let mut min = i16::MAX;
+        let mut max = i16::MIN;
 
         for i in 0..AUDIO_BLOCK_SAMPLES {
             let d = input[i];
@@ -103,9 +210,20 @@ impl AudioNode for AudioAnalyzePeak {
             }
         }
 
-        self.min_val = min;
-        self.max_val = max;
+        self.min_val = self.min_val.min(min);
+        self.max_val = self.max_val.max(max);
         self.new_output = true;
+
+        if self.true_peak {
+            self.scan_true_peak(input);
+        }
+
+        if hold {
+            // The held value owns the peak; true_max restarts every block
+            let block_peak = (min as i32).abs().max((max as i32).abs());
+            self.held = self.held.max(block_peak).max(self.true_max);
+            self.true_max = 0;
+        }
     }
 }
 
@@ -247,4 +365,110 @@ mod tests {
         // This is expected sentinel behavior — user should check available() first
         assert!(!peak.available());
     }
+
+    /// A block of fs/4 sine at 45° phase: every sample lands at
+    /// ±0.707 of the waveform's true amplitude.
+    fn quarter_rate_block(amplitude: f32) -> AudioBlockRef {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        for (i, s) in block.iter_mut().enumerate() {
+            let phase = core::f32::consts::FRAC_PI_2 * i as f32 + core::f32::consts::FRAC_PI_4;
+            *s = libm::roundf(libm::sinf(phase) * amplitude * 32767.0) as i16;
+        }
+        block.into_shared()
+    }
+
+    #[test]
+    fn true_peak_exceeds_sample_peak() {
+        reset_pool();
+        let mut plain = AudioAnalyzePeak::new();
+        let mut tp = AudioAnalyzePeak::new();
+        tp.true_peak(true);
+
+        for _ in 0..2 {
+            let inputs = [Some(quarter_rate_block(0.9))];
+            plain.update(&inputs, &mut []);
+            tp.update(&inputs, &mut []);
+        }
+
+        let sample_peak = plain.read();
+        let true_peak = tp.read();
+        assert!((sample_peak - 0.9 * core::f32::consts::FRAC_1_SQRT_2).abs() < 0.01);
+        assert!(true_peak > sample_peak * 1.2, "true {} vs sample {}", true_peak, sample_peak);
+        assert!((true_peak - 0.9).abs() < 0.05, "true peak {}", true_peak);
+    }
+
+    #[test]
+    fn true_peak_reports_overs_above_full_scale() {
+        reset_pool();
+        let mut tp = AudioAnalyzePeak::new();
+        tp.true_peak(true);
+        // Samples at ±0.92 of full scale, waveform peaks at ~1.3
+        for _ in 0..2 {
+            tp.update(&[Some(quarter_rate_block(1.3))], &mut []);
+        }
+        assert!(tp.read() > 1.0);
+    }
+
+    #[test]
+    fn peek_does_not_reset() {
+        reset_pool();
+        let mut peak = AudioAnalyzePeak::new();
+        let mut input = alloc_block_with(&[0, -12000, 9000]);
+        input[100] = 3000;
+        peak.update(&[Some(input.into_shared())], &mut []);
+
+        let peeked = peak.peek();
+        assert_eq!(peak.peek(), peeked);
+        assert!(peak.available(), "peek leaves available() set");
+        assert_eq!(peak.read(), peeked);
+        assert!((peeked - 12000.0 / 32767.0).abs() < 0.001);
+        assert!(!peak.available());
+    }
+
+    #[test]
+    fn peak_hold_decays_over_silence() {
+        reset_pool();
+        let mut peak = AudioAnalyzePeak::new();
+        peak.peak_hold(0.1);
+
+        peak.update(&[Some(alloc_block_with(&[0, 16384]).into_shared())], &mut []);
+        assert!((peak.read() - 0.5).abs() < 0.001);
+
+        // Silent blocks (and missing input) bring it down step by step
+        let mut prev = peak.read();
+        for n in 0..3 {
+            let input = if n == 1 {
+                None
+            } else {
+                Some(alloc_block_with(&[]).into_shared())
+            };
+            peak.update(&[input], &mut []);
+            assert!(peak.available());
+            let level = peak.read();
+            assert!((prev - level - 0.1).abs() < 0.001, "{} -> {}", prev, level);
+            prev = level;
+        }
+
+        // Down to zero, never below
+        for _ in 0..5 {
+            peak.update(&[None], &mut []);
+        }
+        assert_eq!(peak.read(), 0.0);
+
+        // A louder block raises it again
+        peak.update(&[Some(alloc_block_with(&[-8192]).into_shared())], &mut []);
+        assert!((peak.peek() - 0.25).abs() < 0.001);
+    }
+
+    #[test]
+    fn peak_hold_off_restores_reset_on_read() {
+        reset_pool();
+        let mut peak = AudioAnalyzePeak::new();
+        peak.peak_hold(0.01);
+        peak.peak_hold(0.0);
+        peak.update(&[Some(alloc_block_with(&[20000]).into_shared())], &mut []);
+        let _ = peak.read();
+        peak.update(&[Some(alloc_block_with(&[1000]).into_shared())], &mut []);
+        assert!((peak.read() - 1000.0 / 32767.0).abs() < 0.001);
+    }
 }
@@ -1,10 +1,11 @@
 //! RMS (root-mean-square) level meter.
 //!
 //! Port of `TeensyAudio/analyze_rms.cpp`. Computes the RMS level over
-//! one or more block periods.
+//! one or more block periods, or as an exponential running average.
 
 use crate::block::{AudioBlockMut, AudioBlockRef};
 use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::dsp::block_sum_squares;
 use crate::node::AudioNode;
 
 /// RMS level meter. Analyzer node: 1 input, 0 outputs.
@@ -12,9 +13,15 @@ use crate::node::AudioNode;
 /// Accumulates sum-of-squares over one or more blocks, then computes
 /// `sqrt(mean_square) / 32767` on `read()`.
 ///
+/// With [`averaging()`](Self::averaging) set above 1 block, each block's
+/// mean square is instead folded into an exponential average with a time
+/// constant of that many blocks, and `read()` reports the smoothed level
+/// without resetting it.
+///
 /// # Example
 /// ```ignore
 /// let mut rms = AudioAnalyzeRms::new();
+/// rms.averaging(8); // smooth over ~8 blocks (~23 ms)
 /// // ... after processing ...
 /// if rms.available() {
 ///     let level = rms.read(); // 0.0–1.0
@@ -27,6 +34,10 @@ pub struct AudioAnalyzeRms {
     count: u32,
     /// Whether new data is available since last read.
     new_output: bool,
+    /// Averaging time constant in blocks; 1 = instantaneous.
+    averaging: u16,
+    /// Exponentially averaged mean square, normalized to full scale.
+    avg_mean_sq: f32,
 }
 
 impl AudioAnalyzeRms {
@@ -36,9 +47,25 @@ impl AudioAnalyzeRms {
             accum: 0,
             count: 0,
             new_output: false,
+            averaging: 1,
+            avg_mean_sq: 0.0,
         }
     }
 
+    /// Set the averaging time constant in blocks.
+    ///
+    /// `1` (the default) reports the RMS of the blocks since the last
+    /// `read()`. Larger values report an exponential running average that
+    /// persists across reads. `0` is treated as 1. Changing the setting
+    /// clears the accumulated state.
+    pub fn averaging(&mut self, blocks: u16) {
+        self.averaging = blocks.max(1);
+        self.accum = 0;
+        self.count = 0;
+        self.avg_mean_sq = 0.0;
+        self.new_output = false;
+    }
+
     /// Returns `true` if new data has been accumulated since the last `read()`.
     pub fn available(&self) -> bool {
         self.new_output
@@ -46,8 +73,14 @@ impl AudioAnalyzeRms {
 
     /// Read the RMS level (0.0–1.0) and reset the accumulator.
     ///
-    /// If no samples have been accumulated, returns 0.0.
+    /// If no samples have been accumulated, returns 0.0. In averaging mode
+    /// this returns the running average, which is not reset.
     pub fn read(&mut self) -> f32 {
+        if self.averaging > 1 {
+            self.new_output = false;
+            return libm::sqrtf(self.avg_mean_sq);
+        }
+
         let sum = self.accum;
         let num = self.count;
         self.accum = 0;
@@ -67,20 +100,25 @@ impl AudioAnalyzeRms {
 impl AudioNode for AudioAnalyzeRms {
     const NUM_INPUTS: usize = 1;
     const NUM_OUTPUTS: usize = 0;
+    const NAME: &'static str = "AudioAnalyzeRms";
 
     fn update(
         &mut self,
         inputs: &[Option<AudioBlockRef>],
         _outputs: &mut [Option<AudioBlockMut>],
     ) {
+        if self.averaging > 1 {
+            // No input: a silent block pulls the average towards zero
+            let sum = inputs[0].as_ref().map_or(0, |input| block_sum_squares(input));
+            let mean_sq = sum as f32 / (AUDIO_BLOCK_SAMPLES as f32 * 32767.0 * 32767.0);
+            self.avg_mean_sq += (mean_sq - self.avg_mean_sq) / self.averaging as f32;
+            self.new_output = true;
+            return;
+        }
+
         match inputs[0] {
             Some(ref input) => {
-                let mut sum = self.accum;
-                for i in 0..AUDIO_BLOCK_SAMPLES {
-                    let s = input[i] as i64;
-                    sum += (s * s) as u64;
-                }
-                self.accum = sum;
+                self.accum = self.accum.saturating_add(block_sum_squares(input));
                 self.count += AUDIO_BLOCK_SAMPLES as u32;
                 self.new_output = true;
             }
@@ -216,4 +254,73 @@ mod tests {
         let level = rms.read();
         assert_eq!(level, 0.0);
     }
+
+    /// Feed one block of constant `value`.
+    fn feed_dc(rms: &mut AudioAnalyzeRms, value: i16) {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block.fill(value);
+        let inputs = [Some(block.into_shared())];
+        let mut outputs: [Option<AudioBlockMut>; 0] = [];
+        rms.update(&inputs, &mut outputs);
+    }
+
+    #[test]
+    fn rms_averaging_converges_on_steady_dc() {
+        reset_pool();
+        let mut instant = AudioAnalyzeRms::new();
+        let mut averaged = AudioAnalyzeRms::new();
+        averaged.averaging(8);
+        let expected = 16384.0 / 32767.0;
+
+        // One block in: instantaneous is already there, the average lags
+        feed_dc(&mut instant, 16384);
+        feed_dc(&mut averaged, 16384);
+        assert!((instant.read() - expected).abs() < 0.001);
+        let first = averaged.read();
+        assert!(first < expected * 0.5, "average should lag, got {}", first);
+
+        let mut prev = first;
+        for _ in 0..100 {
+            feed_dc(&mut averaged, 16384);
+            let level = averaged.read();
+            assert!(level >= prev, "average should rise monotonically");
+            prev = level;
+        }
+        assert!((prev - expected).abs() < 0.001, "expected ~{}, got {}", expected, prev);
+    }
+
+    #[test]
+    fn rms_averaging_persists_across_reads() {
+        reset_pool();
+        let mut rms = AudioAnalyzeRms::new();
+        rms.averaging(4);
+        for _ in 0..10 {
+            feed_dc(&mut rms, 32767);
+        }
+
+        let level = rms.read();
+        assert!(!rms.available());
+        assert_eq!(rms.read(), level, "read must not reset the average");
+
+        // Silence decays it by 1/4 of the mean square per block
+        rms.update(&[None], &mut []);
+        assert!(rms.available());
+        let decayed = rms.read();
+        let expected = libm::sqrtf(level * level * 0.75);
+        assert!((decayed - expected).abs() < 0.001, "expected ~{}, got {}", expected, decayed);
+    }
+
+    #[test]
+    fn rms_averaging_one_is_instantaneous() {
+        reset_pool();
+        let mut rms = AudioAnalyzeRms::new();
+        rms.averaging(8);
+        feed_dc(&mut rms, 32767);
+        rms.averaging(0);
+        assert_eq!(rms.read(), 0.0, "changing mode clears state");
+
+        feed_dc(&mut rms, 16384);
+        assert!((rms.read() - 16384.0 / 32767.0).abs() < 0.001);
+        assert_eq!(rms.read(), 0.0, "instantaneous read resets");
+    }
 }
@@ -0,0 +1,246 @@
+//! Single-frequency tone detector (Goertzel algorithm).
+//!
+//! Port of `TeensyAudio/analyze_tonedetect.cpp`. Measures the level of one
+//! frequency over a window of a whole number of its cycles, which is far
+//! cheaper than a full FFT when only a few tones matter (DTMF, pilot tones).
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::{AUDIO_BLOCK_SAMPLES, AUDIO_SAMPLE_RATE_EXACT};
+use crate::node::AudioNode;
+
+/// Goertzel tone detector. Analyzer node: 1 input, 0 outputs.
+///
+/// After each window of `cycles` periods of the target frequency the
+/// magnitude is latched: a full-scale sine at exactly the target reads 1.0,
+/// other frequencies read progressively less. Longer windows give a
+/// narrower detection bandwidth but a slower response. A missing input
+/// block counts as silence.
+///
+/// # Example
+/// ```ignore
+/// let mut tone = AudioAnalyzeToneDetect::new();
+/// tone.frequency(697.0, 20);
+/// tone.threshold(0.3);
+/// // ... after processing ...
+/// if tone.available() && tone.detected() {
+///     let level = tone.read();
+/// }
+/// ```
+pub struct AudioAnalyzeToneDetect {
+    /// `2·cos(2π·f/fs)` in Q30.
+    coefficient: i64,
+    /// Goertzel state `s[n-1]`, `s[n-2]`.
+    q1: i64,
+    q2: i64,
+    /// Window length in samples (0 = disabled).
+    length: u32,
+    /// Samples remaining in the current window.
+    count: u32,
+    /// Latched magnitude of the last complete window, 0.0–1.0.
+    value: f32,
+    /// Level for [`detected()`](Self::detected).
+    threshold: f32,
+    new_output: bool,
+}
+
+impl AudioAnalyzeToneDetect {
+    /// Create a disabled detector. Call [`frequency()`](Self::frequency)
+    /// to start it.
+    pub const fn new() -> Self {
+        AudioAnalyzeToneDetect {
+            coefficient: 0,
+            q1: 0,
+            q2: 0,
+            length: 0,
+            count: 0,
+            value: 0.0,
+            threshold: 0.5,
+            new_output: false,
+        }
+    }
+
+    /// Detect `target_hz`, integrating over `cycles` periods of it.
+    ///
+    /// Restarts the current window. Frequencies outside 0 < f < Nyquist
+    /// disable the detector.
+    pub fn frequency(&mut self, target_hz: f32, cycles: u16) {
+        self.q1 = 0;
+        self.q2 = 0;
+        if !(target_hz > 0.0 && target_hz < AUDIO_SAMPLE_RATE_EXACT / 2.0) || cycles == 0 {
+            self.length = 0;
+            self.count = 0;
+            return;
+        }
+        let omega =
+            target_hz as f64 * (2.0 * core::f64::consts::PI / AUDIO_SAMPLE_RATE_EXACT as f64);
+        self.coefficient = libm::round(2.0 * libm::cos(omega) * (1u64 << 30) as f64) as i64;
+        let length = libm::roundf(AUDIO_SAMPLE_RATE_EXACT / target_hz * cycles as f32) as u32;
+        self.length = length.max(1);
+        self.count = self.length;
+    }
+
+    /// Set the level (0.0–1.0) at or above which [`detected()`](Self::detected)
+    /// reports the tone as present.
+    pub fn threshold(&mut self, level: f32) {
+        self.threshold = level.clamp(0.0, 1.0);
+    }
+
+    /// Returns `true` if a window has completed since the last `read()`.
+    pub fn available(&self) -> bool {
+        self.new_output
+    }
+
+    /// Read the tone level (0.0–1.0) from the last complete window.
+    pub fn read(&mut self) -> f32 {
+        self.new_output = false;
+        self.value
+    }
+
+    /// Returns `true` if the last window's level reached the threshold.
+    pub fn detected(&self) -> bool {
+        self.value >= self.threshold
+    }
+
+    /// One Goertzel step.
+    ///
+    /// Near the target the state grows by about `32767 / (2·sin ω)` per
+    /// sample, reaching ~2⁴¹ over a long low-frequency window, so the
+    /// Q30 product is taken in 128 bits.
+    #[inline(always)]
+    fn step(&mut self, sample: i16) {
+        let product = ((self.coefficient as i128 * self.q1 as i128) >> 30) as i64;
+        let q0 = product - self.q2 + sample as i64;
+        self.q2 = self.q1;
+        self.q1 = q0;
+    }
+
+    /// Latch the window's magnitude and start the next window.
+    fn finish_window(&mut self) {
+        let (q1, q2) = (self.q1 as f64, self.q2 as f64);
+        let coef = self.coefficient as f64 / (1u64 << 30) as f64;
+        let power = q1 * q1 + q2 * q2 - coef * q1 * q2;
+        // A sine of amplitude A over N samples gives |X| = A·N/2
+        let magnitude = libm::sqrt(power.max(0.0)) * 2.0 / (self.length as f64 * 32767.0);
+        self.value = (magnitude as f32).min(1.0);
+        self.new_output = true;
+        self.q1 = 0;
+        self.q2 = 0;
+        self.count = self.length;
+    }
+}
+
+impl Default for AudioAnalyzeToneDetect {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioAnalyzeToneDetect {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = 0;
+    const NAME: &'static str = "AudioAnalyzeToneDetect";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], _outputs: &mut [Option<AudioBlockMut>]) {
+        if self.length == 0 {
+            return;
+        }
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            let sample = inputs[0].as_ref().map_or(0, |b| b[i]);
+            self.step(sample);
+            self.count -= 1;
+            if self.count == 0 {
+                self.finish_window();
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::nodes::AudioSynthSine;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Feed `blocks` blocks of a sine at `hz` and return the last level.
+    fn detect(tone: &mut AudioAnalyzeToneDetect, hz: f32, amplitude: f32, blocks: usize) -> f32 {
+        let mut sine = AudioSynthSine::new();
+        sine.frequency(hz);
+        sine.amplitude(amplitude);
+        for _ in 0..blocks {
+            let mut src = [AudioBlockMut::alloc()];
+            sine.update(&[], &mut src);
+            let inputs = [src[0].take().map(|b| b.into_shared())];
+            tone.update(&inputs, &mut []);
+        }
+        assert!(tone.available());
+        tone.read()
+    }
+
+    #[test]
+    fn tone_detect_matching_sine_reads_near_one() {
+        reset_pool();
+        let mut tone = AudioAnalyzeToneDetect::new();
+        tone.frequency(1000.0, 20);
+        let level = detect(&mut tone, 1000.0, 1.0, 20);
+        assert!((level - 1.0).abs() < 0.05, "level = {}", level);
+        assert!(tone.detected());
+        assert!(!tone.available());
+    }
+
+    #[test]
+    fn tone_detect_scales_with_amplitude() {
+        reset_pool();
+        let mut tone = AudioAnalyzeToneDetect::new();
+        tone.frequency(440.0, 10);
+        let level = detect(&mut tone, 440.0, 0.25, 20);
+        assert!((level - 0.25).abs() < 0.02, "level = {}", level);
+        tone.threshold(0.5);
+        assert!(!tone.detected());
+    }
+
+    #[test]
+    fn tone_detect_far_frequency_reads_near_zero() {
+        reset_pool();
+        let mut tone = AudioAnalyzeToneDetect::new();
+        tone.frequency(1000.0, 20);
+        let level = detect(&mut tone, 3000.0, 1.0, 20);
+        assert!(level < 0.05, "level = {}", level);
+        assert!(!tone.detected());
+    }
+
+    #[test]
+    fn tone_detect_long_low_window_does_not_overflow() {
+        reset_pool();
+        let mut tone = AudioAnalyzeToneDetect::new();
+        // 200 cycles of 20 Hz: a 10 s window of ~441 000 samples
+        tone.frequency(20.0, 200);
+        let blocks = tone.length as usize / AUDIO_BLOCK_SAMPLES + 1;
+        let level = detect(&mut tone, 20.0, 1.0, blocks);
+        assert!((level - 1.0).abs() < 0.05, "level = {}", level);
+    }
+
+    #[test]
+    fn tone_detect_silence_reads_zero() {
+        let mut tone = AudioAnalyzeToneDetect::new();
+        tone.frequency(1000.0, 4);
+        for _ in 0..4 {
+            tone.update(&[None], &mut []);
+        }
+        assert!(tone.available());
+        assert_eq!(tone.read(), 0.0);
+    }
+
+    #[test]
+    fn tone_detect_disabled_until_frequency_set() {
+        let mut tone = AudioAnalyzeToneDetect::new();
+        tone.update(&[None], &mut []);
+        assert!(!tone.available());
+        tone.frequency(30000.0, 10);
+        tone.update(&[None], &mut []);
+        assert!(!tone.available());
+    }
+}
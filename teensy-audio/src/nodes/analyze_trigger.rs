@@ -0,0 +1,197 @@
+//! Triggered waveform capture.
+//!
+//! Like a scope's edge trigger: waits for the signal to rise through a
+//! level, then records a fixed number of samples from that point, so
+//! successive captures of a periodic signal line up on a display.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::node::AudioNode;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum TriggerState {
+    /// Waiting for a rising edge through the trigger level.
+    Armed,
+    /// Recording; the value is the number of samples captured so far.
+    Capturing(usize),
+    /// Capture complete, waiting for `copy_waveform()`.
+    Ready,
+}
+
+/// Edge-triggered capture. Analyzer node: 1 input, 0 outputs.
+///
+/// `N` (at least 1) is the capture length in samples. While armed, the
+/// first sample above the trigger level (default 0.0) that follows a
+/// sample at or below it starts a capture of `N` samples, which may span
+/// several blocks. The last sample of each block is remembered, so an edge
+/// falling between two blocks is still seen. A missing input block counts
+/// as silence.
+///
+/// [`copy_waveform()`](Self::copy_waveform) hands over a finished capture
+/// and re-arms the trigger.
+///
+/// # Example
+/// ```ignore
+/// // In the graph: trig: AudioAnalyzeTrigger<256> { (synth, 0) },
+/// // In the display task:
+/// let mut wave = [0i16; 256];
+/// if graph.trig.available() {
+///     graph.trig.copy_waveform(&mut wave);
+///     draw_waveform(&wave);
+/// }
+/// ```
+pub struct AudioAnalyzeTrigger<const N: usize> {
+    buffer: [i16; N],
+    /// Trigger level.
+    level: i16,
+    /// Last sample seen, carried across blocks for edge detection.
+    prev: i16,
+    state: TriggerState,
+}
+
+impl<const N: usize> AudioAnalyzeTrigger<N> {
+    /// There must be room for at least one sample.
+    const CHECK_SIZE: () = assert!(N > 0, "AudioAnalyzeTrigger: N must be at least 1");
+
+    /// Create a new trigger, armed at level 0.0.
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::CHECK_SIZE;
+        AudioAnalyzeTrigger {
+            buffer: [0; N],
+            level: 0,
+            prev: 0,
+            state: TriggerState::Armed,
+        }
+    }
+
+    /// Set the trigger level (−1.0 to 1.0, clamped).
+    pub fn threshold(&mut self, level: f32) {
+        self.level = (level.clamp(-1.0, 1.0) * 32767.0) as i16;
+    }
+
+    /// Returns `true` if a capture is complete and waiting to be copied.
+    pub fn available(&self) -> bool {
+        self.state == TriggerState::Ready
+    }
+
+    /// Copy the finished capture into `dest` and re-arm the trigger.
+    ///
+    /// Copies up to `N` samples and returns how many were copied; returns
+    /// 0 and leaves `dest` alone if no capture is complete.
+    pub fn copy_waveform(&mut self, dest: &mut [i16]) -> usize {
+        if self.state != TriggerState::Ready {
+            return 0;
+        }
+        let len = dest.len().min(N);
+        dest[..len].copy_from_slice(&self.buffer[..len]);
+        self.state = TriggerState::Armed;
+        len
+    }
+}
+
+impl<const N: usize> Default for AudioAnalyzeTrigger<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> AudioNode for AudioAnalyzeTrigger<N> {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = 0;
+    const NAME: &'static str = "AudioAnalyzeTrigger";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], _outputs: &mut [Option<AudioBlockMut>]) {
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            let sample = inputs[0].as_ref().map_or(0, |b| b[i]);
+            if self.state == TriggerState::Armed && self.prev <= self.level && sample > self.level {
+                self.state = TriggerState::Capturing(0);
+            }
+            if let TriggerState::Capturing(n) = self.state {
+                self.buffer[n] = sample;
+                self.state = if n + 1 >= N {
+                    TriggerState::Ready
+                } else {
+                    TriggerState::Capturing(n + 1)
+                };
+            }
+            self.prev = sample;
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::constants::AUDIO_SAMPLE_RATE_EXACT;
+    use crate::nodes::AudioSynthSine;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    fn feed(trig: &mut AudioAnalyzeTrigger<256>, sine: &mut AudioSynthSine) {
+        let mut src = [AudioBlockMut::alloc()];
+        sine.update(&[], &mut src);
+        let inputs = [src[0].take().map(|b| b.into_shared())];
+        trig.update(&inputs, &mut []);
+    }
+
+    #[test]
+    fn captures_start_at_rising_zero_crossing() {
+        reset_pool();
+        let mut trig = AudioAnalyzeTrigger::<256>::new();
+        let mut sine = AudioSynthSine::new();
+        // Not a divisor of the block rate, so edges land all over the block
+        sine.frequency(437.0);
+        sine.amplitude(0.8);
+        sine.phase(200.0);
+
+        // Largest step between samples of this sine, ~1630
+        let max_step = (0.8 * 32767.0 * 2.0 * core::f32::consts::PI * 437.0
+            / AUDIO_SAMPLE_RATE_EXACT) as i16
+            + 1;
+        let mut wave = [0i16; 256];
+        let mut captures = 0;
+        for _ in 0..40 {
+            feed(&mut trig, &mut sine);
+            if trig.available() {
+                assert_eq!(trig.copy_waveform(&mut wave), 256);
+                assert!(!trig.available());
+                assert!(wave[0] > 0 && wave[0] <= max_step, "starts at {}", wave[0]);
+                assert!(wave[1] > wave[0], "must be rising");
+                captures += 1;
+            }
+        }
+        assert!(captures >= 10, "only {} captures", captures);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn threshold_and_silence() {
+        reset_pool();
+        let mut trig = AudioAnalyzeTrigger::<256>::new();
+        for _ in 0..4 {
+            trig.update(&[None], &mut []);
+        }
+        assert!(!trig.available(), "silence never triggers");
+        let mut wave = [0i16; 4];
+        assert_eq!(trig.copy_waveform(&mut wave), 0);
+
+        trig.threshold(0.5);
+        let mut sine = AudioSynthSine::new();
+        sine.frequency(437.0);
+        sine.amplitude(0.8);
+        while !trig.available() {
+            feed(&mut trig, &mut sine);
+        }
+        assert_eq!(trig.copy_waveform(&mut wave), 4);
+        assert!(
+            wave[0] > 16383 && wave[0] < 16383 + 1700,
+            "starts at {}",
+            wave[0]
+        );
+        assert!(wave[1] > wave[0]);
+    }
+}
@@ -0,0 +1,285 @@
+//! Stereo chorus from a mono source.
+//!
+//! A single delay line is read by two taps whose delay is swept by the same
+//! triangle LFO at different phases. Each output mixes the dry signal with
+//! its own swept tap, so the left and right pitch wobble is out of step and
+//! the result spreads across the stereo field.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::{ms_to_samples, AUDIO_BLOCK_SAMPLES, AUDIO_SAMPLE_RATE_EXACT};
+use crate::node::AudioNode;
+
+/// Delay-line length in samples (~46 ms). Base delay plus depth is limited
+/// to this minus two samples.
+pub const CHORUS_BUFFER_SAMPLES: usize = 2048;
+
+/// Longest base + depth delay, in samples.
+const MAX_SWEEP_SAMPLES: f32 = (CHORUS_BUFFER_SAMPLES - 2) as f32;
+
+/// Stereo chorus. Effect node: 1 input, 2 outputs (left, right).
+///
+/// Each output is `(dry + tap) / 2`, where the tap delay moves between
+/// `delay - depth` and `delay + depth` at the LFO rate. The right tap's LFO
+/// leads the left by [`stereo_phase()`](Self::stereo_phase) (90° by
+/// default). With zero depth both taps sit at the same delay and the
+/// outputs are identical.
+///
+/// # Example
+/// ```ignore
+/// let mut chorus = AudioEffectChorusStereo::new();
+/// chorus.delay(15.0);
+/// chorus.depth(3.0);
+/// chorus.rate(0.8);
+/// ```
+pub struct AudioEffectChorusStereo {
+    buffer: [i16; CHORUS_BUFFER_SAMPLES],
+    /// Index of the most recently written sample.
+    write_index: usize,
+    /// Centre delay in samples, Q16.16.
+    delay_q16: i32,
+    /// Sweep half-range in samples, Q16.16.
+    depth_q16: i32,
+    /// LFO phase (left channel).
+    lfo_phase: u32,
+    /// LFO phase increment per sample.
+    lfo_increment: u32,
+    /// Right-channel LFO phase offset.
+    phase_offset: u32,
+}
+
+impl AudioEffectChorusStereo {
+    /// Create a new chorus: 15 ms delay, 2 ms depth, 0.5 Hz, 90° spread.
+    pub fn new() -> Self {
+        let mut chorus = AudioEffectChorusStereo {
+            buffer: [0; CHORUS_BUFFER_SAMPLES],
+            write_index: 0,
+            delay_q16: 0,
+            depth_q16: 0,
+            lfo_phase: 0,
+            lfo_increment: 0,
+            phase_offset: 0x4000_0000,
+        };
+        chorus.delay(15.0);
+        chorus.depth(2.0);
+        chorus.rate(0.5);
+        chorus
+    }
+
+    /// Set the centre delay in milliseconds.
+    pub fn delay(&mut self, milliseconds: f32) {
+        self.delay_q16 = ms_to_q16(milliseconds);
+        self.clamp_sweep();
+    }
+
+    /// Set the sweep depth (± around the centre delay) in milliseconds.
+    /// Zero disables modulation.
+    pub fn depth(&mut self, milliseconds: f32) {
+        self.depth_q16 = ms_to_q16(milliseconds);
+        self.clamp_sweep();
+    }
+
+    /// Set the LFO rate in Hz.
+    pub fn rate(&mut self, hz: f32) {
+        let hz = hz.clamp(0.0, 20.0);
+        self.lfo_increment = (hz * (4_294_967_296.0 / AUDIO_SAMPLE_RATE_EXACT)) as u32;
+    }
+
+    /// Set how far the right channel's LFO leads the left, in degrees.
+    /// 0° gives a mono chorus; 90°–180° gives the widest image.
+    pub fn stereo_phase(&mut self, degrees: f32) {
+        let turns = libm::fmodf(degrees, 360.0) / 360.0;
+        let turns = if turns < 0.0 { turns + 1.0 } else { turns };
+        self.phase_offset = (turns * 4_294_967_296.0) as u64 as u32;
+    }
+
+    /// Keep `delay ± depth` within the buffer.
+    fn clamp_sweep(&mut self) {
+        let max = (MAX_SWEEP_SAMPLES * 65536.0) as i32;
+        self.depth_q16 = self.depth_q16.min(max / 2);
+        self.delay_q16 = self.delay_q16.clamp(self.depth_q16, max - self.depth_q16);
+    }
+
+    /// Read the line `delay_q16` samples behind the newest sample, with
+    /// linear interpolation.
+    #[inline(always)]
+    fn read(&self, delay_q16: i32) -> i32 {
+        let whole = (delay_q16 >> 16) as usize;
+        let frac = delay_q16 & 0xFFFF;
+        let i1 = (self.write_index + CHORUS_BUFFER_SAMPLES - whole) % CHORUS_BUFFER_SAMPLES;
+        let i2 = (i1 + CHORUS_BUFFER_SAMPLES - 1) % CHORUS_BUFFER_SAMPLES;
+        let s1 = self.buffer[i1] as i32;
+        let s2 = self.buffer[i2] as i32;
+        s1 + (((s2 - s1) * frac) >> 16)
+    }
+
+    /// Tap delay for LFO phase `phase`.
+    #[inline(always)]
+    fn tap_delay(&self, phase: u32) -> i32 {
+        self.delay_q16 + ((self.depth_q16 as i64 * triangle_q15(phase) as i64) >> 15) as i32
+    }
+}
+
+impl Default for AudioEffectChorusStereo {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Milliseconds to Q16.16 samples (negative → 0): the whole-sample count
+/// of a duration 65536 times as long.
+fn ms_to_q16(milliseconds: f32) -> i32 {
+    let max = (MAX_SWEEP_SAMPLES * 65536.0) as u32;
+    ms_to_samples(milliseconds * 65536.0).min(max) as i32
+}
+
+/// Triangle wave in Q15 (-32768 … 32767) from a 32-bit phase.
+#[inline(always)]
+fn triangle_q15(phase: u32) -> i32 {
+    let p = (phase >> 16) as i32;
+    if p < 32768 {
+        p * 2 - 32768
+    } else {
+        (65535 - p) * 2 - 32767
+    }
+}
+
+impl AudioNode for AudioEffectChorusStereo {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = 2;
+    const NAME: &'static str = "AudioEffectChorusStereo";
+    const OUTPUT_NAMES: &'static [&'static str] = &["left", "right"];
+
+    fn update(
+        &mut self,
+        inputs: &[Option<AudioBlockRef>],
+        outputs: &mut [Option<AudioBlockMut>],
+    ) {
+        let (mut left, mut right) = match (outputs[0].take(), outputs[1].take()) {
+            (Some(l), Some(r)) => (l, r),
+            // Need both outputs; whichever was allocated drops here
+            _ => return,
+        };
+
+        // No input feeds silence so the delayed tail still plays out
+        let input = inputs[0].as_ref();
+        let mut phase = self.lfo_phase;
+
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            let dry = input.map_or(0, |b| b[i]);
+            self.write_index = (self.write_index + 1) % CHORUS_BUFFER_SAMPLES;
+            self.buffer[self.write_index] = dry;
+
+            let wet_l = self.read(self.tap_delay(phase));
+            let wet_r = self.read(self.tap_delay(phase.wrapping_add(self.phase_offset)));
+            left[i] = ((dry as i32 + wet_l) >> 1) as i16;
+            right[i] = ((dry as i32 + wet_r) >> 1) as i16;
+
+            phase = phase.wrapping_add(self.lfo_increment);
+        }
+
+        self.lfo_phase = phase;
+        outputs[0] = Some(left);
+        outputs[1] = Some(right);
+    }
+
+    fn reset(&mut self) {
+        self.buffer.fill(0);
+        self.write_index = 0;
+        self.lfo_phase = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::nodes::AudioSynthSine;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Run `blocks` blocks of a 440 Hz sine through `chorus`, returning the
+    /// summed squared left/right difference and the summed squared left output
+    /// over the last half.
+    fn run(chorus: &mut AudioEffectChorusStereo, blocks: usize) -> (i64, i64) {
+        let mut sine = AudioSynthSine::new();
+        sine.frequency(440.0);
+        sine.amplitude(0.8);
+        let (mut diff, mut level) = (0i64, 0i64);
+        for n in 0..blocks {
+            let mut src = [AudioBlockMut::alloc()];
+            sine.update(&[], &mut src);
+            let inputs = [src[0].take().map(|b| b.into_shared())];
+            let mut outputs = [AudioBlockMut::alloc(), AudioBlockMut::alloc()];
+            chorus.update(&inputs, &mut outputs);
+            if n < blocks / 2 {
+                continue;
+            }
+            let (l, r) = (outputs[0].as_ref().unwrap(), outputs[1].as_ref().unwrap());
+            for i in 0..AUDIO_BLOCK_SAMPLES {
+                let d = l[i] as i64 - r[i] as i64;
+                diff += d * d;
+                level += l[i] as i64 * l[i] as i64;
+            }
+        }
+        (diff, level)
+    }
+
+    #[test]
+    fn chorus_outputs_differ_when_modulated() {
+        reset_pool();
+        let mut chorus = AudioEffectChorusStereo::new();
+        chorus.delay(10.0);
+        chorus.depth(4.0);
+        chorus.rate(2.0);
+        chorus.stereo_phase(180.0);
+
+        let (diff, level) = run(&mut chorus, 80);
+        assert!(level > 0);
+        assert!(diff > level / 20, "outputs too similar: diff {} level {}", diff, level);
+    }
+
+    #[test]
+    fn chorus_outputs_match_at_zero_depth() {
+        reset_pool();
+        let mut chorus = AudioEffectChorusStereo::new();
+        chorus.delay(10.0);
+        chorus.depth(0.0);
+        chorus.rate(2.0);
+
+        let (diff, level) = run(&mut chorus, 40);
+        assert!(level > 0);
+        assert_eq!(diff, 0);
+    }
+
+    #[test]
+    fn chorus_zero_phase_is_mono() {
+        reset_pool();
+        let mut chorus = AudioEffectChorusStereo::new();
+        chorus.depth(4.0);
+        chorus.stereo_phase(0.0);
+        let (diff, _) = run(&mut chorus, 20);
+        assert_eq!(diff, 0);
+    }
+
+    #[test]
+    fn chorus_sweep_stays_in_buffer() {
+        let mut chorus = AudioEffectChorusStereo::new();
+        chorus.depth(1000.0);
+        chorus.delay(1000.0);
+        let max = (MAX_SWEEP_SAMPLES * 65536.0) as i32;
+        for phase in [0u32, 0x4000_0000, 0x8000_0000, 0xC000_0000, u32::MAX] {
+            let d = chorus.tap_delay(phase);
+            assert!(d >= 0 && d <= max, "delay {} out of range", d >> 16);
+        }
+    }
+
+    #[test]
+    fn chorus_output_names() {
+        use crate::node::output_port_index;
+        const NAMES: &[&str] = <AudioEffectChorusStereo as AudioNode>::OUTPUT_NAMES;
+        assert_eq!(output_port_index(NAMES, "left"), 0);
+        assert_eq!(output_port_index(NAMES, "right"), 1);
+    }
+}
@@ -0,0 +1,204 @@
+//! Equal-power crossfade between two inputs.
+//!
+//! A linear crossfade dips by 6 dB in the middle when the inputs are
+//! uncorrelated. Here the gains follow a quarter cycle of `SINE_TABLE`
+//! (`cos` for input 0, `sin` for input 1), so the summed power stays
+//! constant across the fade.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::dsp::intrinsics::saturate16;
+use crate::dsp::wavetables::SINE_TABLE;
+use crate::node::AudioNode;
+
+/// Table entries in a quarter cycle of `SINE_TABLE`.
+const QUARTER: usize = 64;
+
+/// Equal-power crossfader. Effect node: 2 inputs, 1 output.
+///
+/// [`mix()`](Self::mix) goes from 0.0 (all input 0) to 1.0 (all input 1);
+/// at 0.5 both inputs pass at −3 dB. A missing input contributes nothing;
+/// with both missing the output is dropped.
+///
+/// With [`ramp()`](Self::ramp) enabled, a new mix position is reached by
+/// interpolating the gains across the next block instead of jumping, so
+/// a position changed every block sweeps without zipper noise.
+///
+/// # Example
+/// ```ignore
+/// let mut xfade = AudioEffectCrossfade::new();
+/// xfade.ramp(true);
+/// xfade.mix(0.25); // mostly input 0
+/// ```
+pub struct AudioEffectCrossfade {
+    /// Target gains (Q15) for inputs 0 and 1.
+    target: [i32; 2],
+    /// Gains (Q15) applied at the end of the previous block.
+    current: [i32; 2],
+    ramp: bool,
+}
+
+impl AudioEffectCrossfade {
+    /// Create a new crossfader passing input 0 only, ramping off.
+    pub const fn new() -> Self {
+        AudioEffectCrossfade {
+            target: [SINE_TABLE[QUARTER] as i32, 0],
+            current: [SINE_TABLE[QUARTER] as i32, 0],
+            ramp: false,
+        }
+    }
+
+    /// Set the crossfade position (0.0 = input 0, 1.0 = input 1; clamped).
+    pub fn mix(&mut self, position: f32) {
+        let x = position.clamp(0.0, 1.0) * QUARTER as f32;
+        self.target = [quarter_sine(QUARTER as f32 - x), quarter_sine(x)];
+        if !self.ramp {
+            self.current = self.target;
+        }
+    }
+
+    /// Enable or disable per-block gain ramping.
+    pub fn ramp(&mut self, enable: bool) {
+        self.ramp = enable;
+    }
+}
+
+impl Default for AudioEffectCrossfade {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `sin` over the first quarter cycle, `x` in table entries (0–64), as Q15.
+fn quarter_sine(x: f32) -> i32 {
+    let index = (x as usize).min(QUARTER - 1);
+    let frac = ((x - index as f32) * 65536.0) as i32;
+    let val1 = SINE_TABLE[index] as i32;
+    let val2 = SINE_TABLE[index + 1] as i32;
+    (val1 * (0x10000 - frac) + val2 * frac) >> 16
+}
+
+impl AudioNode for AudioEffectCrossfade {
+    const NUM_INPUTS: usize = 2;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioEffectCrossfade";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        let start = self.current;
+        let end = self.target;
+        self.current = end;
+
+        if inputs[0].is_none() && inputs[1].is_none() {
+            return;
+        }
+        let Some(mut out) = outputs[0].take() else {
+            return;
+        };
+
+        // Gains in Q15.16 so the per-sample step keeps its fraction
+        let mut gain = [start[0] << 16, start[1] << 16];
+        let step = [
+            ((end[0] - start[0]) << 16) / AUDIO_BLOCK_SAMPLES as i32,
+            ((end[1] - start[1]) << 16) / AUDIO_BLOCK_SAMPLES as i32,
+        ];
+        for (i, dst) in out.iter_mut().enumerate() {
+            gain[0] += step[0];
+            gain[1] += step[1];
+            let a = inputs[0]
+                .as_ref()
+                .map_or(0, |b| b[i] as i32 * (gain[0] >> 16));
+            let b = inputs[1]
+                .as_ref()
+                .map_or(0, |b| b[i] as i32 * (gain[1] >> 16));
+            *dst = saturate16((a + b) >> 15);
+        }
+
+        outputs[0] = Some(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::nodes::AudioSynthWaveformDc;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    fn dc(level: f32) -> AudioBlockRef {
+        let mut src = AudioSynthWaveformDc::new();
+        src.amplitude(level);
+        let mut outputs = [AudioBlockMut::alloc()];
+        src.update(&[], &mut outputs);
+        outputs[0].take().unwrap().into_shared()
+    }
+
+    /// Output for input 0 alone and input 1 alone.
+    fn gains(xfade: &mut AudioEffectCrossfade) -> (i16, i16) {
+        let mut outputs = [AudioBlockMut::alloc()];
+        xfade.update(&[Some(dc(1.0)), None], &mut outputs);
+        let a = outputs[0].take().unwrap()[0];
+        let mut outputs = [AudioBlockMut::alloc()];
+        xfade.update(&[None, Some(dc(1.0))], &mut outputs);
+        let b = outputs[0].take().unwrap()[0];
+        (a, b)
+    }
+
+    #[test]
+    fn crossfade_endpoints_select_one_input() {
+        reset_pool();
+        let mut xfade = AudioEffectCrossfade::new();
+        xfade.mix(0.0);
+        let (a, b) = gains(&mut xfade);
+        assert!(a >= 32760, "input 0 at full: {}", a);
+        assert_eq!(b, 0);
+
+        xfade.mix(1.0);
+        let (a, b) = gains(&mut xfade);
+        assert_eq!(a, 0);
+        assert!(b >= 32760, "input 1 at full: {}", b);
+    }
+
+    #[test]
+    fn crossfade_midpoint_is_equal_power() {
+        reset_pool();
+        let mut xfade = AudioEffectCrossfade::new();
+        xfade.mix(0.5);
+        let (a, b) = gains(&mut xfade);
+        assert_eq!(a, b);
+        // −3 dB: 32767 / √2
+        assert!((a as i32 - 23170).abs() <= 8, "got {}", a);
+
+        // Two equal DC inputs sum to √2, not 1
+        let mut outputs = [AudioBlockMut::alloc()];
+        xfade.update(&[Some(dc(0.5)), Some(dc(0.5))], &mut outputs);
+        let sum = outputs[0].take().unwrap()[0];
+        assert!((sum as i32 - 23170).abs() <= 8, "got {}", sum);
+    }
+
+    #[test]
+    fn crossfade_ramp_interpolates_across_block() {
+        reset_pool();
+        let mut xfade = AudioEffectCrossfade::new();
+        xfade.ramp(true);
+        xfade.mix(1.0);
+
+        let mut outputs = [AudioBlockMut::alloc()];
+        xfade.update(&[Some(dc(1.0)), None], &mut outputs);
+        let out = outputs[0].take().unwrap();
+        for pair in out.windows(2) {
+            assert!(pair[1] <= pair[0], "ramp must fall monotonically");
+        }
+        assert!(out[0] > 32000, "starts at the old gain: {}", out[0]);
+        assert!(out[AUDIO_BLOCK_SAMPLES - 1] < 300, "ends at the new gain");
+        drop(out);
+
+        // Next block holds the new gain
+        let mut outputs = [AudioBlockMut::alloc()];
+        xfade.update(&[Some(dc(1.0)), None], &mut outputs);
+        assert!(outputs[0].take().unwrap().iter().all(|&s| s == 0));
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+}
@@ -0,0 +1,404 @@
+//! Multi-tap delay line.
+//!
+//! Port of `TeensyAudio/effect_delay.cpp`. The C++ version chains
+//! pool blocks into a queue sized for the longest tap; here the history is a
+//! fixed `i16` ring owned by the node, sized by a const generic, so no pool
+//! blocks are held between updates.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::{ms_to_samples, samples_to_ms, AUDIO_BLOCK_SAMPLES};
+use crate::dsp::intrinsics::saturate16;
+use crate::dsp::music::NoteDivision;
+use crate::node::AudioNode;
+
+/// Number of delay taps (outputs).
+pub const DELAY_TAPS: usize = 8;
+
+/// What each delay tap outputs.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub enum DelayOutputMode {
+    /// Only the delayed signal: a pure delay line for parallel routing
+    /// (mix the dry path elsewhere). This is the default.
+    WetOnly,
+    /// Blend of delayed and current input for in-line use. The value is the
+    /// wet fraction, 0.0 (all dry) to 1.0 (all wet); 0.5 is an equal mix.
+    WetDry(f32),
+}
+
+/// Multi-tap delay. Effect node: 1 input, 8 outputs.
+///
+/// `MAX_SAMPLES` is the ring length. The current block is written before
+/// the taps are read, so the longest usable delay is
+/// `MAX_SAMPLES - AUDIO_BLOCK_SAMPLES` samples (see
+/// [`max_delay_ms()`](Self::max_delay_ms)); longer requests are clamped.
+/// Disabled taps produce no output block. See
+/// [`set_output_mode()`](Self::set_output_mode) for wet-only vs. blended
+/// output.
+///
+/// # Example
+/// ```ignore
+/// // ~0.5 s of history at 44.1 kHz
+/// let mut delay: AudioEffectDelay<22144> = AudioEffectDelay::new();
+/// delay.delay(0, 125.0);
+/// delay.delay(1, 250.0);
+/// ```
+pub struct AudioEffectDelay<const MAX_SAMPLES: usize> {
+    /// Sample history.
+    buffer: [i16; MAX_SAMPLES],
+    /// Index where the next input sample will be written.
+    head: usize,
+    /// Delay of each tap in samples.
+    delay_samples: [usize; DELAY_TAPS],
+    /// Bit `n` set = tap `n` enabled.
+    active_mask: u8,
+    output_mode: DelayOutputMode,
+    /// Wet and dry gains for [`DelayOutputMode::WetDry`], Q15.
+    wet_gain: i32,
+    dry_gain: i32,
+}
+
+impl<const MAX_SAMPLES: usize> AudioEffectDelay<MAX_SAMPLES> {
+    /// The ring must hold at least one block.
+    const CHECK_SIZE: () = assert!(
+        MAX_SAMPLES >= AUDIO_BLOCK_SAMPLES,
+        "AudioEffectDelay: MAX_SAMPLES must be at least AUDIO_BLOCK_SAMPLES"
+    );
+
+    /// Longest delay in samples a tap can be set to.
+    pub const MAX_DELAY_SAMPLES: usize = MAX_SAMPLES - AUDIO_BLOCK_SAMPLES;
+
+    /// Create a new delay with an empty (silent) history and all taps disabled.
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::CHECK_SIZE;
+        AudioEffectDelay {
+            buffer: [0; MAX_SAMPLES],
+            head: 0,
+            delay_samples: [0; DELAY_TAPS],
+            active_mask: 0,
+            output_mode: DelayOutputMode::WetOnly,
+            wet_gain: 32768,
+            dry_gain: 0,
+        }
+    }
+
+    /// Choose between wet-only and blended wet/dry output for all taps.
+    ///
+    /// Wet fractions outside 0.0–1.0 are clamped.
+    pub fn set_output_mode(&mut self, mode: DelayOutputMode) {
+        let wet = match mode {
+            DelayOutputMode::WetOnly => 1.0,
+            DelayOutputMode::WetDry(wet) => wet.clamp(0.0, 1.0),
+        };
+        self.output_mode = match mode {
+            DelayOutputMode::WetOnly => mode,
+            DelayOutputMode::WetDry(_) => DelayOutputMode::WetDry(wet),
+        };
+        self.wet_gain = (wet * 32768.0) as i32;
+        self.dry_gain = 32768 - self.wet_gain;
+    }
+
+    /// Current output mode.
+    pub fn output_mode(&self) -> DelayOutputMode {
+        self.output_mode
+    }
+
+    /// Set tap `channel` to `milliseconds` of delay and enable it.
+    ///
+    /// Delays beyond [`max_delay_ms()`](Self::max_delay_ms) are clamped;
+    /// negative values mean no delay. Out-of-range channels are ignored.
+    pub fn delay(&mut self, channel: usize, milliseconds: f32) {
+        if channel >= DELAY_TAPS {
+            return;
+        }
+        let samples = ms_to_samples(milliseconds) as usize;
+        self.delay_samples[channel] = samples.min(Self::MAX_DELAY_SAMPLES);
+        self.active_mask |= 1 << channel;
+    }
+
+    /// Set tap `channel` to a tempo-synced note length at `bpm`.
+    pub fn delay_sync(&mut self, channel: usize, division: NoteDivision, bpm: f32) {
+        self.delay(channel, division.milliseconds(bpm));
+    }
+
+    /// Disable tap `channel`; its output stops producing blocks.
+    pub fn disable(&mut self, channel: usize) {
+        if channel < DELAY_TAPS {
+            self.active_mask &= !(1 << channel);
+        }
+    }
+
+    /// Longest delay a tap can be set to, in milliseconds.
+    pub fn max_delay_ms(&self) -> f32 {
+        samples_to_ms(Self::MAX_DELAY_SAMPLES as u32)
+    }
+
+    /// Current delay of tap `channel` in samples, or `None` if disabled.
+    pub fn tap_samples(&self, channel: usize) -> Option<usize> {
+        if channel < DELAY_TAPS && self.active_mask & (1 << channel) != 0 {
+            Some(self.delay_samples[channel])
+        } else {
+            None
+        }
+    }
+
+    /// Append one block of input (or silence) to the ring.
+    fn write_block(&mut self, input: Option<&[i16]>) {
+        let first = (MAX_SAMPLES - self.head).min(AUDIO_BLOCK_SAMPLES);
+        let (head_part, wrap_part) = (self.head..self.head + first, 0..AUDIO_BLOCK_SAMPLES - first);
+        match input {
+            Some(src) => {
+                self.buffer[head_part].copy_from_slice(&src[..first]);
+                self.buffer[wrap_part].copy_from_slice(&src[first..]);
+            }
+            None => {
+                self.buffer[head_part].fill(0);
+                self.buffer[wrap_part].fill(0);
+            }
+        }
+        self.head = (self.head + AUDIO_BLOCK_SAMPLES) % MAX_SAMPLES;
+    }
+
+    /// Copy the block that starts `delay` samples before the block just written.
+    fn read_tap(&self, delay: usize, out: &mut [i16]) {
+        // head has already advanced past the current block
+        let back = delay + AUDIO_BLOCK_SAMPLES;
+        let start = (self.head + MAX_SAMPLES - back) % MAX_SAMPLES;
+        let first = (MAX_SAMPLES - start).min(AUDIO_BLOCK_SAMPLES);
+        out[..first].copy_from_slice(&self.buffer[start..start + first]);
+        out[first..].copy_from_slice(&self.buffer[..AUDIO_BLOCK_SAMPLES - first]);
+    }
+}
+
+impl<const MAX_SAMPLES: usize> Default for AudioEffectDelay<MAX_SAMPLES> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const MAX_SAMPLES: usize> AudioNode for AudioEffectDelay<MAX_SAMPLES> {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = DELAY_TAPS;
+    const NAME: &'static str = "AudioEffectDelay";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        // No input keeps the ring moving with silence so taps ring out
+        self.write_block(inputs[0].as_ref().map(|b| &b[..]));
+
+        for (channel, slot) in outputs.iter_mut().enumerate().take(DELAY_TAPS) {
+            let mut out = match slot.take() {
+                Some(b) => b,
+                None => continue,
+            };
+            if self.active_mask & (1 << channel) == 0 {
+                // Disabled tap: the block returns to the pool
+                continue;
+            }
+            self.read_tap(self.delay_samples[channel], &mut out[..]);
+            if let DelayOutputMode::WetDry(_) = self.output_mode {
+                for i in 0..AUDIO_BLOCK_SAMPLES {
+                    let dry = inputs[0].as_ref().map_or(0, |b| b[i] as i32);
+                    let mixed = out[i] as i32 * self.wet_gain + dry * self.dry_gain;
+                    out[i] = saturate16(mixed >> 15);
+                }
+            }
+            *slot = Some(out);
+        }
+    }
+
+    fn reset(&mut self) {
+        self.buffer.fill(0);
+        self.head = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::constants::AUDIO_SAMPLE_RATE_EXACT;
+    use crate::dsp::music::NoteValue;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Block whose samples count up from `start`.
+    fn ramp_block(start: i16) -> AudioBlockRef {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        for (i, s) in block.iter_mut().enumerate() {
+            *s = start + i as i16;
+        }
+        block.into_shared()
+    }
+
+    fn alloc_outputs() -> [Option<AudioBlockMut>; DELAY_TAPS] {
+        core::array::from_fn(|_| AudioBlockMut::alloc())
+    }
+
+    #[test]
+    fn delay_taps_read_past_samples_across_wraparound() {
+        reset_pool();
+        // 300 samples is not a multiple of the block size, so writes and
+        // reads both wrap at odd offsets
+        let mut delay = AudioEffectDelay::<300>::new();
+        delay.delay_samples[0] = 0;
+        delay.delay_samples[1] = 1;
+        delay.delay_samples[2] = 150;
+        delay.delay_samples[3] = AudioEffectDelay::<300>::MAX_DELAY_SAMPLES;
+        delay.active_mask = 0b1111;
+
+        // Input is a continuous count: sample n has value n
+        for blk in 0..10 {
+            let inputs = [Some(ramp_block((blk * AUDIO_BLOCK_SAMPLES) as i16))];
+            let mut outputs = alloc_outputs();
+            delay.update(&inputs, &mut outputs);
+
+            let now = (blk * AUDIO_BLOCK_SAMPLES) as i32;
+            for (tap, &d) in [0usize, 1, 150, 172].iter().enumerate() {
+                let out = outputs[tap].as_ref().unwrap();
+                for i in 0..AUDIO_BLOCK_SAMPLES {
+                    let n = now + i as i32 - d as i32;
+                    let expected = if n < 0 { 0 } else { n as i16 };
+                    assert_eq!(out[i], expected, "tap {} block {} sample {}", tap, blk, i);
+                }
+            }
+        }
+    }
+
+    #[test]
+    fn delay_clamps_to_max() {
+        let mut delay = AudioEffectDelay::<4410>::new();
+        let max_ms = delay.max_delay_ms();
+        assert!((max_ms - 4282.0 * 1000.0 / AUDIO_SAMPLE_RATE_EXACT).abs() < 0.01);
+
+        delay.delay(0, 10_000.0);
+        assert_eq!(
+            delay.tap_samples(0),
+            Some(AudioEffectDelay::<4410>::MAX_DELAY_SAMPLES)
+        );
+        delay.delay(1, max_ms);
+        assert_eq!(delay.tap_samples(1), Some(4282));
+        delay.delay(2, -5.0);
+        assert_eq!(delay.tap_samples(2), Some(0));
+    }
+
+    #[test]
+    fn delay_ms_converts_to_samples() {
+        let mut delay = AudioEffectDelay::<20000>::new();
+        delay.delay(0, 100.0);
+        let expected = (100.0 * AUDIO_SAMPLE_RATE_EXACT / 1000.0 + 0.5) as usize;
+        assert_eq!(delay.tap_samples(0), Some(expected));
+
+        // Dotted eighth at 120 BPM = 375 ms
+        delay.delay_sync(1, NoteDivision::Dotted(NoteValue::Eighth), 120.0);
+        let expected = (375.0 * AUDIO_SAMPLE_RATE_EXACT / 1000.0 + 0.5) as usize;
+        assert_eq!(delay.tap_samples(1), Some(expected));
+    }
+
+    #[test]
+    fn delay_disabled_taps_release_blocks() {
+        reset_pool();
+        let mut delay = AudioEffectDelay::<1024>::new();
+        delay.delay(2, 1.0);
+        delay.delay(5, 1.0);
+        delay.disable(5);
+        delay.disable(DELAY_TAPS); // ignored
+
+        let inputs = [Some(ramp_block(0))];
+        let mut outputs = alloc_outputs();
+        delay.update(&inputs, &mut outputs);
+        for (tap, out) in outputs.iter().enumerate() {
+            assert_eq!(out.is_some(), tap == 2, "tap {}", tap);
+        }
+        drop(outputs);
+        drop(inputs);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn delay_rings_out_after_input_stops() {
+        reset_pool();
+        let mut delay = AudioEffectDelay::<512>::new();
+        delay.delay(0, 0.0);
+        delay.delay_samples[0] = 256;
+
+        let mut outputs = alloc_outputs();
+        delay.update(&[Some(ramp_block(1))], &mut outputs);
+        assert!(outputs[0].as_ref().unwrap().iter().all(|&s| s == 0));
+
+        // Input gone: the stored block still arrives two blocks later
+        let mut outputs = alloc_outputs();
+        delay.update(&[None], &mut outputs);
+        let mut outputs = alloc_outputs();
+        delay.update(&[None], &mut outputs);
+        assert_eq!(outputs[0].as_ref().unwrap()[0], 1);
+        assert_eq!(outputs[0].as_ref().unwrap()[127], 128);
+    }
+
+    #[test]
+    fn delay_reset_outputs_silence() {
+        reset_pool();
+        let mut delay = AudioEffectDelay::<512>::new();
+        delay.delay(0, 0.0);
+        delay.delay_samples[0] = 128;
+
+        for blk in 0..4 {
+            let mut outputs = alloc_outputs();
+            delay.update(&[Some(ramp_block(1 + blk))], &mut outputs);
+        }
+        delay.reset();
+
+        // Without the reset the last input block would arrive now
+        let mut outputs = alloc_outputs();
+        delay.update(&[None], &mut outputs);
+        assert!(outputs[0].as_ref().unwrap().iter().all(|&s| s == 0));
+        assert_eq!(delay.tap_samples(0), Some(128), "taps kept");
+    }
+
+    #[test]
+    fn delay_wet_only_is_silent_before_echo() {
+        reset_pool();
+        let mut delay = AudioEffectDelay::<512>::new();
+        delay.delay(0, 0.0);
+        delay.delay_samples[0] = 128;
+
+        let mut outputs = alloc_outputs();
+        delay.update(&[Some(ramp_block(1))], &mut outputs);
+        assert!(outputs[0].as_ref().unwrap().iter().all(|&s| s == 0));
+
+        let mut outputs = alloc_outputs();
+        delay.update(&[None], &mut outputs);
+        assert_eq!(outputs[0].as_ref().unwrap()[0], 1);
+    }
+
+    #[test]
+    fn delay_wet_dry_passes_dry_then_echo() {
+        reset_pool();
+        let mut delay = AudioEffectDelay::<512>::new();
+        delay.delay(0, 0.0);
+        delay.delay_samples[0] = 128;
+        delay.set_output_mode(DelayOutputMode::WetDry(0.5));
+
+        // Dry signal at half level straight away
+        let mut outputs = alloc_outputs();
+        delay.update(&[Some(ramp_block(1000))], &mut outputs);
+        assert_eq!(outputs[0].as_ref().unwrap()[0], 500);
+        assert_eq!(outputs[0].as_ref().unwrap()[127], 563);
+
+        // Echo at half level one block later
+        let mut outputs = alloc_outputs();
+        delay.update(&[None], &mut outputs);
+        assert_eq!(outputs[0].as_ref().unwrap()[0], 500);
+        assert_eq!(outputs[0].as_ref().unwrap()[127], 563);
+    }
+
+    #[test]
+    fn delay_output_mode_clamps_wet_fraction() {
+        let mut delay = AudioEffectDelay::<256>::new();
+        assert_eq!(delay.output_mode(), DelayOutputMode::WetOnly);
+        delay.set_output_mode(DelayOutputMode::WetDry(1.5));
+        assert_eq!(delay.output_mode(), DelayOutputMode::WetDry(1.0));
+        assert_eq!(delay.dry_gain, 0);
+    }
+}
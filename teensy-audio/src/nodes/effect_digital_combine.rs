@@ -0,0 +1,164 @@
+//! Bitwise / modulo combination of two signals.
+//!
+//! Port of `TeensyAudio/effect_combine.cpp`. Operating on the raw `i16`
+//! sample bits rather than their values gives harsh, glitchy timbres,
+//! useful as a lo-fi effect.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::node::AudioNode;
+
+/// How [`AudioEffectDigitalCombine`] combines its two inputs.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CombineMode {
+    /// Bitwise `a | b`. This is the default.
+    Or,
+    /// Bitwise `a ^ b`.
+    Xor,
+    /// Bitwise `a & b`.
+    And,
+    /// `a + b`, wrapping on overflow.
+    ModuloAdd,
+    /// `a - b`, wrapping on overflow.
+    ModuloSubtract,
+}
+
+/// Digital combine. Effect node: 2 inputs (A, B), 1 output.
+///
+/// Each output sample is `a op b` on the raw sample bits, with no
+/// saturation. A missing input counts as silence (all zero bits); with
+/// both inputs missing the output is dropped.
+///
+/// # Example
+/// ```ignore
+/// let mut glitch = AudioEffectDigitalCombine::new();
+/// glitch.mode(CombineMode::Xor);
+/// ```
+pub struct AudioEffectDigitalCombine {
+    mode: CombineMode,
+}
+
+impl AudioEffectDigitalCombine {
+    /// Create a new combiner in [`CombineMode::Or`].
+    pub const fn new() -> Self {
+        AudioEffectDigitalCombine {
+            mode: CombineMode::Or,
+        }
+    }
+
+    /// Select the combining operation.
+    pub fn mode(&mut self, mode: CombineMode) {
+        self.mode = mode;
+    }
+}
+
+impl Default for AudioEffectDigitalCombine {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioEffectDigitalCombine {
+    const NUM_INPUTS: usize = 2;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioEffectDigitalCombine";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        let Some(mut out) = outputs[0].take() else {
+            return;
+        };
+        if inputs[0].is_none() && inputs[1].is_none() {
+            return;
+        }
+
+        let op: fn(i16, i16) -> i16 = match self.mode {
+            CombineMode::Or => |a, b| a | b,
+            CombineMode::Xor => |a, b| a ^ b,
+            CombineMode::And => |a, b| a & b,
+            CombineMode::ModuloAdd => i16::wrapping_add,
+            CombineMode::ModuloSubtract => i16::wrapping_sub,
+        };
+        for (i, dst) in out.iter_mut().enumerate() {
+            let a = inputs[0].as_ref().map_or(0, |b| b[i]);
+            let b = inputs[1].as_ref().map_or(0, |b| b[i]);
+            *dst = op(a, b);
+        }
+
+        outputs[0] = Some(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    fn block(values: [i16; 4]) -> AudioBlockRef {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        for (i, s) in block.iter_mut().enumerate() {
+            *s = values[i % 4];
+        }
+        block.into_shared()
+    }
+
+    fn combine(mode: CombineMode, a: Option<[i16; 4]>, b: Option<[i16; 4]>) -> Option<[i16; 4]> {
+        let mut node = AudioEffectDigitalCombine::new();
+        node.mode(mode);
+        let mut outputs = [AudioBlockMut::alloc()];
+        node.update(&[a.map(block), b.map(block)], &mut outputs);
+        outputs[0].take().map(|out| {
+            assert_eq!(out[4..8], out[..4], "pattern repeats across the block");
+            [out[0], out[1], out[2], out[3]]
+        })
+    }
+
+    const A: [i16; 4] = [0x0F0F, -0x8000, 0x7FFF, 0x1234];
+    const B: [i16; 4] = [0x00FF, -0x0001, 0x0001, -0x1234];
+
+    #[test]
+    fn bitwise_modes() {
+        reset_pool();
+        assert_eq!(
+            combine(CombineMode::Or, Some(A), Some(B)),
+            Some([0x0FFF, -0x0001, 0x7FFF, -0x0004])
+        );
+        assert_eq!(
+            combine(CombineMode::Xor, Some(A), Some(B)),
+            Some([0x0FF0, 0x7FFF, 0x7FFE, -0x0008])
+        );
+        assert_eq!(
+            combine(CombineMode::And, Some(A), Some(B)),
+            Some([0x000F, -0x8000, 0x0001, 0x0004])
+        );
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn modulo_modes_wrap() {
+        reset_pool();
+        assert_eq!(
+            combine(CombineMode::ModuloAdd, Some(A), Some(B)),
+            Some([0x100E, 0x7FFF, -0x8000, 0x0000])
+        );
+        assert_eq!(
+            combine(CombineMode::ModuloSubtract, Some(A), Some(B)),
+            Some([0x0E10, -0x7FFF, 0x7FFE, 0x2468])
+        );
+    }
+
+    #[test]
+    fn missing_inputs_are_silence() {
+        reset_pool();
+        assert_eq!(combine(CombineMode::Or, Some(A), None), Some(A));
+        assert_eq!(combine(CombineMode::And, None, Some(B)), Some([0; 4]));
+        assert_eq!(
+            combine(CombineMode::ModuloSubtract, None, Some(B)),
+            Some([-0x00FF, 0x0001, -0x0001, 0x1234])
+        );
+        assert_eq!(combine(CombineMode::Xor, None, None), None);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+}
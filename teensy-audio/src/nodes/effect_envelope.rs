@@ -3,15 +3,14 @@
 //! Port of `TeensyAudio/effect_envelope.cpp`. Applies an
 //! Attack-Decay-Sustain-Release (ADSR) envelope to audio input.
 //! Processes 8 samples at a time with per-sample gain interpolation.
+//! Ramps are linear by default, or curved with [`EnvelopeCurve::Exponential`].
 
 use crate::block::{AudioBlockMut, AudioBlockRef};
-use crate::constants::{AUDIO_BLOCK_SAMPLES, AUDIO_SAMPLE_RATE_EXACT};
+use crate::constants::{ms_to_samples_truncated, AUDIO_BLOCK_SAMPLES};
 use crate::dsp::intrinsics::saturate16;
+use crate::dsp::wavetables::FADER_TABLE;
 use crate::node::AudioNode;
 
-/// Samples per millisecond at the audio sample rate.
-const SAMPLES_PER_MSEC: f32 = AUDIO_SAMPLE_RATE_EXACT / 1000.0;
-
 /// Number of samples per envelope processing group.
 const SAMPLES_PER_GROUP: u32 = 8;
 
@@ -39,6 +38,17 @@ pub enum EnvelopeState {
     Forced = 7,
 }
 
+/// Shape of the envelope's attack, decay and release ramps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnvelopeCurve {
+    /// Straight-line ramps, as in the C++ library.
+    Linear,
+    /// Curved ramps: attacks start slowly and accelerate, decays and
+    /// releases fall quickly and then level out. Follows a quarter cycle of
+    /// the fader table's raised-cosine curve.
+    Exponential,
+}
+
 /// ADSR envelope effect. Shapes audio volume over time.
 ///
 /// Effect node: 1 input, 1 output.
@@ -73,6 +83,24 @@ pub struct AudioEffectEnvelope {
     sustain_mult: i32,
     release_count: u16,
     release_forced_count: u16,
+
+    /// Cycle attack→decay→release continuously until `note_off()`.
+    loop_mode: bool,
+    /// Set by `note_off()`; stops a looping envelope at the end of release.
+    note_released: bool,
+
+    /// Ramp shape for attack, decay and release.
+    curve: EnvelopeCurve,
+    /// Level at the start of the current ramp.
+    ramp_start: i32,
+    /// Level at the end of the current ramp.
+    ramp_end: i32,
+    /// Length of the current ramp, in 8-sample groups.
+    ramp_len: u16,
+
+    /// Set when release runs out and the envelope goes idle; cleared by
+    /// `just_finished()`.
+    finished: bool,
 }
 
 impl AudioEffectEnvelope {
@@ -97,6 +125,13 @@ impl AudioEffectEnvelope {
             sustain_mult: 0,
             release_count: 1,
             release_forced_count: 0,
+            loop_mode: false,
+            note_released: true,
+            curve: EnvelopeCurve::Linear,
+            ramp_start: 0,
+            ramp_end: 0,
+            ramp_len: 0,
+            finished: false,
         };
         env.delay(0.0);
         env.attack(10.5);
@@ -110,8 +145,7 @@ impl AudioEffectEnvelope {
 
     /// Convert milliseconds to count of 8-sample groups.
     fn milliseconds2count(milliseconds: f32) -> u16 {
-        let ms = if milliseconds < 0.0 { 0.0 } else { milliseconds };
-        let c = ((ms * SAMPLES_PER_MSEC) as u32 + 7) >> 3;
+        let c = ms_to_samples_truncated(milliseconds).saturating_add(7) >> 3;
         if c > 65535 { 65535 } else { c as u16 }
     }
 
@@ -155,41 +189,134 @@ impl AudioEffectEnvelope {
         self.release_forced_count = if count == 0 { 1 } else { count };
     }
 
+    /// Enable or disable looping.
+    ///
+    /// In loop mode a triggered envelope skips the sustain hold: once decay
+    /// reaches the sustain level it goes straight into release, and at the
+    /// end of release it re-triggers from delay/attack. The result is a
+    /// repeating contour usable as a complex LFO. `note_off()` lets the
+    /// current release finish and then stops at idle.
+    pub fn loop_mode(&mut self, enable: bool) {
+        self.loop_mode = enable;
+    }
+
+    /// Select linear (the default) or curved attack, decay and release
+    /// ramps. Stage times are the same either way.
+    pub fn curve(&mut self, shape: EnvelopeCurve) {
+        self.curve = shape;
+    }
+
     /// Trigger the envelope (start the attack phase).
     pub fn note_on(&mut self) {
+        self.note_released = false;
         if self.state == EnvelopeState::Idle
             || self.state == EnvelopeState::Delay
             || self.release_forced_count == 0
         {
-            self.mult_hires = 0;
-            self.count = self.delay_count;
-            if self.count > 0 {
-                self.state = EnvelopeState::Delay;
-                self.inc_hires = 0;
-            } else {
-                self.state = EnvelopeState::Attack;
-                self.count = self.attack_count;
-                self.inc_hires = UNITY_GAIN / self.count as i32;
-            }
+            self.retrigger();
         } else if self.state != EnvelopeState::Forced {
             self.state = EnvelopeState::Forced;
             self.count = self.release_forced_count;
-            self.inc_hires = (-self.mult_hires) / self.count as i32;
+            self.ramp_to(0);
         }
     }
 
     /// Release the envelope (start the release phase).
     pub fn note_off(&mut self) {
+        self.note_released = true;
         if self.state != EnvelopeState::Release
             && self.state != EnvelopeState::Idle
             && self.state != EnvelopeState::Forced
         {
             self.state = EnvelopeState::Release;
             self.count = self.release_count;
-            self.inc_hires = (-self.mult_hires) / self.count as i32;
+            self.ramp_to(0);
+        }
+    }
+
+    /// Trigger several envelopes together, e.g. the voices of a unison stack.
+    ///
+    /// Calling [`note_on()`](Self::note_on) one at a time can leave voices
+    /// out of step: one that is still sounding fades through the forced
+    /// release while idle ones start their attack immediately. Here, if any
+    /// envelope is sounding, all of them take the forced release for the
+    /// longest forced-release time in the set (silent ones simply stay at
+    /// zero); otherwise all restart at once. Either way every attack begins
+    /// on the same update.
+    pub fn note_on_all(envelopes: &mut [&mut AudioEffectEnvelope]) {
+        let sounding = envelopes
+            .iter()
+            .any(|e| e.state != EnvelopeState::Idle && e.state != EnvelopeState::Delay);
+        let forced_count = envelopes
+            .iter()
+            .map(|e| e.release_forced_count)
+            .max()
+            .unwrap_or(0);
+
+        for env in envelopes.iter_mut() {
+            env.note_released = false;
+            if sounding && forced_count > 0 {
+                env.state = EnvelopeState::Forced;
+                env.count = forced_count;
+                env.ramp_to(0);
+            } else {
+                env.retrigger();
+            }
         }
     }
 
+    /// Release several envelopes together (see [`note_on_all()`](Self::note_on_all)).
+    pub fn note_off_all(envelopes: &mut [&mut AudioEffectEnvelope]) {
+        for env in envelopes.iter_mut() {
+            env.note_off();
+        }
+    }
+
+    /// Restart from zero level at the delay (or attack) phase.
+    fn retrigger(&mut self) {
+        self.mult_hires = 0;
+        self.count = self.delay_count;
+        if self.count > 0 {
+            self.state = EnvelopeState::Delay;
+            self.inc_hires = 0;
+        } else {
+            self.state = EnvelopeState::Attack;
+            self.count = self.attack_count;
+            self.ramp_to(UNITY_GAIN);
+        }
+    }
+
+    /// Start a ramp from the current level to `target` over `self.count`
+    /// groups.
+    fn ramp_to(&mut self, target: i32) {
+        self.ramp_start = self.mult_hires;
+        self.ramp_end = target;
+        self.ramp_len = self.count;
+        self.inc_hires = (target - self.mult_hires) / self.count as i32;
+    }
+
+    /// For curved ramps, set `inc_hires` so the group about to be processed
+    /// ends on the curve.
+    fn follow_curve(&mut self) {
+        let ramping = matches!(
+            self.state,
+            EnvelopeState::Attack
+                | EnvelopeState::Decay
+                | EnvelopeState::Release
+                | EnvelopeState::Forced
+        );
+        if self.curve == EnvelopeCurve::Linear || !ramping || self.ramp_len == 0 {
+            return;
+        }
+
+        let elapsed = self.ramp_len.saturating_sub(self.count) + 1;
+        let rising = self.ramp_end > self.ramp_start;
+        let fraction = curve_fraction(elapsed, self.ramp_len, rising) as i64;
+        let span = (self.ramp_end - self.ramp_start) as i64;
+        let target = self.ramp_start + ((span * fraction) >> 15) as i32;
+        self.inc_hires = target - self.mult_hires;
+    }
+
     /// Check if the envelope is currently active (not idle).
     pub fn is_active(&self) -> bool {
         self.state != EnvelopeState::Idle
@@ -204,11 +331,27 @@ impl AudioEffectEnvelope {
     pub fn state(&self) -> EnvelopeState {
         self.state
     }
+
+    /// Returns `true` once after the release has finished and the envelope
+    /// has gone idle, then `false` until it finishes again.
+    ///
+    /// Poll after each update to free a voice on the exact block it fell
+    /// silent.
+    pub fn just_finished(&mut self) -> bool {
+        core::mem::take(&mut self.finished)
+    }
+
+    /// Current envelope level (0.0 = silent, 1.0 = full volume), e.g. for
+    /// display.
+    pub fn level(&self) -> f32 {
+        self.mult_hires as f32 / UNITY_GAIN as f32
+    }
 }
 
 impl AudioNode for AudioEffectEnvelope {
     const NUM_INPUTS: usize = 1;
     const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioEffectEnvelope";
 
     fn update(
         &mut self,
@@ -248,15 +391,20 @@ impl AudioNode for AudioEffectEnvelope {
                         } else {
                             self.state = EnvelopeState::Decay;
                             self.count = self.decay_count;
-                            self.inc_hires =
-                                (self.sustain_mult - UNITY_GAIN) / self.count as i32;
+                            self.mult_hires = UNITY_GAIN;
+                            self.ramp_to(self.sustain_mult);
                         }
                     }
                     EnvelopeState::Hold => {
                         self.state = EnvelopeState::Decay;
                         self.count = self.decay_count;
-                        self.inc_hires =
-                            (self.sustain_mult - UNITY_GAIN) / self.count as i32;
+                        self.ramp_to(self.sustain_mult);
+                    }
+                    EnvelopeState::Decay if self.loop_mode && !self.note_released => {
+                        self.state = EnvelopeState::Release;
+                        self.count = self.release_count;
+                        self.mult_hires = self.sustain_mult;
+                        self.ramp_to(0);
                     }
                     EnvelopeState::Decay => {
                         self.state = EnvelopeState::Sustain;
@@ -267,8 +415,13 @@ impl AudioNode for AudioEffectEnvelope {
                     EnvelopeState::Sustain => {
                         self.count = 0xFFFF;
                     }
+                    EnvelopeState::Release if self.loop_mode && !self.note_released => {
+                        self.retrigger();
+                    }
                     EnvelopeState::Release => {
                         self.state = EnvelopeState::Idle;
+                        self.mult_hires = 0;
+                        self.finished = true;
                         // Zero remaining output
                         if let Some(ref mut out_block) = out {
                             if let Some(ref input) = inputs[0] {
@@ -283,25 +436,17 @@ impl AudioNode for AudioEffectEnvelope {
                         return;
                     }
                     EnvelopeState::Forced => {
-                        self.mult_hires = 0;
-                        self.count = self.delay_count;
-                        if self.count > 0 {
-                            self.state = EnvelopeState::Delay;
-                            self.inc_hires = 0;
-                        } else {
-                            self.state = EnvelopeState::Attack;
-                            self.count = self.attack_count;
-                            self.inc_hires = UNITY_GAIN / self.count as i32;
-                        }
+                        self.retrigger();
                     }
                     EnvelopeState::Delay => {
                         self.state = EnvelopeState::Attack;
                         self.count = self.attack_count;
-                        self.inc_hires = UNITY_GAIN / self.count as i32;
+                        self.ramp_to(UNITY_GAIN);
                     }
                     EnvelopeState::Idle => {}
                 }
             }
+            self.follow_curve();
 
             // Process 8 samples with linearly interpolated gain
             if let (Some(ref mut out_block), Some(ref input)) = (&mut out, &inputs[0]) {
@@ -324,6 +469,43 @@ impl AudioNode for AudioEffectEnvelope {
 
         outputs[0] = out;
     }
+
+    fn reset(&mut self) {
+        self.state = EnvelopeState::Idle;
+        self.count = 0;
+        self.mult_hires = 0;
+        self.inc_hires = 0;
+        self.note_released = true;
+        self.ramp_start = 0;
+        self.ramp_end = 0;
+        self.ramp_len = 0;
+        self.finished = false;
+    }
+}
+
+/// Fraction of a curved ramp covered after `elapsed` of `len` groups, in
+/// Q15 (32768 = the whole ramp).
+///
+/// Rising ramps use the first quarter cycle of [`FADER_TABLE`] (slow start),
+/// falling ramps the second (fast start), each rescaled to 0–1.
+fn curve_fraction(elapsed: u16, len: u16, rising: bool) -> i32 {
+    if elapsed >= len {
+        return 32768;
+    }
+    // Position within the 128-entry quarter cycle, 16 fractional bits
+    let pos = ((elapsed as u64) << 23) / len as u64;
+    let offset = if rising { 0 } else { 128 };
+    let index = (pos >> 16) as usize + offset;
+    let scale = (pos & 0xFFFF) as i32;
+    let val1 = FADER_TABLE[index] as i32;
+    let val2 = FADER_TABLE[index + 1] as i32;
+    let value = (val1 * (0x10000 - scale) + val2 * scale) >> 16;
+    let mid = FADER_TABLE[128] as i32;
+    if rising {
+        value * 32768 / mid
+    } else {
+        (value - mid) * 32768 / (FADER_TABLE[256] as i32 - mid)
+    }
 }
 
 #[cfg(test)]
@@ -477,4 +659,239 @@ mod tests {
         env.note_on();
         assert_eq!(env.state(), EnvelopeState::Forced);
     }
+
+    #[test]
+    fn envelope_loop_mode_cycles() {
+        reset_pool();
+        let mut env = AudioEffectEnvelope::new();
+        env.delay(0.0);
+        env.attack(5.0);
+        env.hold(0.0);
+        env.decay(5.0);
+        env.sustain(0.5);
+        env.release(5.0);
+        env.loop_mode(true);
+        env.note_on();
+
+        // One cycle is ~15 ms (~5 blocks); 40 blocks covers several cycles.
+        // Count rising passes through 75% that follow a dip below 10%.
+        let mut peaks = 0;
+        let mut armed = true;
+        for _ in 0..40 {
+            let input = alloc_block_with_value(32767);
+            let output = AudioBlockMut::alloc().unwrap();
+            let input_ref = input.into_shared();
+            let mut outputs = [Some(output)];
+            let inputs = [Some(input_ref)];
+            env.update(&inputs, &mut outputs);
+
+            let out = outputs[0].as_ref().unwrap();
+            for &s in out.iter() {
+                if armed && s > 24000 {
+                    peaks += 1;
+                    armed = false;
+                } else if !armed && s < 3000 {
+                    armed = true;
+                }
+            }
+        }
+
+        assert!(peaks >= 5, "expected repeated cycles, saw {} peaks", peaks);
+        assert!(env.is_active());
+
+        // note_off lets the envelope finish its release and go idle.
+        env.note_off();
+        for _ in 0..10 {
+            let input = alloc_block_with_value(32767);
+            let output = AudioBlockMut::alloc().unwrap();
+            let input_ref = input.into_shared();
+            let mut outputs = [Some(output)];
+            let inputs = [Some(input_ref)];
+            env.update(&inputs, &mut outputs);
+        }
+        assert_eq!(env.state(), EnvelopeState::Idle);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    /// Run one update with a full-scale DC input and return the output.
+    fn render(env: &mut AudioEffectEnvelope) -> [i16; AUDIO_BLOCK_SAMPLES] {
+        let inputs = [Some(alloc_block_with_value(32767).into_shared())];
+        let mut outputs = [AudioBlockMut::alloc()];
+        env.update(&inputs, &mut outputs);
+        let mut samples = [0; AUDIO_BLOCK_SAMPLES];
+        if let Some(out) = outputs[0].as_ref() {
+            samples.copy_from_slice(&out[..]);
+        }
+        samples
+    }
+
+    fn unison_voice() -> AudioEffectEnvelope {
+        let mut env = AudioEffectEnvelope::new();
+        env.delay(0.0);
+        env.attack(10.0);
+        env.release_note_on(5.0);
+        env
+    }
+
+    #[test]
+    fn envelope_note_on_all_from_idle_is_aligned() {
+        reset_pool();
+        let (mut a, mut b, mut c) = (unison_voice(), unison_voice(), unison_voice());
+        AudioEffectEnvelope::note_on_all(&mut [&mut a, &mut b, &mut c]);
+        for env in [&a, &b, &c] {
+            assert_eq!(env.state(), EnvelopeState::Attack);
+        }
+
+        for _ in 0..4 {
+            let (oa, ob, oc) = (render(&mut a), render(&mut b), render(&mut c));
+            assert_eq!(oa, ob);
+            assert_eq!(oa, oc);
+        }
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn envelope_note_on_all_aligns_sounding_and_idle_voices() {
+        reset_pool();
+        let (mut a, mut b) = (unison_voice(), unison_voice());
+
+        // Voice a is still sounding from an earlier note; b is idle
+        a.note_on();
+        for _ in 0..20 {
+            render(&mut a);
+        }
+        assert!(a.is_active() && !b.is_active());
+
+        AudioEffectEnvelope::note_on_all(&mut [&mut a, &mut b]);
+        assert_eq!(a.state(), EnvelopeState::Forced);
+        assert_eq!(b.state(), EnvelopeState::Forced);
+
+        // Both leave the forced release on the same update...
+        let mut attack_seen = false;
+        for _ in 0..10 {
+            let (oa, ob) = (render(&mut a), render(&mut b));
+            assert_eq!(a.state(), b.state());
+            if a.state() == EnvelopeState::Forced {
+                assert!(ob.iter().all(|&s| s == 0), "idle voice leaked during forced release");
+            } else if attack_seen {
+                // ...and from then on produce identical ramps
+                assert_eq!(oa, ob);
+            } else {
+                attack_seen = true;
+            }
+        }
+        assert!(attack_seen);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    /// Output level at the middle of a 20 ms attack with the given curve.
+    fn attack_midpoint(curve: EnvelopeCurve) -> i16 {
+        let mut env = AudioEffectEnvelope::new();
+        env.delay(0.0);
+        env.attack(20.0); // ~110 groups, just under 7 blocks
+        env.curve(curve);
+        env.note_on();
+        let mut out = [0; AUDIO_BLOCK_SAMPLES];
+        for _ in 0..4 {
+            out = render(&mut env);
+        }
+        // Sample 440 of ~882: the middle of the attack
+        assert_eq!(env.state(), EnvelopeState::Attack);
+        out[AUDIO_BLOCK_SAMPLES * 7 / 16 - 8]
+    }
+
+    #[test]
+    fn envelope_exponential_attack_starts_slower() {
+        reset_pool();
+        let linear = attack_midpoint(EnvelopeCurve::Linear);
+        let curved = attack_midpoint(EnvelopeCurve::Exponential);
+        assert!((15000..18000).contains(&linear), "linear midpoint {}", linear);
+        assert!(curved < linear * 2 / 3, "curved {} vs linear {}", curved, linear);
+    }
+
+    #[test]
+    fn envelope_exponential_reaches_each_stage_target() {
+        reset_pool();
+        let mut env = AudioEffectEnvelope::new();
+        env.delay(0.0);
+        env.attack(5.0);
+        env.hold(0.0);
+        env.decay(5.0);
+        env.sustain(0.5);
+        env.release(5.0);
+        env.curve(EnvelopeCurve::Exponential);
+        env.note_on();
+
+        let mut peak = 0;
+        let mut prev = 0;
+        for _ in 0..8 {
+            let out = render(&mut env);
+            peak = peak.max(*out.iter().max().unwrap());
+            prev = out[AUDIO_BLOCK_SAMPLES - 1];
+        }
+        assert_eq!(env.state(), EnvelopeState::Sustain);
+        assert!(peak > 32000, "attack peak {}", peak);
+        assert!((prev - 16384).abs() < 200, "sustain level {}", prev);
+
+        // Release falls fast at first: a quarter of the way in, well past a
+        // quarter of the drop
+        env.note_off();
+        let out = render(&mut env);
+        let quarter = out[(env.release_count as usize * 8 / 4).min(AUDIO_BLOCK_SAMPLES - 1)];
+        assert!(quarter < 12288 - 1000, "release quarter level {}", quarter);
+        for _ in 0..4 {
+            render(&mut env);
+        }
+        assert_eq!(env.state(), EnvelopeState::Idle);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn envelope_curve_fraction_endpoints() {
+        for rising in [true, false] {
+            assert_eq!(curve_fraction(0, 100, rising), 0);
+            assert_eq!(curve_fraction(100, 100, rising), 32768);
+            let mut prev = 0;
+            for n in 1..100 {
+                let f = curve_fraction(n, 100, rising);
+                assert!(f >= prev, "not monotonic at {}", n);
+                prev = f;
+            }
+        }
+        assert!(curve_fraction(50, 100, true) < 16384);
+        assert!(curve_fraction(50, 100, false) > 16384);
+    }
+
+    #[test]
+    fn envelope_just_finished_fires_once() {
+        reset_pool();
+        let mut env = AudioEffectEnvelope::new();
+        env.delay(0.0);
+        env.attack(1.0);
+        env.hold(0.0);
+        env.decay(1.0);
+        env.sustain(0.5);
+        env.release(10.0);
+
+        assert!(!env.just_finished());
+        env.note_on();
+        for _ in 0..5 {
+            render(&mut env);
+            assert!(!env.just_finished());
+        }
+        assert!((env.level() - 0.5).abs() < 0.01, "level {}", env.level());
+
+        env.note_off();
+        let mut fired = 0;
+        for _ in 0..10 {
+            render(&mut env);
+            if env.just_finished() {
+                fired += 1;
+                assert_eq!(env.state(), EnvelopeState::Idle);
+                assert_eq!(env.level(), 0.0);
+            }
+        }
+        assert_eq!(fired, 1);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
 }
@@ -4,13 +4,24 @@
 //! with linear interpolation to provide a perceptually smooth fade curve.
 
 use crate::block::{AudioBlockMut, AudioBlockRef};
-use crate::constants::{AUDIO_BLOCK_SAMPLES, AUDIO_SAMPLE_RATE_EXACT};
-use crate::dsp::wavetables::FADER_TABLE;
+use crate::constants::{ms_to_samples_truncated, AUDIO_BLOCK_SAMPLES};
+use crate::dsp::wavetables::fader_lookup;
 use crate::node::AudioNode;
 
 /// Maximum fade position (fully on).
 const MAX_FADE: u32 = 0xFFFF_FFFF;
 
+/// Gain curve of [`AudioEffectFade`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FadeCurve {
+    /// The fader table's raised-cosine curve, as in the C++ library: gentle
+    /// at both ends. This is the default.
+    Perceptual,
+    /// Gain proportional to fade progress, for fades that must sum
+    /// linearly with other signals.
+    Linear,
+}
+
 /// Volume fade effect. Smoothly fades audio in or out.
 ///
 /// Effect node: 1 input, 1 output.
@@ -27,6 +38,10 @@ pub struct AudioEffectFade {
     rate: u32,
     /// Fade direction: true = fading in, false = fading out.
     direction_in: bool,
+    /// Set when a fade reaches its endpoint; cleared by `just_completed()`.
+    completed: bool,
+    /// Position-to-gain curve.
+    curve: FadeCurve,
 }
 
 impl AudioEffectFade {
@@ -36,6 +51,8 @@ impl AudioEffectFade {
             position: MAX_FADE,
             rate: 0,
             direction_in: true,
+            completed: false,
+            curve: FadeCurve::Perceptual,
         }
     }
 
@@ -45,17 +62,14 @@ impl AudioEffectFade {
             position: 0,
             rate: 0,
             direction_in: true,
+            completed: false,
+            curve: FadeCurve::Perceptual,
         }
     }
 
     /// Begin fading in over the given duration in milliseconds.
     pub fn fade_in(&mut self, milliseconds: u32) {
-        let samples = if milliseconds == 0 {
-            1
-        } else {
-            ((milliseconds as f32 * AUDIO_SAMPLE_RATE_EXACT) / 1000.0) as u32
-        };
-        let samples = if samples == 0 { 1 } else { samples };
+        let samples = ms_to_samples_truncated(milliseconds as f32).max(1);
         self.rate = MAX_FADE / samples;
         self.direction_in = true;
         // Ensure we're not stuck at exactly 0
@@ -66,12 +80,7 @@ impl AudioEffectFade {
 
     /// Begin fading out over the given duration in milliseconds.
     pub fn fade_out(&mut self, milliseconds: u32) {
-        let samples = if milliseconds == 0 {
-            1
-        } else {
-            ((milliseconds as f32 * AUDIO_SAMPLE_RATE_EXACT) / 1000.0) as u32
-        };
-        let samples = if samples == 0 { 1 } else { samples };
+        let samples = ms_to_samples_truncated(milliseconds as f32).max(1);
         self.rate = MAX_FADE / samples;
         self.direction_in = false;
         // Ensure we're not stuck at exactly MAX_FADE
@@ -80,27 +89,72 @@ impl AudioEffectFade {
         }
     }
 
+    /// Select the perceptual (default) or linear gain curve.
+    ///
+    /// Fade times are the same either way; a change applies from the next
+    /// block, including mid-fade.
+    pub fn curve(&mut self, curve: FadeCurve) {
+        self.curve = curve;
+    }
+
     /// Get the current fade position (0.0 = silent, 1.0 = full volume).
     pub fn position_f32(&self) -> f32 {
         self.position as f32 / MAX_FADE as f32
     }
+
+    /// Whether a fade is in progress.
+    ///
+    /// Becomes `false` once the fade reaches full volume (fading in) or
+    /// silence (fading out).
+    pub fn is_fading(&self) -> bool {
+        self.rate > 0 && self.position != self.endpoint()
+    }
+
+    /// Returns `true` once after a fade reaches its endpoint, e.g. to stop
+    /// a player when its fade-out is done.
+    pub fn just_completed(&mut self) -> bool {
+        core::mem::take(&mut self.completed)
+    }
+
+    /// Position the current fade is heading for.
+    fn endpoint(&self) -> u32 {
+        if self.direction_in {
+            MAX_FADE
+        } else {
+            0
+        }
+    }
+
+    /// Advance a block's worth of position without producing output.
+    fn skip_block(&mut self) {
+        let advance = (self.rate as u64) * (AUDIO_BLOCK_SAMPLES as u64);
+        self.position = if self.direction_in {
+            (self.position as u64 + advance).min(MAX_FADE as u64) as u32
+        } else {
+            (self.position as u64).saturating_sub(advance) as u32
+        };
+        self.finish_if_done();
+    }
+
+    /// Stop a fade that has reached its endpoint and flag its completion.
+    fn finish_if_done(&mut self) {
+        if self.rate > 0 && self.position == self.endpoint() {
+            self.rate = 0;
+            self.completed = true;
+        }
+    }
 }
 
-/// Look up the fader table with linear interpolation.
-/// `pos` is a 32-bit position: upper 8 bits = index, bits 8–23 = fractional part.
+/// Gain proportional to `pos`, in the same Q15 range as [`fader_lookup`].
 #[inline]
-fn fader_lookup(pos: u32) -> i32 {
-    let index = (pos >> 24) as usize;
-    let val1 = FADER_TABLE[index] as i32;
-    let val2 = FADER_TABLE[index + 1] as i32;
-    let scale = ((pos >> 8) & 0xFFFF) as i32;
-    let interpolated = val1 * (0x10000 - scale) + val2 * scale;
-    interpolated >> 16
+fn linear_gain(pos: u32) -> i32 {
+    (pos >> 17) as i32
 }
 
 impl AudioNode for AudioEffectFade {
     const NUM_INPUTS: usize = 1;
     const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioEffectFade";
 
     fn update(
         &mut self,
@@ -111,16 +165,7 @@ impl AudioNode for AudioEffectFade {
             Some(ref b) => b,
             None => {
                 // No input: still advance position
-                if self.rate > 0 {
-                    let advance = (self.rate as u64) * (AUDIO_BLOCK_SAMPLES as u64);
-                    if self.direction_in {
-                        let new_pos = (self.position as u64).saturating_add(advance);
-                        self.position = if new_pos > MAX_FADE as u64 { MAX_FADE } else { new_pos as u32 };
-                    } else {
-                        let new_pos = (self.position as u64).wrapping_sub(advance);
-                        self.position = if self.position as u64 <= advance { 0 } else { new_pos as u32 };
-                    }
-                }
+                self.skip_block();
                 return;
             }
         };
@@ -129,6 +174,7 @@ impl AudioNode for AudioEffectFade {
 
         if pos == 0 {
             // Fully silent: discard input
+            self.finish_if_done();
             return;
         }
 
@@ -147,24 +193,20 @@ impl AudioNode for AudioEffectFade {
             Some(b) => b,
             None => {
                 // Still advance position even without output block
-                if self.rate > 0 {
-                    let advance = (self.rate as u64) * (AUDIO_BLOCK_SAMPLES as u64);
-                    if self.direction_in {
-                        let new_pos = (self.position as u64).saturating_add(advance);
-                        self.position = if new_pos > MAX_FADE as u64 { MAX_FADE } else { new_pos as u32 };
-                    } else {
-                        self.position = if self.position as u64 <= advance { 0 } else { (self.position as u64 - advance) as u32 };
-                    }
-                }
+                self.skip_block();
                 return;
             }
         };
 
         let mut current_pos = pos;
         let inc = self.rate;
+        let gain_at: fn(u32) -> i32 = match self.curve {
+            FadeCurve::Perceptual => fader_lookup,
+            FadeCurve::Linear => linear_gain,
+        };
 
         for i in 0..AUDIO_BLOCK_SAMPLES {
-            let gain = fader_lookup(current_pos);
+            let gain = gain_at(current_pos);
             let sample = input[i] as i32;
             out[i] = ((sample * gain) >> 15) as i16;
 
@@ -185,8 +227,16 @@ impl AudioNode for AudioEffectFade {
         }
 
         self.position = current_pos;
+        self.finish_if_done();
         outputs[0] = Some(out);
     }
+
+    /// Finish any fade in progress: jump to full volume if fading in,
+    /// silence if fading out.
+    fn reset(&mut self) {
+        self.position = if self.direction_in { MAX_FADE } else { 0 };
+        self.rate = 0;
+    }
 }
 
 #[cfg(test)]
@@ -282,15 +332,6 @@ mod tests {
         assert!(out[0] > out[127], "first should be louder than last: {} vs {}", out[0], out[127]);
     }
 
-    #[test]
-    fn fader_lookup_endpoints() {
-        // Position 0 → gain 0
-        assert_eq!(fader_lookup(0), 0);
-        // Position MAX → gain ~32767
-        let gain = fader_lookup(MAX_FADE);
-        assert!(gain >= 32766, "expected ~32767, got {}", gain);
-    }
-
     #[test]
     fn fade_position_clamps() {
         reset_pool();
@@ -310,4 +351,77 @@ mod tests {
 
         assert_eq!(fade.position, MAX_FADE);
     }
+
+    #[test]
+    fn fade_reports_fading_and_completion() {
+        reset_pool();
+        let mut fade = AudioEffectFade::new();
+        assert!(!fade.is_fading());
+
+        // 10 ms ≈ 441 samples: fading for three blocks, done in the fourth
+        fade.fade_out(10);
+        let mut blocks = 0;
+        while fade.is_fading() {
+            assert!(!fade.just_completed());
+            let inputs = [Some(alloc_block_with_value(10000).into_shared())];
+            let mut outputs = [AudioBlockMut::alloc()];
+            fade.update(&inputs, &mut outputs);
+            blocks += 1;
+            assert!(blocks <= 4, "fade never ended");
+        }
+        assert_eq!(blocks, 4);
+        assert_eq!(fade.position, 0);
+        assert!(fade.just_completed());
+        assert!(!fade.just_completed(), "reported once");
+
+        // A fade in without input completes the same way
+        fade.fade_in(10);
+        assert!(fade.is_fading());
+        for _ in 0..4 {
+            fade.update(&[None], &mut [AudioBlockMut::alloc()]);
+        }
+        assert!(!fade.is_fading());
+        assert!(fade.just_completed());
+        assert_eq!(fade.position, MAX_FADE);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    /// Run a fade-in and a fade-out of the same DC block side by side and
+    /// return the per-sample sums over the fade.
+    fn crossfade_sums(curve: FadeCurve) -> [i32; 4 * AUDIO_BLOCK_SAMPLES] {
+        let mut fade_up = AudioEffectFade::new_silent();
+        let mut fade_down = AudioEffectFade::new();
+        fade_up.curve(curve);
+        fade_down.curve(curve);
+        fade_up.fade_in(10);
+        fade_down.fade_out(10);
+
+        let mut sums = [0; 4 * AUDIO_BLOCK_SAMPLES];
+        for block in sums.chunks_exact_mut(AUDIO_BLOCK_SAMPLES) {
+            let inputs = [Some(alloc_block_with_value(20000).into_shared())];
+            let (mut up, mut down) = ([AudioBlockMut::alloc()], [AudioBlockMut::alloc()]);
+            fade_up.update(&inputs, &mut up);
+            fade_down.update(&inputs, &mut down);
+            let (up, down) = (up[0].as_ref().unwrap(), down[0].as_ref().unwrap());
+            for (i, sum) in block.iter_mut().enumerate() {
+                *sum = up[i] as i32 + down[i] as i32;
+            }
+        }
+        sums
+    }
+
+    #[test]
+    fn fade_linear_curve_sums_to_constant() {
+        reset_pool();
+        let sums = crossfade_sums(FadeCurve::Linear);
+        for (n, &sum) in sums.iter().enumerate() {
+            assert!((sum - 20000).abs() <= 2, "sample {}: {}", n, sum);
+        }
+
+        // Linear gain tracks progress; the perceptual curve starts slower
+        let quarter = MAX_FADE / 4;
+        assert_eq!(linear_gain(quarter), 8191);
+        assert!((fader_lookup(quarter) - 4799).abs() <= 2, "{}", fader_lookup(quarter));
+        assert_eq!(POOL.allocated_count(), 0);
+    }
 }
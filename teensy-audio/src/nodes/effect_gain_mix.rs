@@ -0,0 +1,176 @@
+//! Two-input gain + mix.
+//!
+//! Covers the common "two sources with level control" pattern — e.g. dry/wet
+//! or A/B sources — without a full N-channel mixer. Each input has its own
+//! Q16.16 gain; the gained inputs are summed with saturation.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::dsp::intrinsics::saturate16;
+use crate::node::AudioNode;
+
+use super::amplifier::{gain_to_multiplier, MULTI_UNITYGAIN};
+
+/// Two-input gain + mix. Effect node: 2 inputs (A, B), 1 output.
+///
+/// Output is `saturate16(a * gain_a + b * gain_b)`. A missing input
+/// contributes nothing; with both inputs missing the output is dropped
+/// (silence).
+///
+/// # Example
+/// ```ignore
+/// let mut mix = AudioEffectGainMix::new();
+/// mix.gain_a(0.7); // dry
+/// mix.gain_b(0.3); // wet
+/// ```
+pub struct AudioEffectGainMix {
+    /// Per-input gain in Q16.16 fixed-point. 65536 = unity (1.0).
+    multiplier: [i32; 2],
+}
+
+impl AudioEffectGainMix {
+    /// Create a new gain-mix with both inputs at unity gain.
+    pub const fn new() -> Self {
+        AudioEffectGainMix {
+            multiplier: [MULTI_UNITYGAIN; 2],
+        }
+    }
+
+    /// Set the gain of input A (input 0).
+    ///
+    /// 0.0 = silence, 1.0 = unity, >1.0 = boost. Clamped to ±32767.0.
+    pub fn gain_a(&mut self, level: f32) {
+        self.multiplier[0] = gain_to_multiplier(level);
+    }
+
+    /// Set the gain of input B (input 1).
+    ///
+    /// 0.0 = silence, 1.0 = unity, >1.0 = boost. Clamped to ±32767.0.
+    pub fn gain_b(&mut self, level: f32) {
+        self.multiplier[1] = gain_to_multiplier(level);
+    }
+
+    /// Set both gains at once.
+    pub fn gains(&mut self, a: f32, b: f32) {
+        self.gain_a(a);
+        self.gain_b(b);
+    }
+}
+
+impl Default for AudioEffectGainMix {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioEffectGainMix {
+    const NUM_INPUTS: usize = 2;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioEffectGainMix";
+
+    fn update(
+        &mut self,
+        inputs: &[Option<AudioBlockRef>],
+        outputs: &mut [Option<AudioBlockMut>],
+    ) {
+        let mut out = match outputs[0].take() {
+            Some(b) => b,
+            None => return,
+        };
+
+        let (mult_a, mult_b) = (self.multiplier[0] as i64, self.multiplier[1] as i64);
+        match (&inputs[0], &inputs[1]) {
+            (Some(a), Some(b)) => {
+                for i in 0..AUDIO_BLOCK_SAMPLES {
+                    let sum = (a[i] as i64 * mult_a + b[i] as i64 * mult_b) >> 16;
+                    out[i] = saturate16(sum.clamp(i32::MIN as i64, i32::MAX as i64) as i32);
+                }
+            }
+            (Some(src), None) | (None, Some(src)) => {
+                let mult = if inputs[0].is_some() { mult_a } else { mult_b };
+                for i in 0..AUDIO_BLOCK_SAMPLES {
+                    let val = (src[i] as i64 * mult) >> 16;
+                    out[i] = saturate16(val.clamp(i32::MIN as i64, i32::MAX as i64) as i32);
+                }
+            }
+            (None, None) => {
+                // No inputs: drop the output block (silence)
+                return;
+            }
+        }
+
+        outputs[0] = Some(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    fn dc_block(value: i16) -> AudioBlockRef {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block.fill(value);
+        block.into_shared()
+    }
+
+    #[test]
+    fn gain_mix_weighted_sum() {
+        reset_pool();
+        let mut mix = AudioEffectGainMix::new();
+        mix.gain_a(0.5);
+        mix.gain_b(0.25);
+
+        let inputs = [Some(dc_block(20000)), Some(dc_block(-8000))];
+        let mut outputs = [AudioBlockMut::alloc()];
+        mix.update(&inputs, &mut outputs);
+
+        // 20000 * 0.5 + (-8000) * 0.25 = 8000
+        let out = outputs[0].as_ref().unwrap();
+        assert!(out.iter().all(|&s| s == 8000), "got {}", out[0]);
+    }
+
+    #[test]
+    fn gain_mix_saturates() {
+        reset_pool();
+        let mut mix = AudioEffectGainMix::new();
+        mix.gains(1.0, 1.0);
+
+        let inputs = [Some(dc_block(30000)), Some(dc_block(30000))];
+        let mut outputs = [AudioBlockMut::alloc()];
+        mix.update(&inputs, &mut outputs);
+        assert_eq!(outputs[0].as_ref().unwrap()[0], 32767);
+
+        let inputs = [Some(dc_block(-30000)), Some(dc_block(-30000))];
+        let mut outputs = [AudioBlockMut::alloc()];
+        mix.update(&inputs, &mut outputs);
+        assert_eq!(outputs[0].as_ref().unwrap()[0], -32768);
+    }
+
+    #[test]
+    fn gain_mix_single_input() {
+        reset_pool();
+        let mut mix = AudioEffectGainMix::new();
+        mix.gains(0.5, 0.25);
+
+        let inputs = [None, Some(dc_block(16000))];
+        let mut outputs = [AudioBlockMut::alloc()];
+        mix.update(&inputs, &mut outputs);
+        assert_eq!(outputs[0].as_ref().unwrap()[0], 4000);
+    }
+
+    #[test]
+    fn gain_mix_no_inputs_drops_output() {
+        reset_pool();
+        let mut mix = AudioEffectGainMix::new();
+        let inputs: [Option<AudioBlockRef>; 2] = [None, None];
+        let mut outputs = [AudioBlockMut::alloc()];
+        mix.update(&inputs, &mut outputs);
+        assert!(outputs[0].is_none());
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+}
@@ -0,0 +1,128 @@
+//! Sample-by-sample multiply (ring modulation).
+//!
+//! Port of `TeensyAudio/effect_multiply.cpp`. Multiplying a signal by an
+//! LFO gives tremolo; by an audio-rate oscillator, ring modulation / AM.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::dsp::intrinsics::saturate16;
+use crate::node::AudioNode;
+
+/// Multiply two signals. Effect node: 2 inputs, 1 output.
+///
+/// Each output sample is `saturate16((a * b) >> 15)`, treating both inputs
+/// as Q15. If either input is missing the product is silence and the output
+/// block is dropped.
+///
+/// # Example
+/// ```ignore
+/// audio_graph! {
+///     pub struct Tremolo {
+///         voice: AudioSynthWaveform {},
+///         lfo: AudioSynthSine {},
+///         trem: AudioEffectMultiply { (voice, 0), (lfo, 0) },
+///     }
+/// }
+/// ```
+pub struct AudioEffectMultiply;
+
+impl AudioEffectMultiply {
+    /// Create a new multiply node.
+    pub const fn new() -> Self {
+        AudioEffectMultiply
+    }
+}
+
+impl Default for AudioEffectMultiply {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioEffectMultiply {
+    const NUM_INPUTS: usize = 2;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioEffectMultiply";
+
+    fn update(
+        &mut self,
+        inputs: &[Option<AudioBlockRef>],
+        outputs: &mut [Option<AudioBlockMut>],
+    ) {
+        let mut out = match outputs[0].take() {
+            Some(b) => b,
+            None => return,
+        };
+
+        let (a, b) = match (&inputs[0], &inputs[1]) {
+            (Some(a), Some(b)) => (a, b),
+            // Anything times silence is silence: drop the output block
+            _ => return,
+        };
+
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            out[i] = saturate16((a[i] as i32 * b[i] as i32) >> 15);
+        }
+
+        outputs[0] = Some(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    fn dc_block(value: i16) -> AudioBlockRef {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block.fill(value);
+        block.into_shared()
+    }
+
+    #[test]
+    fn multiply_full_scale_by_half() {
+        reset_pool();
+        let mut mult = AudioEffectMultiply::new();
+        let inputs = [Some(dc_block(32767)), Some(dc_block(16384))];
+        let mut outputs = [AudioBlockMut::alloc()];
+        mult.update(&inputs, &mut outputs);
+
+        let out = outputs[0].as_ref().unwrap();
+        assert!(out.iter().all(|&s| (s - 16384).abs() <= 1), "got {}", out[0]);
+    }
+
+    #[test]
+    fn multiply_sign_and_saturation() {
+        reset_pool();
+        let mut mult = AudioEffectMultiply::new();
+        let inputs = [Some(dc_block(-16384)), Some(dc_block(16384))];
+        let mut outputs = [AudioBlockMut::alloc()];
+        mult.update(&inputs, &mut outputs);
+        assert_eq!(outputs[0].as_ref().unwrap()[0], -8192);
+
+        // -1.0 × -1.0 = +1.0, which saturates to 32767
+        let inputs = [Some(dc_block(-32768)), Some(dc_block(-32768))];
+        let mut outputs = [AudioBlockMut::alloc()];
+        mult.update(&inputs, &mut outputs);
+        assert_eq!(outputs[0].as_ref().unwrap()[0], 32767);
+    }
+
+    #[test]
+    fn multiply_missing_input_frees_output() {
+        reset_pool();
+        let mut mult = AudioEffectMultiply::new();
+        let signal = dc_block(12345);
+
+        for inputs in [[None, Some(signal.clone())], [Some(signal.clone()), None]] {
+            let mut outputs = [AudioBlockMut::alloc()];
+            assert_eq!(POOL.allocated_count(), 2);
+            mult.update(&inputs, &mut outputs);
+            assert!(outputs[0].is_none());
+            assert_eq!(POOL.allocated_count(), 1, "only the input block should remain");
+        }
+    }
+}
@@ -0,0 +1,191 @@
+//! Click-free mute.
+//!
+//! A lighter [`AudioEffectFade`](super::AudioEffectFade) for plain on/off:
+//! the gain follows `FADER_TABLE` over a short, fixed number of samples
+//! instead of cutting instantly.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::dsp::wavetables::fader_lookup;
+use crate::node::AudioNode;
+
+/// Fully-on ramp position.
+const MAX_POSITION: u32 = 0xFFFF_FFFF;
+
+/// Default ramp length in samples (~5.8 ms).
+const DEFAULT_RAMP_SAMPLES: u32 = 256;
+
+/// Mute switch. Effect node: 1 input, 1 output.
+///
+/// Starts unmuted. [`mute()`](Self::mute) and [`unmute()`](Self::unmute)
+/// ramp the gain along the fader curve over
+/// [`ramp_samples()`](Self::ramp_samples) samples, reversing smoothly if
+/// called mid-ramp. Once fully muted the output block is dropped; once
+/// fully unmuted the input is copied through unchanged.
+///
+/// # Example
+/// ```ignore
+/// let mut mute = AudioEffectMute::new();
+/// mute.ramp_samples(512);
+/// mute.mute();
+/// ```
+pub struct AudioEffectMute {
+    /// Ramp position: 0 = silent, `MAX_POSITION` = full volume.
+    position: u32,
+    /// Position change per sample.
+    rate: u32,
+    muted: bool,
+}
+
+impl AudioEffectMute {
+    /// Create a new, unmuted mute switch.
+    pub const fn new() -> Self {
+        AudioEffectMute {
+            position: MAX_POSITION,
+            rate: MAX_POSITION / DEFAULT_RAMP_SAMPLES,
+            muted: false,
+        }
+    }
+
+    /// Set the ramp length in samples (at least 1, which is an instant cut).
+    pub fn ramp_samples(&mut self, samples: u32) {
+        self.rate = MAX_POSITION / samples.max(1);
+    }
+
+    /// Ramp down to silence.
+    pub fn mute(&mut self) {
+        self.muted = true;
+    }
+
+    /// Ramp back up to full volume.
+    pub fn unmute(&mut self) {
+        self.muted = false;
+    }
+
+    /// Returns `true` if muted or ramping towards mute.
+    pub fn is_muted(&self) -> bool {
+        self.muted
+    }
+
+    /// Advance the ramp by `samples` samples.
+    #[inline(always)]
+    fn advance(&mut self, samples: u32) {
+        let delta = self.rate.saturating_mul(samples);
+        self.position = if self.muted {
+            self.position.saturating_sub(delta)
+        } else {
+            self.position.saturating_add(delta)
+        };
+    }
+}
+
+impl Default for AudioEffectMute {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioEffectMute {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioEffectMute";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        // Taken up front so a fully muted switch drops it (silence)
+        let out = outputs[0].take();
+        let (Some(input), Some(mut out)) = (inputs[0].as_ref(), out) else {
+            self.advance(AUDIO_BLOCK_SAMPLES as u32);
+            return;
+        };
+        if self.muted && self.position == 0 {
+            return;
+        }
+
+        if !self.muted && self.position == MAX_POSITION {
+            out.copy_from_slice(&input[..]);
+        } else {
+            for (dst, &src) in out.iter_mut().zip(input.iter()) {
+                *dst = ((src as i32 * fader_lookup(self.position)) >> 15) as i16;
+                self.advance(1);
+            }
+        }
+
+        outputs[0] = Some(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    fn run(mute: &mut AudioEffectMute, value: i16) -> Option<AudioBlockMut> {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block.fill(value);
+        let mut outputs = [AudioBlockMut::alloc()];
+        mute.update(&[Some(block.into_shared())], &mut outputs);
+        outputs[0].take()
+    }
+
+    #[test]
+    fn mute_passes_through_when_unmuted() {
+        reset_pool();
+        let mut mute = AudioEffectMute::new();
+        let out = run(&mut mute, 12345).unwrap();
+        assert!(out.iter().all(|&s| s == 12345));
+    }
+
+    #[test]
+    fn mute_ramps_down_then_drops_output() {
+        reset_pool();
+        let mut mute = AudioEffectMute::new();
+        mute.ramp_samples(300);
+        mute.mute();
+        assert!(mute.is_muted());
+
+        let mut prev = i16::MAX;
+        let mut samples = 0;
+        while let Some(out) = run(&mut mute, 20000) {
+            for &s in out.iter() {
+                assert!(s <= prev, "gain rose while muting: {} after {}", s, prev);
+                prev = s;
+            }
+            samples += out.len();
+            assert!(samples <= 512, "ramp took too long");
+        }
+        assert_eq!(prev, 0, "ramp ends at silence");
+        assert!(run(&mut mute, 20000).is_none());
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn unmute_ramps_back_up() {
+        reset_pool();
+        let mut mute = AudioEffectMute::new();
+        mute.ramp_samples(200);
+        mute.mute();
+        while run(&mut mute, 20000).is_some() {}
+
+        mute.unmute();
+        assert!(!mute.is_muted());
+        let first = run(&mut mute, 20000).unwrap();
+        assert!(first[0] < 100, "starts near silence: {}", first[0]);
+        for pair in first.windows(2) {
+            assert!(pair[1] >= pair[0], "gain fell while unmuting");
+        }
+        drop(first);
+
+        let second = run(&mut mute, 20000).unwrap();
+        assert!(
+            second[AUDIO_BLOCK_SAMPLES - 1] >= 19990,
+            "reaches full level"
+        );
+        drop(second);
+        let settled = run(&mut mute, 20000).unwrap();
+        assert!(settled.iter().all(|&s| s == 20000));
+    }
+}
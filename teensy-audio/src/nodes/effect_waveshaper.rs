@@ -0,0 +1,241 @@
+//! Waveshaper — maps each sample through a transfer curve.
+//!
+//! Port of `TeensyAudio/effect_waveshaper.cpp`. The curve is a table of
+//! `2^k + 1` points spanning input -1.0 … +1.0, looked up with linear
+//! interpolation (the same scheme as the fader table in `effect_fade`).
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::node::AudioNode;
+
+/// Largest transfer table accepted by [`AudioEffectWaveshaper::shape()`].
+pub const WAVESHAPER_MAX_POINTS: usize = 1025;
+
+/// Table size used by [`AudioEffectWaveshaper::tanh_drive()`].
+const TANH_POINTS: usize = 257;
+
+/// Waveshaper. Effect node: 1 input, 1 output.
+///
+/// The table is copied into the node, so no pool or heap memory is used.
+/// Until a shape is set the node passes audio through unchanged.
+///
+/// # Example
+/// ```ignore
+/// let mut shaper = AudioEffectWaveshaper::new();
+/// shaper.tanh_drive(4.0);                // soft clip
+/// shaper.shape(&[-16384, 0, 16384]);      // or any 2^k + 1 point curve
+/// ```
+pub struct AudioEffectWaveshaper {
+    table: [i16; WAVESHAPER_MAX_POINTS],
+    /// Number of valid points in `table` (0 = passthrough).
+    len: usize,
+    /// `16 - k`: input bits below the table index.
+    shift: u32,
+}
+
+impl AudioEffectWaveshaper {
+    /// Create a new waveshaper with no curve (passthrough).
+    pub const fn new() -> Self {
+        AudioEffectWaveshaper {
+            table: [0; WAVESHAPER_MAX_POINTS],
+            len: 0,
+            shift: 0,
+        }
+    }
+
+    /// Set the transfer curve.
+    ///
+    /// `table[0]` is the output for input -1.0 and the last entry the output
+    /// for +1.0. The length must be `2^k + 1` with `1 ≤ k ≤ 10` (3 to 1025
+    /// points); otherwise the call is ignored and `false` is returned.
+    pub fn shape(&mut self, table: &[i16]) -> bool {
+        let steps = table.len().wrapping_sub(1);
+        if table.len() > WAVESHAPER_MAX_POINTS || steps < 2 || !steps.is_power_of_two() {
+            return false;
+        }
+        self.table[..table.len()].copy_from_slice(table);
+        self.len = table.len();
+        self.shift = 16 - steps.trailing_zeros();
+        true
+    }
+
+    /// Fill the curve with a `tanh` soft clip.
+    ///
+    /// `drive` is the input gain ahead of the `tanh`; the curve is
+    /// normalised so full-scale input still reaches full-scale output.
+    /// Higher drive gives a harder knee. Drive ≤ 0 restores passthrough.
+    pub fn tanh_drive(&mut self, drive: f32) {
+        if drive <= 0.0 {
+            self.len = 0;
+            return;
+        }
+        let norm = 32767.0 / libm::tanhf(drive);
+        let mut curve = [0i16; TANH_POINTS];
+        for (i, point) in curve.iter_mut().enumerate() {
+            let x = i as f32 * (2.0 / (TANH_POINTS - 1) as f32) - 1.0;
+            *point = libm::roundf(libm::tanhf(drive * x) * norm) as i16;
+        }
+        self.shape(&curve);
+    }
+
+    /// Look up one sample in the transfer table.
+    #[inline(always)]
+    fn lookup(&self, sample: i16) -> i16 {
+        // Offset to 0..=65535 so the table spans -1.0 … +1.0
+        let x = (sample as i32 + 32768) as u32;
+        let index = (x >> self.shift) as usize;
+        let frac = (x & ((1 << self.shift) - 1)) as i32;
+        let val1 = self.table[index] as i32;
+        let val2 = self.table[index + 1] as i32;
+        (val1 + (((val2 - val1) * frac) >> self.shift)) as i16
+    }
+}
+
+impl Default for AudioEffectWaveshaper {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioEffectWaveshaper {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioEffectWaveshaper";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        let input = match inputs[0] {
+            Some(ref b) => b,
+            None => {
+                // No input: drop the output block (silence)
+                outputs[0] = None;
+                return;
+            }
+        };
+
+        let mut out = match outputs[0].take() {
+            Some(b) => b,
+            None => return,
+        };
+
+        if self.len == 0 {
+            out.copy_from_slice(&input[..]);
+        } else {
+            for i in 0..AUDIO_BLOCK_SAMPLES {
+                out[i] = self.lookup(input[i]);
+            }
+        }
+
+        outputs[0] = Some(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Run a block spanning the full input range (-32768 … 32767 in 512 steps).
+    fn sweep(
+        shaper: &mut AudioEffectWaveshaper,
+    ) -> ([i16; AUDIO_BLOCK_SAMPLES], [i16; AUDIO_BLOCK_SAMPLES]) {
+        let mut input = AudioBlockMut::alloc().unwrap();
+        for (i, s) in input.iter_mut().enumerate() {
+            *s = (-32768 + i as i32 * 512 + (i as i32 * 511) / 127) as i16;
+        }
+        let mut x = [0; AUDIO_BLOCK_SAMPLES];
+        x.copy_from_slice(&input[..]);
+        let inputs = [Some(input.into_shared())];
+        let mut outputs = [AudioBlockMut::alloc()];
+        shaper.update(&inputs, &mut outputs);
+        let mut y = [0; AUDIO_BLOCK_SAMPLES];
+        y.copy_from_slice(&outputs[0].as_ref().unwrap()[..]);
+        (x, y)
+    }
+
+    #[test]
+    fn waveshaper_identity_table_is_passthrough() {
+        reset_pool();
+        let mut shaper = AudioEffectWaveshaper::new();
+        let mut table = [0i16; 17];
+        for (i, t) in table.iter_mut().enumerate() {
+            *t = (-32768 + i as i32 * 4096).min(32767) as i16;
+        }
+        assert!(shaper.shape(&table));
+
+        let (x, y) = sweep(&mut shaper);
+        assert_eq!(x[0], -32768);
+        assert_eq!(x[127], 32767);
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            assert!(
+                (x[i] as i32 - y[i] as i32).abs() <= 1,
+                "{} -> {}",
+                x[i],
+                y[i]
+            );
+        }
+    }
+
+    #[test]
+    fn waveshaper_hard_clip_saturates() {
+        reset_pool();
+        let mut shaper = AudioEffectWaveshaper::new();
+        // Linear through ±0.5, flat beyond
+        assert!(shaper.shape(&[-16384, -16384, 0, 16384, 16384]));
+
+        let (x, y) = sweep(&mut shaper);
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            if x[i] <= -16384 {
+                assert_eq!(y[i], -16384);
+            } else if x[i] >= 16384 {
+                assert_eq!(y[i], 16384);
+            } else {
+                assert!((x[i] as i32 - y[i] as i32).abs() <= 1);
+            }
+        }
+    }
+
+    #[test]
+    fn waveshaper_tanh_is_soft_and_odd() {
+        reset_pool();
+        let mut shaper = AudioEffectWaveshaper::new();
+        shaper.tanh_drive(3.0);
+
+        let (_, y) = sweep(&mut shaper);
+        // Monotonic, boosted near zero, compressed near full scale
+        assert!(y.windows(2).all(|w| w[1] >= w[0]));
+        assert!(y[127] >= 32760);
+        let quarter = shaper.lookup(8192);
+        assert!(
+            quarter > 16000,
+            "tanh(0.75)/tanh(3) ≈ 0.64, got {}",
+            quarter
+        );
+        assert!((shaper.lookup(8192) as i32 + shaper.lookup(-8192) as i32).abs() <= 1);
+    }
+
+    #[test]
+    fn waveshaper_rejects_bad_table_lengths() {
+        let mut shaper = AudioEffectWaveshaper::new();
+        assert!(!shaper.shape(&[]));
+        assert!(!shaper.shape(&[0, 0]));
+        assert!(!shaper.shape(&[0; 6]));
+        assert!(!shaper.shape(&[0; 2049]));
+        assert_eq!(shaper.len, 0);
+        assert!(shaper.shape(&[0; 1025]));
+        assert_eq!(shaper.shift, 6);
+    }
+
+    #[test]
+    fn waveshaper_no_input_drops_output() {
+        reset_pool();
+        let mut shaper = AudioEffectWaveshaper::new();
+        let mut outputs = [AudioBlockMut::alloc()];
+        shaper.update(&[None], &mut outputs);
+        assert!(outputs[0].is_none());
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+}
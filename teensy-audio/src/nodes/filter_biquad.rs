@@ -0,0 +1,319 @@
+//! Cascaded biquad IIR filter.
+//!
+//! Port of `TeensyAudio/filter_biquad.cpp`. Runs up to four second-order
+//! sections in series, each in Direct Form I with Q30 coefficients (the same
+//! `[b0, b1, b2, a1, a2]` layout the SGTL5000 PEQ uses).
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::{AUDIO_BLOCK_SAMPLES, AUDIO_SAMPLE_RATE_EXACT};
+use crate::dsp::intrinsics::{
+    multiply_accumulate_32x32_rshift32_rounded, multiply_subtract_32x32_rshift32_rounded,
+    saturate16,
+};
+use crate::node::AudioNode;
+
+/// Maximum number of cascaded biquad stages.
+pub const BIQUAD_MAX_STAGES: usize = 4;
+
+/// 1.0 in Q30.
+const Q30_ONE: f32 = 1_073_741_824.0;
+
+/// Cascaded biquad filter. Effect node: 1 input, 1 output.
+///
+/// Each stage computes
+/// `y[n] = b0·x[n] + b1·x[n-1] + b2·x[n-2] − a1·y[n-1] − a2·y[n-2]`
+/// with coefficients in Q30 (already normalized so `a0 = 1`). Stages that
+/// have not been configured are skipped; with no stages configured the
+/// filter passes audio through unchanged.
+///
+/// # Example
+/// ```ignore
+/// let mut filter = AudioFilterBiquad::new();
+/// filter.set_lowpass(0, 800.0, 0.707);
+/// filter.set_lowpass(1, 800.0, 0.707); // 4th-order
+/// ```
+pub struct AudioFilterBiquad {
+    /// Per-stage `[b0, b1, b2, a1, a2]` in Q30.
+    coefficients: [[i32; 5]; BIQUAD_MAX_STAGES],
+    /// Per-stage `[x1, x2, y1, y2]`, samples scaled by 2^16.
+    state: [[i32; 4]; BIQUAD_MAX_STAGES],
+    /// Which stages have been configured.
+    active: [bool; BIQUAD_MAX_STAGES],
+}
+
+impl AudioFilterBiquad {
+    /// Create a new filter with no stages configured (passthrough).
+    pub const fn new() -> Self {
+        AudioFilterBiquad {
+            coefficients: [[0; 5]; BIQUAD_MAX_STAGES],
+            state: [[0; 4]; BIQUAD_MAX_STAGES],
+            active: [false; BIQUAD_MAX_STAGES],
+        }
+    }
+
+    /// Set raw `[b0, b1, b2, a1, a2]` coefficients (Q30) for a stage.
+    ///
+    /// Out-of-range stages are ignored. The stage's history is kept, so
+    /// coefficients can be changed while running without a click from
+    /// reset state.
+    pub fn set_coefficients(&mut self, stage: usize, coefficients: &[i32; 5]) {
+        if stage >= BIQUAD_MAX_STAGES {
+            return;
+        }
+        self.coefficients[stage] = *coefficients;
+        self.active[stage] = true;
+    }
+
+    /// Configure a stage as a 2nd-order lowpass at `freq` Hz with quality `q`.
+    pub fn set_lowpass(&mut self, stage: usize, freq: f32, q: f32) {
+        let (cosw, alpha) = Self::params(freq, q);
+        let b0 = (1.0 - cosw) / 2.0;
+        self.set_normalized(
+            stage,
+            [b0, 1.0 - cosw, b0, -2.0 * cosw, 1.0 - alpha],
+            1.0 + alpha,
+        );
+    }
+
+    /// Configure a stage as a 2nd-order highpass at `freq` Hz with quality `q`.
+    pub fn set_highpass(&mut self, stage: usize, freq: f32, q: f32) {
+        let (cosw, alpha) = Self::params(freq, q);
+        let b0 = (1.0 + cosw) / 2.0;
+        self.set_normalized(
+            stage,
+            [b0, -(1.0 + cosw), b0, -2.0 * cosw, 1.0 - alpha],
+            1.0 + alpha,
+        );
+    }
+
+    /// Configure a stage as a bandpass (0 dB peak gain) centred on `freq` Hz.
+    pub fn set_bandpass(&mut self, stage: usize, freq: f32, q: f32) {
+        let (cosw, alpha) = Self::params(freq, q);
+        self.set_normalized(
+            stage,
+            [alpha, 0.0, -alpha, -2.0 * cosw, 1.0 - alpha],
+            1.0 + alpha,
+        );
+    }
+
+    /// Configure a stage as a notch centred on `freq` Hz.
+    pub fn set_notch(&mut self, stage: usize, freq: f32, q: f32) {
+        let (cosw, alpha) = Self::params(freq, q);
+        self.set_normalized(
+            stage,
+            [1.0, -2.0 * cosw, 1.0, -2.0 * cosw, 1.0 - alpha],
+            1.0 + alpha,
+        );
+    }
+
+    /// `(cos(w0), alpha)` for the RBJ cookbook formulas.
+    fn params(freq: f32, q: f32) -> (f32, f32) {
+        let w0 = freq * (2.0 * core::f32::consts::PI / AUDIO_SAMPLE_RATE_EXACT);
+        let q = if q < 0.01 { 0.01 } else { q };
+        (libm::cosf(w0), libm::sinf(w0) / (2.0 * q))
+    }
+
+    /// Divide by `a0` and convert to Q30.
+    fn set_normalized(&mut self, stage: usize, coefs: [f32; 5], a0: f32) {
+        let mut q30 = [0i32; 5];
+        for (dst, &c) in q30.iter_mut().zip(coefs.iter()) {
+            // Q30 spans [-2.0, 2.0); the float→int cast saturates at the ends
+            *dst = (c / a0 * Q30_ONE) as i32;
+        }
+        self.set_coefficients(stage, &q30);
+    }
+}
+
+impl Default for AudioFilterBiquad {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Run one biquad stage in place over a block.
+fn process_stage(data: &mut [i16; AUDIO_BLOCK_SAMPLES], c: &[i32; 5], s: &mut [i32; 4]) {
+    let [b0, b1, b2, a1, a2] = *c;
+    let [mut x1, mut x2, mut y1, mut y2] = *s;
+
+    for sample in data.iter_mut() {
+        let x0 = (*sample as i32) << 16;
+        // Q30 coefficient × (sample << 16) >> 32 = sample << 14
+        let mut sum = multiply_accumulate_32x32_rshift32_rounded(0, b0, x0);
+        sum = multiply_accumulate_32x32_rshift32_rounded(sum, b1, x1);
+        sum = multiply_accumulate_32x32_rshift32_rounded(sum, b2, x2);
+        sum = multiply_subtract_32x32_rshift32_rounded(sum, a1, y1);
+        sum = multiply_subtract_32x32_rshift32_rounded(sum, a2, y2);
+
+        // Back to sample << 16, saturating so a ringing stage can't wrap
+        let y0 = sum.clamp(i32::MIN >> 2, i32::MAX >> 2) << 2;
+        *sample = saturate16(((y0 >> 15) + 1) >> 1);
+
+        x2 = x1;
+        x1 = x0;
+        y2 = y1;
+        y1 = y0;
+    }
+
+    *s = [x1, x2, y1, y2];
+}
+
+impl AudioNode for AudioFilterBiquad {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioFilterBiquad";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        let input = match inputs[0] {
+            Some(ref b) => b,
+            None => {
+                // No input: clear every stage's history, so audio resuming
+                // later does not ring with stale samples
+                self.reset();
+                return;
+            }
+        };
+
+        let mut out = match outputs[0].take() {
+            Some(b) => b,
+            None => return,
+        };
+
+        out.copy_from_slice(&input[..]);
+        for stage in 0..BIQUAD_MAX_STAGES {
+            if self.active[stage] {
+                process_stage(&mut out, &self.coefficients[stage], &mut self.state[stage]);
+            }
+        }
+
+        outputs[0] = Some(out);
+    }
+
+    /// Clear the history of every stage (e.g. after a discontinuity).
+    fn reset(&mut self) {
+        self.state = [[0; 4]; BIQUAD_MAX_STAGES];
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::nodes::AudioSynthSine;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Feed a full-scale sine at `hz` through `filter` for `blocks` blocks
+    /// and return the peak absolute output of the final block.
+    fn steady_state_peak(filter: &mut AudioFilterBiquad, hz: f32, blocks: usize) -> i16 {
+        let mut sine = AudioSynthSine::new();
+        sine.frequency(hz);
+        sine.amplitude(1.0);
+        let mut peak = 0;
+        for _ in 0..blocks {
+            let mut src = [AudioBlockMut::alloc()];
+            sine.update(&[], &mut src);
+            let inputs = [src[0].take().map(|b| b.into_shared())];
+            let mut outputs = [AudioBlockMut::alloc()];
+            filter.update(&inputs, &mut outputs);
+            peak = outputs[0]
+                .as_ref()
+                .unwrap()
+                .iter()
+                .map(|s| s.saturating_abs())
+                .max()
+                .unwrap();
+        }
+        peak
+    }
+
+    #[test]
+    fn biquad_passthrough_without_stages() {
+        reset_pool();
+        let mut filter = AudioFilterBiquad::new();
+        let mut block = AudioBlockMut::alloc().unwrap();
+        for (i, s) in block.iter_mut().enumerate() {
+            *s = (i as i16 - 64) * 500;
+        }
+        let inputs = [Some(block.into_shared())];
+        let mut outputs = [AudioBlockMut::alloc()];
+        filter.update(&inputs, &mut outputs);
+        assert_eq!(
+            &outputs[0].as_ref().unwrap()[..],
+            &inputs[0].as_ref().unwrap()[..]
+        );
+    }
+
+    #[test]
+    fn biquad_lowpass_sweep_attenuates_above_cutoff() {
+        reset_pool();
+        let mut filter = AudioFilterBiquad::new();
+        filter.set_lowpass(0, 1000.0, 0.707);
+
+        let pass = steady_state_peak(&mut filter, 200.0, 20);
+        assert!(pass > 31000, "200 Hz should pass, peak {}", pass);
+
+        let mut last = pass;
+        for &hz in &[2000.0, 4000.0, 8000.0, 16000.0] {
+            filter.reset();
+            let peak = steady_state_peak(&mut filter, hz, 20);
+            assert!(
+                peak < last,
+                "{} Hz ({}) not below previous ({})",
+                hz,
+                peak,
+                last
+            );
+            last = peak;
+        }
+
+        // 2nd-order rolloff: 8 kHz is 3 octaves up, about -36 dB
+        filter.reset();
+        let stop = steady_state_peak(&mut filter, 8000.0, 20);
+        assert!(
+            stop < 32767 / 40,
+            "8 kHz should be well attenuated, peak {}",
+            stop
+        );
+    }
+
+    #[test]
+    fn biquad_cascade_is_steeper() {
+        reset_pool();
+        let mut one = AudioFilterBiquad::new();
+        one.set_lowpass(0, 1000.0, 0.707);
+        let mut two = AudioFilterBiquad::new();
+        two.set_lowpass(0, 1000.0, 0.707);
+        two.set_lowpass(1, 1000.0, 0.707);
+
+        let p1 = steady_state_peak(&mut one, 4000.0, 20);
+        let p2 = steady_state_peak(&mut two, 4000.0, 20);
+        assert!(p2 < p1 / 5, "cascade {} vs single {}", p2, p1);
+    }
+
+    #[test]
+    fn biquad_highpass_and_notch() {
+        reset_pool();
+        let mut hp = AudioFilterBiquad::new();
+        hp.set_highpass(0, 2000.0, 0.707);
+        assert!(steady_state_peak(&mut hp, 200.0, 20) < 32767 / 50);
+        hp.reset();
+        assert!(steady_state_peak(&mut hp, 10000.0, 20) > 30000);
+
+        let mut notch = AudioFilterBiquad::new();
+        notch.set_notch(0, 1000.0, 2.0);
+        assert!(steady_state_peak(&mut notch, 1000.0, 40) < 32767 / 20);
+
+        let mut bp = AudioFilterBiquad::new();
+        bp.set_bandpass(0, 1000.0, 2.0);
+        assert!(steady_state_peak(&mut bp, 1000.0, 40) > 30000);
+    }
+
+    #[test]
+    fn biquad_out_of_range_stage_ignored() {
+        let mut filter = AudioFilterBiquad::new();
+        filter.set_coefficients(BIQUAD_MAX_STAGES, &[1 << 30, 0, 0, 0, 0]);
+        assert!(!filter.active.iter().any(|&a| a));
+    }
+}
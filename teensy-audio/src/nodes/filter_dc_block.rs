@@ -0,0 +1,150 @@
+//! DC-blocking filter node.
+//!
+//! Removes DC offset (e.g. from line-in bias or asymmetric waveshaping)
+//! before it reaches an output and turns into pops when audio starts or
+//! stops. Wraps the [`DcBlocker`] primitive.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::dsp::DcBlocker;
+use crate::node::AudioNode;
+
+/// One-pole DC blocker. Effect node: 1 input, 1 output.
+///
+/// Computes `y[n] = x[n] − x[n-1] + R·y[n-1]` per sample in fixed point.
+/// The pole `R` defaults to 0.995 (about 35 Hz at 44.1 kHz); raise it
+/// towards 1.0 to reach lower while leaving more bass alone.
+///
+/// # Example
+/// ```ignore
+/// let mut dc = AudioFilterDcBlock::new();
+/// dc.pole(0.999);
+/// ```
+pub struct AudioFilterDcBlock {
+    blocker: DcBlocker,
+}
+
+impl AudioFilterDcBlock {
+    /// Create a DC blocker with the default pole (0.995).
+    pub const fn new() -> Self {
+        AudioFilterDcBlock {
+            blocker: DcBlocker::new(),
+        }
+    }
+
+    /// Set the pole radius (0.0–0.9999, clamped).
+    pub fn pole(&mut self, pole: f32) {
+        self.blocker.set_pole(pole);
+    }
+}
+
+impl Default for AudioFilterDcBlock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioFilterDcBlock {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioFilterDcBlock";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        let input = match inputs[0] {
+            Some(ref b) => b,
+            None => {
+                // No input: start again from rest when the next block arrives
+                self.blocker.reset();
+                return;
+            }
+        };
+
+        let mut out = match outputs[0].take() {
+            Some(b) => b,
+            None => return,
+        };
+
+        for (dst, &src) in out.iter_mut().zip(input.iter()) {
+            *dst = self.blocker.process(src);
+        }
+
+        outputs[0] = Some(out);
+    }
+
+    fn reset(&mut self) {
+        self.blocker.reset();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::constants::{AUDIO_BLOCK_SAMPLES, AUDIO_SAMPLE_RATE_EXACT};
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Block `n` of a 1 kHz sine with amplitude `amp` riding on `offset`.
+    fn biased_sine_block(n: usize, amp: f32, offset: f32) -> AudioBlockRef {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        for (i, s) in block.iter_mut().enumerate() {
+            let t = (n * AUDIO_BLOCK_SAMPLES + i) as f32 / AUDIO_SAMPLE_RATE_EXACT;
+            let phase = 2.0 * core::f32::consts::PI * 1000.0 * t;
+            *s = (offset + amp * libm::sinf(phase)) as i16;
+        }
+        block.into_shared()
+    }
+
+    #[test]
+    fn dc_block_removes_offset_keeps_ac() {
+        reset_pool();
+        let mut dc = AudioFilterDcBlock::new();
+
+        let mut sum = 0.0;
+        let mut peak = 0;
+        for n in 0..100 {
+            let inputs = [Some(biased_sine_block(n, 8000.0, 10000.0))];
+            let mut outputs = [AudioBlockMut::alloc()];
+            dc.update(&inputs, &mut outputs);
+            let out = outputs[0].take().unwrap();
+            // Measure once settled, over ~29 whole cycles of the sine
+            if n >= 90 {
+                sum += out.iter().map(|&s| s as f32).sum::<f32>();
+                peak = peak.max(out.iter().map(|&s| s.saturating_abs()).max().unwrap());
+            }
+        }
+
+        let mean = sum / (10 * AUDIO_BLOCK_SAMPLES) as f32;
+        assert!(mean.abs() < 100.0, "offset remains: mean {}", mean);
+        // 1 kHz is far above the cutoff: amplitude within ~1%
+        assert!(
+            (7900..=8100).contains(&peak),
+            "AC amplitude changed: {}",
+            peak
+        );
+    }
+
+    #[test]
+    fn dc_block_missing_input_resets_history() {
+        reset_pool();
+        let mut dc = AudioFilterDcBlock::new();
+        let run = |dc: &mut AudioFilterDcBlock| {
+            let mut block = AudioBlockMut::alloc().unwrap();
+            block.fill(5000);
+            let mut outputs = [AudioBlockMut::alloc()];
+            dc.update(&[Some(block.into_shared())], &mut outputs);
+            outputs[0].take().unwrap()[0]
+        };
+
+        assert!(run(&mut dc) > 4900, "step edge passes");
+        for _ in 0..50 {
+            run(&mut dc);
+        }
+        assert!(run(&mut dc).abs() <= 1, "DC settled out");
+
+        // Silence in between: the next step starts from a clean state
+        dc.update(&[None], &mut [AudioBlockMut::alloc()]);
+        assert!(run(&mut dc) > 4900, "history was reset");
+    }
+}
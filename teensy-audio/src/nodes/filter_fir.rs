@@ -0,0 +1,211 @@
+//! Finite impulse response filter.
+//!
+//! Port of `TeensyAudio/filter_fir.cpp`. Each output sample is the dot
+//! product of the coefficients with the most recent `TAPS` input samples,
+//! so any linear-phase response can be applied at a cost of one
+//! multiply-accumulate per tap per sample.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::dsp::intrinsics::{multiply_accumulate_32x16b, saturate16};
+use crate::node::AudioNode;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum FirMode {
+    /// No output.
+    Off,
+    /// Input copied straight through.
+    Passthrough,
+    /// Filtering with the stored coefficients.
+    Filter,
+}
+
+/// FIR filter. Effect node: 1 input, 1 output.
+///
+/// `TAPS` (at least 1) is the number of coefficients. Coefficients are Q15
+/// (32767 ≈ 1.0) and the products are summed with 9 bits of headroom, so
+/// even a full-scale kernel of up to 512 taps cannot overflow before the
+/// final saturation to 16 bits.
+///
+/// Starts [`off()`](Self::off), producing no output, like the C++ before
+/// `begin()`. A missing input block clears the history and drops the
+/// output.
+///
+/// # Example
+/// ```ignore
+/// let mut fir: AudioFilterFIR<64> = AudioFilterFIR::new();
+/// fir.set_coefficients(&LOWPASS_64);
+/// ```
+pub struct AudioFilterFIR<const TAPS: usize> {
+    coefficients: [i16; TAPS],
+    /// Last `TAPS` input samples, as a ring ending at `head`.
+    history: [i16; TAPS],
+    /// Index of the newest sample in `history`.
+    head: usize,
+    mode: FirMode,
+}
+
+impl<const TAPS: usize> AudioFilterFIR<TAPS> {
+    /// There must be at least one tap.
+    const CHECK_SIZE: () = assert!(TAPS > 0, "AudioFilterFIR: TAPS must be at least 1");
+
+    /// Create a new filter, off, with all coefficients zero.
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::CHECK_SIZE;
+        AudioFilterFIR {
+            coefficients: [0; TAPS],
+            history: [0; TAPS],
+            head: 0,
+            mode: FirMode::Off,
+        }
+    }
+
+    /// Load Q15 coefficients and start filtering.
+    ///
+    /// `coefficients[0]` weights the newest sample. The history is kept,
+    /// so a new kernel takes effect without a gap.
+    pub fn set_coefficients(&mut self, coefficients: &[i16; TAPS]) {
+        self.coefficients = *coefficients;
+        self.mode = FirMode::Filter;
+    }
+
+    /// Pass the input through unfiltered.
+    pub fn passthrough(&mut self) {
+        self.mode = FirMode::Passthrough;
+    }
+
+    /// Stop producing output. The coefficients are kept for the next
+    /// [`set_coefficients()`](Self::set_coefficients).
+    pub fn off(&mut self) {
+        self.mode = FirMode::Off;
+    }
+}
+
+impl<const TAPS: usize> Default for AudioFilterFIR<TAPS> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const TAPS: usize> AudioNode for AudioFilterFIR<TAPS> {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioFilterFIR";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        let Some(mut out) = outputs[0].take() else {
+            return;
+        };
+        let Some(input) = inputs[0].as_ref() else {
+            // No input: start again from silence when the next block arrives
+            self.reset();
+            return;
+        };
+
+        match self.mode {
+            FirMode::Off => return,
+            FirMode::Passthrough => out.copy_from_slice(&input[..]),
+            FirMode::Filter => {
+                for (dst, &x) in out.iter_mut().zip(input.iter()) {
+                    self.head = if self.head + 1 == TAPS {
+                        0
+                    } else {
+                        self.head + 1
+                    };
+                    self.history[self.head] = x;
+
+                    // Newest to oldest: back from `head`, then back from the end
+                    let (newer, older) = self.history.split_at(self.head + 1);
+                    let samples = newer.iter().rev().chain(older.iter().rev());
+                    let mut sum = 0i32;
+                    for (&h, &s) in self.coefficients.iter().zip(samples) {
+                        // (s << 8) · h >> 16: the Q30 product in Q22
+                        sum = multiply_accumulate_32x16b(sum, (s as i32) << 8, h as u16 as u32);
+                    }
+                    *dst = saturate16(sum >> 7);
+                }
+            }
+        }
+
+        outputs[0] = Some(out);
+    }
+
+    /// Clear the history, as if the input had been silent.
+    fn reset(&mut self) {
+        self.history = [0; TAPS];
+        self.head = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    fn run<const TAPS: usize>(
+        fir: &mut AudioFilterFIR<TAPS>,
+        input: AudioBlockRef,
+    ) -> Option<AudioBlockMut> {
+        let mut outputs = [AudioBlockMut::alloc()];
+        fir.update(&[Some(input)], &mut outputs);
+        outputs[0].take()
+    }
+
+    fn ramp_block(start: i16) -> AudioBlockRef {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        for (i, s) in block.iter_mut().enumerate() {
+            *s = start + 100 * i as i16;
+        }
+        block.into_shared()
+    }
+
+    #[test]
+    fn fir_unit_coefficient_and_modes() {
+        reset_pool();
+        let mut fir: AudioFilterFIR<8> = AudioFilterFIR::new();
+        assert!(run(&mut fir, ramp_block(-6000)).is_none(), "off by default");
+
+        // Unity at tap 0 (32767 ≈ 1.0 loses at most 1 LSB)
+        let mut unit = [0i16; 8];
+        unit[0] = 32767;
+        fir.set_coefficients(&unit);
+        let input = ramp_block(-6000);
+        let out = run(&mut fir, input.clone()).unwrap();
+        for (&y, &x) in out.iter().zip(input.iter()) {
+            assert!((y as i32 - x as i32).abs() <= 1, "{} -> {}", x, y);
+        }
+        drop(out);
+
+        fir.passthrough();
+        assert_eq!(run(&mut fir, input.clone()).unwrap()[..], input[..]);
+        fir.off();
+        assert!(run(&mut fir, input).is_none());
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn fir_moving_average_smooths_step() {
+        reset_pool();
+        // Four taps of 1/4: a step rises in four equal stairs
+        let mut fir: AudioFilterFIR<4> = AudioFilterFIR::new();
+        fir.set_coefficients(&[8192; 4]);
+
+        let mut before = AudioBlockMut::alloc().unwrap();
+        before.fill(8000);
+        drop(run(&mut fir, before.into_shared()));
+
+        // The step lands at the first sample of a block, so the stairs
+        // start from the history carried over from the previous one
+        let mut step = AudioBlockMut::alloc().unwrap();
+        step.fill(20000);
+        let out = run(&mut fir, step.into_shared()).unwrap();
+        assert_eq!(out[..5], [11000, 14000, 17000, 20000, 20000]);
+        assert!(out.iter().skip(3).all(|&s| s == 20000));
+        drop(out);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+}
@@ -0,0 +1,346 @@
+//! State-variable (Chamberlin) filter with lowpass, bandpass and highpass
+//! outputs.
+//!
+//! Port of `TeensyAudio/filter_variable.cpp`. The filter runs 2× oversampled
+//! so it stays stable up to `AUDIO_SAMPLE_RATE_EXACT / 2.5`. A second input
+//! sweeps the corner frequency by up to ±7 octaves around the set frequency.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::{AUDIO_BLOCK_SAMPLES, AUDIO_SAMPLE_RATE_EXACT};
+use crate::dsp::intrinsics::{mul_32x32_rshift32_rounded, signed_saturate_rshift};
+use crate::node::AudioNode;
+
+/// Highest usable corner frequency (the oversampled filter's stability limit).
+const MAX_FREQUENCY: f32 = AUDIO_SAMPLE_RATE_EXACT / 2.5;
+
+/// Lowest corner frequency accepted by [`AudioFilterStateVariable::frequency`].
+const MIN_FREQUENCY: f32 = 20.0;
+
+/// Q30 coefficient multiply as used by the C++ `MULT()` macro.
+#[inline(always)]
+fn mult(a: i32, b: i32) -> i32 {
+    mul_32x32_rshift32_rounded(a, b) << 2
+}
+
+/// State-variable filter. 2 inputs (signal, frequency control),
+/// 3 outputs (lowpass, bandpass, highpass).
+///
+/// The control input is read as a Q15 value in [-1.0, 1.0]; once per block
+/// its mean shifts the corner to `frequency × 2^(control × octaves)`, where
+/// `octaves` is set by [`octave_control()`](Self::octave_control). With no
+/// control block the set frequency is used.
+///
+/// # Example
+/// ```ignore
+/// let mut svf = AudioFilterStateVariable::new();
+/// svf.frequency(800.0);
+/// svf.resonance(2.0);
+/// svf.octave_control(2.5);
+/// ```
+pub struct AudioFilterStateVariable {
+    /// Corner frequency in Hz, before control modulation.
+    frequency: f32,
+    /// `2·sin(π·f/(2·fs))` in Q30 for the unmodulated corner.
+    fmult: i32,
+    /// `1/q` in Q30.
+    damp: i32,
+    /// Octave range of the control input.
+    octaves: f32,
+    state_inputprev: i32,
+    state_lowpass: i32,
+    state_bandpass: i32,
+}
+
+impl AudioFilterStateVariable {
+    /// Create a new filter at 1 kHz, Q = 0.707, with a 1-octave control range.
+    pub fn new() -> Self {
+        let mut svf = AudioFilterStateVariable {
+            frequency: 0.0,
+            fmult: 0,
+            damp: 0,
+            octaves: 1.0,
+            state_inputprev: 0,
+            state_lowpass: 0,
+            state_bandpass: 0,
+        };
+        svf.frequency(1000.0);
+        svf.resonance(0.707);
+        svf
+    }
+
+    /// Set the corner frequency in Hz. Clamped to 20 Hz …
+    /// `AUDIO_SAMPLE_RATE_EXACT / 2.5`.
+    pub fn frequency(&mut self, freq: f32) {
+        self.frequency = freq.clamp(MIN_FREQUENCY, MAX_FREQUENCY);
+        self.fmult = frequency_to_fmult(self.frequency);
+    }
+
+    /// Set the resonance (Q). Clamped to 0.7 … 5.0; higher values ring.
+    pub fn resonance(&mut self, q: f32) {
+        let q = q.clamp(0.7, 5.0);
+        self.damp = ((1.0 / q) * 1_073_741_824.0) as i32;
+    }
+
+    /// Set how many octaves a full-scale control signal moves the corner.
+    /// Clamped to 0.0 … 6.9999.
+    pub fn octave_control(&mut self, octaves: f32) {
+        self.octaves = octaves.clamp(0.0, 6.9999);
+    }
+
+    /// Corner coefficient for this block, applying the control input.
+    fn block_fmult(&self, control: Option<&AudioBlockRef>) -> i32 {
+        let control = match control {
+            Some(c) if self.octaves > 0.0 => c,
+            _ => return self.fmult,
+        };
+        let sum: i32 = control.iter().map(|&s| s as i32).sum();
+        let mean = sum as f32 / (AUDIO_BLOCK_SAMPLES as f32 * 32768.0);
+        let freq = self.frequency * libm::exp2f(mean * self.octaves);
+        frequency_to_fmult(freq.clamp(MIN_FREQUENCY, MAX_FREQUENCY))
+    }
+}
+
+impl Default for AudioFilterStateVariable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `2·sin(π·f/(2·fs))` in Q30 — the 2× oversampled Chamberlin coefficient.
+fn frequency_to_fmult(freq: f32) -> i32 {
+    let w = freq * (core::f32::consts::PI / (AUDIO_SAMPLE_RATE_EXACT * 2.0));
+    (libm::sinf(w) * 2_147_483_647.0) as i32
+}
+
+/// Store `value` at `i` if the output block exists.
+#[inline(always)]
+fn put(block: &mut Option<AudioBlockMut>, i: usize, value: i32) {
+    if let Some(b) = block {
+        b[i] = value as i16;
+    }
+}
+
+impl AudioNode for AudioFilterStateVariable {
+    const NUM_INPUTS: usize = 2;
+    const NUM_OUTPUTS: usize = 3;
+    const NAME: &'static str = "AudioFilterStateVariable";
+    const OUTPUT_NAMES: &'static [&'static str] = &["lowpass", "bandpass", "highpass"];
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        let mut lp = outputs[0].take();
+        let mut bp = outputs[1].take();
+        let mut hp = outputs[2].take();
+
+        let input = match inputs[0] {
+            Some(ref b) => b,
+            // No signal: the taken output blocks drop here, back to the pool
+            None => return,
+        };
+
+        let fmult = self.block_fmult(inputs[1].as_ref());
+        let damp = self.damp;
+        let mut inputprev = self.state_inputprev;
+        let mut lowpass = self.state_lowpass;
+        let mut bandpass = self.state_bandpass;
+
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            let input = (input[i] as i32) << 12;
+
+            // First pass at the interpolated midpoint
+            lowpass += mult(fmult, bandpass);
+            let highpass = ((input + inputprev) >> 1) - lowpass - mult(damp, bandpass);
+            inputprev = input;
+            bandpass += mult(fmult, highpass);
+            let lowpasstmp = lowpass;
+            let bandpasstmp = bandpass;
+
+            // Second pass at the real sample
+            lowpass += mult(fmult, bandpass);
+            let highpass = input - lowpass - mult(damp, bandpass);
+            bandpass += mult(fmult, highpass);
+
+            put(
+                &mut lp,
+                i,
+                signed_saturate_rshift::<16, 13>(lowpass + lowpasstmp),
+            );
+            put(
+                &mut bp,
+                i,
+                signed_saturate_rshift::<16, 13>(bandpass + bandpasstmp),
+            );
+            put(&mut hp, i, signed_saturate_rshift::<16, 12>(highpass));
+        }
+
+        self.state_inputprev = inputprev;
+        self.state_lowpass = lowpass;
+        self.state_bandpass = bandpass;
+
+        outputs[0] = lp;
+        outputs[1] = bp;
+        outputs[2] = hp;
+    }
+
+    fn reset(&mut self) {
+        self.state_inputprev = 0;
+        self.state_lowpass = 0;
+        self.state_bandpass = 0;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Square wave of `period` samples at ±`level`, continuing from `phase`.
+    fn square_block(period: usize, level: i16, phase: &mut usize) -> AudioBlockRef {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        for s in block.iter_mut() {
+            *s = if *phase < period / 2 { level } else { -level };
+            *phase = (*phase + 1) % period;
+        }
+        block.into_shared()
+    }
+
+    fn dc_block(value: i16) -> AudioBlockRef {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block.fill(value);
+        block.into_shared()
+    }
+
+    fn energy(samples: impl Iterator<Item = i32>) -> i64 {
+        samples.map(|s| (s as i64) * (s as i64)).sum()
+    }
+
+    #[test]
+    fn svf_lowpass_and_highpass_are_complementary() {
+        reset_pool();
+        let mut svf = AudioFilterStateVariable::new();
+        svf.frequency(2000.0);
+        svf.resonance(0.707);
+
+        // ~200 Hz square: fundamental well below the corner, edges well above
+        let mut phase = 0;
+        let (mut input_e, mut lp_e, mut hp_e, mut residual_e) = (0, 0, 0, 0);
+        for n in 0..20 {
+            let input = square_block(220, 10000, &mut phase);
+            let inputs = [Some(input.clone()), None];
+            let mut outputs = [
+                AudioBlockMut::alloc(),
+                AudioBlockMut::alloc(),
+                AudioBlockMut::alloc(),
+            ];
+            svf.update(&inputs, &mut outputs);
+            if n < 4 {
+                continue; // let the filter settle
+            }
+            let lp = outputs[0].as_ref().unwrap();
+            let hp = outputs[2].as_ref().unwrap();
+            input_e += energy(input.iter().map(|&s| s as i32));
+            lp_e += energy(lp.iter().map(|&s| s as i32));
+            hp_e += energy(hp.iter().map(|&s| s as i32));
+            residual_e += energy(
+                (0..AUDIO_BLOCK_SAMPLES).map(|i| input[i] as i32 - lp[i] as i32 - hp[i] as i32),
+            );
+        }
+
+        // Each output carries a real share of the signal...
+        assert!(lp_e > input_e / 2, "lowpass energy {} of {}", lp_e, input_e);
+        assert!(
+            hp_e > input_e / 50,
+            "highpass energy {} of {}",
+            hp_e,
+            input_e
+        );
+        // ...and together they roughly rebuild it
+        assert!(
+            residual_e < input_e / 5,
+            "residual {} of {}",
+            residual_e,
+            input_e
+        );
+    }
+
+    #[test]
+    fn svf_dc_goes_to_lowpass() {
+        reset_pool();
+        let mut svf = AudioFilterStateVariable::new();
+        let mut last = [0i16; 3];
+        for _ in 0..20 {
+            let inputs = [Some(dc_block(8000)), None];
+            let mut outputs = [
+                AudioBlockMut::alloc(),
+                AudioBlockMut::alloc(),
+                AudioBlockMut::alloc(),
+            ];
+            svf.update(&inputs, &mut outputs);
+            for (l, out) in last.iter_mut().zip(outputs.iter()) {
+                *l = out.as_ref().unwrap()[AUDIO_BLOCK_SAMPLES - 1];
+            }
+        }
+        assert!((last[0] as i32 - 8000).abs() < 50, "lowpass {}", last[0]);
+        assert!(last[1].abs() < 50, "bandpass {}", last[1]);
+        assert!(last[2].abs() < 50, "highpass {}", last[2]);
+    }
+
+    #[test]
+    fn svf_control_input_raises_cutoff() {
+        reset_pool();
+        let run = |control: Option<i16>| {
+            let mut svf = AudioFilterStateVariable::new();
+            svf.frequency(500.0);
+            svf.octave_control(3.0);
+            let mut phase = 0;
+            let mut hp_e = 0;
+            for _ in 0..10 {
+                let inputs = [
+                    Some(square_block(64, 10000, &mut phase)),
+                    control.map(dc_block),
+                ];
+                let mut outputs = [
+                    AudioBlockMut::alloc(),
+                    AudioBlockMut::alloc(),
+                    AudioBlockMut::alloc(),
+                ];
+                svf.update(&inputs, &mut outputs);
+                hp_e += energy(outputs[2].as_ref().unwrap().iter().map(|&s| s as i32));
+            }
+            hp_e
+        };
+
+        // Full-scale control moves the corner up 3 octaves: less left for the highpass
+        let base = run(None);
+        let swept = run(Some(32767));
+        assert!(swept < base / 2, "swept {} vs base {}", swept, base);
+    }
+
+    #[test]
+    fn svf_missing_input_releases_outputs() {
+        reset_pool();
+        let mut svf = AudioFilterStateVariable::new();
+        let inputs: [Option<AudioBlockRef>; 2] = [None, Some(dc_block(1000))];
+        let mut outputs = [
+            AudioBlockMut::alloc(),
+            AudioBlockMut::alloc(),
+            AudioBlockMut::alloc(),
+        ];
+        svf.update(&inputs, &mut outputs);
+        assert!(outputs.iter().all(|o| o.is_none()));
+        drop(inputs);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn svf_output_names() {
+        use crate::node::output_port_index;
+        const NAMES: &[&str] = <AudioFilterStateVariable as AudioNode>::OUTPUT_NAMES;
+        assert_eq!(output_port_index(NAMES, "lowpass"), 0);
+        assert_eq!(output_port_index(NAMES, "bandpass"), 1);
+        assert_eq!(output_port_index(NAMES, "highpass"), 2);
+    }
+}
@@ -4,12 +4,7 @@
 //! Uses const generic `N` instead of the C++ hardcoded 4 channels.
 
 use crate::block::{AudioBlockMut, AudioBlockRef};
-use crate::constants::AUDIO_BLOCK_SAMPLES;
-use crate::dsp::intrinsics::saturate16;
-use crate::node::AudioNode;
-
-/// Fixed-point unity gain: 1.0 in Q16.16 format = 65536.
-const MULTI_UNITYGAIN: i32 = 65536;
+use crate::node::{sum_blocks, AudioNode, UNITY_GAIN as MULTI_UNITYGAIN};
 
 /// N-channel mixer. Mixes N input channels into a single mono output with per-channel gain.
 ///
@@ -24,6 +19,13 @@ const MULTI_UNITYGAIN: i32 = 65536;
 pub struct AudioMixer<const N: usize> {
     /// Per-channel gain in Q16.16 fixed-point. 65536 = unity (1.0).
     multiplier: [i32; N],
+    /// Whether to keep a copy of each output block for `last_output()`.
+    retain_output: bool,
+    /// Whether to drop the output block, rather than send zeros, when
+    /// every input is `None`.
+    drop_silence: bool,
+    /// Copy of the most recent output block, if retained.
+    last: Option<AudioBlockRef>,
 }
 
 impl<const N: usize> AudioMixer<N> {
@@ -31,6 +33,9 @@ impl<const N: usize> AudioMixer<N> {
     pub const fn new() -> Self {
         AudioMixer {
             multiplier: [MULTI_UNITYGAIN; N],
+            retain_output: false,
+            drop_silence: false,
+            last: None,
         }
     }
 
@@ -51,73 +56,80 @@ impl<const N: usize> AudioMixer<N> {
         };
         self.multiplier[channel] = (clamped * 65536.0) as i32;
     }
-}
 
-/// Apply gain to a block in-place: `data[i] = saturate16((data[i] * mult) >> 16)`.
-fn apply_gain(data: &mut [i16; AUDIO_BLOCK_SAMPLES], mult: i32) {
-    for sample in data.iter_mut() {
-        let val = ((*sample as i64) * (mult as i64)) >> 16;
-        *sample = saturate16(val as i32);
+    /// Set a channel's gain directly as Q16.16 (65536 = unity), without
+    /// float math.
+    ///
+    /// Negative values invert the channel. Out-of-range channels are
+    /// ignored, as in [`gain()`](Self::gain).
+    pub fn multiplier_raw(&mut self, channel: usize, multiplier: i32) {
+        if channel >= N {
+            return;
+        }
+        self.multiplier[channel] = multiplier;
     }
-}
 
-/// Apply gain to `src` and saturating-add into `dst`.
-fn apply_gain_then_add(
-    dst: &mut [i16; AUDIO_BLOCK_SAMPLES],
-    src: &[i16; AUDIO_BLOCK_SAMPLES],
-    mult: i32,
-) {
-    if mult == MULTI_UNITYGAIN {
-        // Fast path: just saturating-add
-        for (d, &s) in dst.iter_mut().zip(src.iter()) {
-            *d = saturate16(*d as i32 + s as i32);
-        }
-    } else {
-        for (d, &s) in dst.iter_mut().zip(src.iter()) {
-            let gained = ((s as i64) * (mult as i64)) >> 16;
-            let gained_sat = saturate16(gained as i32);
-            *d = saturate16(*d as i32 + gained_sat as i32);
+    /// Keep a copy of each output block for [`last_output()`](Self::last_output).
+    ///
+    /// Lets a meter or sidechain observe the mix bus without an extra graph
+    /// connection. While enabled the mixer holds one extra pool block.
+    /// Disabling releases it.
+    pub fn retain_output(&mut self, enable: bool) {
+        self.retain_output = enable;
+        if !enable {
+            self.last = None;
         }
     }
+
+    /// With no inputs present, return the output block to the pool instead
+    /// of filling it with zeros.
+    ///
+    /// Off by default, so downstream nodes always see a block. Turning it
+    /// on frees a block per silent mixer for memory-hungry nodes (delays,
+    /// reverbs) that follow; they treat the missing block as silence.
+    pub fn drop_silence(&mut self, enable: bool) {
+        self.drop_silence = enable;
+    }
+
+    /// The most recent output block, if `retain_output(true)` is set and the
+    /// last `update()` produced one.
+    pub fn last_output(&self) -> Option<AudioBlockRef> {
+        self.last.clone()
+    }
 }
 
 impl<const N: usize> AudioNode for AudioMixer<N> {
     const NUM_INPUTS: usize = N;
     const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioMixer";
 
     fn update(
         &mut self,
         inputs: &[Option<AudioBlockRef>],
         outputs: &mut [Option<AudioBlockMut>],
     ) {
+        // Release the previous tap first so its slot is available again.
+        self.last = None;
+
         let out_block = match outputs[0].take() {
             Some(b) => b,
             None => return,
         };
 
         let mut out = out_block;
-        let mut initialized = false;
-
-        for ch in 0..N {
-            if let Some(ref input) = inputs[ch] {
-                let mult = self.multiplier[ch];
-                if !initialized {
-                    // First active channel: copy (with gain) into output buffer
-                    out.copy_from_slice(&input[..]);
-                    if mult != MULTI_UNITYGAIN {
-                        apply_gain(&mut out, mult);
-                    }
-                    initialized = true;
-                } else {
-                    // Subsequent channels: gain + accumulate
-                    apply_gain_then_add(&mut out, input, mult);
-                }
-            }
+        let mut active = inputs[..N].iter().zip(self.multiplier).filter(|(b, _)| b.is_some());
+        match (active.next(), active.next()) {
+            (None, _) if self.drop_silence => return,
+            // A lone channel at unity: copy rather than zero-fill and add
+            (Some((Some(only), MULTI_UNITYGAIN)), None) => out.copy_from_slice(&only[..]),
+            _ => sum_blocks(&inputs[..N], &self.multiplier, &mut out),
         }
 
-        if !initialized {
-            // No active inputs: output silence
-            out.fill(0);
+        if self.retain_output {
+            self.last = AudioBlockMut::alloc().map(|mut tap| {
+                tap.copy_from_slice(&out[..]);
+                tap.into_shared()
+            });
         }
 
         outputs[0] = Some(out);
@@ -128,6 +140,7 @@ impl<const N: usize> AudioNode for AudioMixer<N> {
 mod tests {
     use super::*;
     use crate::block::pool::POOL;
+    use crate::constants::AUDIO_BLOCK_SAMPLES;
 
     fn reset_pool() {
         POOL.reset();
@@ -270,4 +283,99 @@ mod tests {
         let out = outputs[0].as_ref().unwrap();
         assert!((out[0] - 10000).abs() <= 1);
     }
+
+    #[test]
+    fn mixer_last_output_matches_output() {
+        reset_pool();
+        let mut mixer = AudioMixer::<2>::new();
+        assert!(mixer.last_output().is_none());
+        mixer.retain_output(true);
+        mixer.gain(1, 0.5);
+
+        let input0 = alloc_block_with(&[1000, -2000, 3000]);
+        let input1 = alloc_block_with(&[4000, 4000, -4000]);
+        let output = AudioBlockMut::alloc().unwrap();
+        let mut outputs = [Some(output)];
+        let inputs = [Some(input0.into_shared()), Some(input1.into_shared())];
+
+        mixer.update(&inputs, &mut outputs);
+
+        let tap = mixer.last_output().unwrap();
+        let out = outputs[0].as_ref().unwrap();
+        assert_eq!(&tap[..], &out[..]);
+        assert_eq!(tap[0], 3000);
+
+        // Inputs, output, and the tap (held by the mixer plus our clone).
+        drop(inputs);
+        drop(outputs);
+        drop(tap);
+        assert_eq!(POOL.allocated_count(), 1);
+
+        // A further cycle replaces, not accumulates, the tap block.
+        let mut outputs = [AudioBlockMut::alloc()];
+        let inputs: [Option<AudioBlockRef>; 2] = [None, None];
+        mixer.update(&inputs, &mut outputs);
+        drop(outputs);
+        assert_eq!(POOL.allocated_count(), 1);
+
+        mixer.retain_output(false);
+        assert!(mixer.last_output().is_none());
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn mixer_raw_multiplier_matches_gain() {
+        let mut float = AudioMixer::<4>::new();
+        let mut raw = AudioMixer::<4>::new();
+        for (level, q16) in [(0.0, 0), (0.5, 32768), (1.0, 65536), (-0.25, -16384), (2.5, 163840)] {
+            float.gain(0, level);
+            raw.multiplier_raw(0, q16);
+            assert_eq!(raw.multiplier[0], float.multiplier[0], "gain {}", level);
+        }
+        raw.multiplier_raw(4, 0);
+        assert_eq!(raw.multiplier, [163840, 65536, 65536, 65536]);
+    }
+
+    #[test]
+    fn mixer_drop_silence_frees_block() {
+        reset_pool();
+        let inputs: [Option<AudioBlockRef>; 4] = [None, None, None, None];
+
+        // Default: an all-silent mix still sends zeros
+        let mut mixer = AudioMixer::<4>::new();
+        let mut outputs = [AudioBlockMut::alloc()];
+        mixer.update(&inputs, &mut outputs);
+        assert!(outputs[0].as_ref().unwrap().iter().all(|&s| s == 0));
+        assert_eq!(POOL.allocated_count(), 1);
+        drop(outputs);
+
+        mixer.drop_silence(true);
+        let mut outputs = [AudioBlockMut::alloc()];
+        mixer.update(&inputs, &mut outputs);
+        assert!(outputs[0].is_none());
+        assert_eq!(POOL.allocated_count(), 0);
+
+        // Any input present still mixes as usual
+        let one = [None, Some(alloc_block_with(&[1234]).into_shared()), None, None];
+        let mut outputs = [AudioBlockMut::alloc()];
+        mixer.update(&one, &mut outputs);
+        assert_eq!(outputs[0].as_ref().unwrap()[0], 1234);
+    }
+
+    #[test]
+    fn mixer_single_channel_fast_path_matches_sum() {
+        reset_pool();
+        let input = alloc_block_with(&[30000, -32768, 5, -5]).into_shared();
+        let mut mixer = AudioMixer::<4>::new();
+        for gain in [1.0, 0.5] {
+            mixer.gain(2, gain);
+            let inputs = [None, None, Some(input.clone()), None];
+            let mut outputs = [AudioBlockMut::alloc()];
+            mixer.update(&inputs, &mut outputs);
+
+            let mut expected = AudioBlockMut::alloc().unwrap();
+            sum_blocks(&inputs, &mixer.multiplier, &mut expected);
+            assert_eq!(outputs[0].as_ref().unwrap()[..], expected[..], "gain {}", gain);
+        }
+    }
 }
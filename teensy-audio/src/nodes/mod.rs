@@ -5,18 +5,72 @@
 
 mod mixer;
 mod amplifier;
+mod amplifier_stereo;
+mod panner;
 mod synth_sine;
+mod synth_sine_modulated;
+mod synth_waveform;
 mod synth_dc;
+mod synth_silence;
+mod synth_tonesweep;
+mod synth_karplus_strong;
+mod synth_simple_drum;
+mod synth_wavetable;
 mod effect_fade;
+mod effect_mute;
 mod effect_envelope;
+mod effect_gain_mix;
+mod effect_crossfade;
+mod effect_multiply;
+mod effect_digital_combine;
+mod effect_delay;
+mod effect_chorus_stereo;
+mod effect_waveshaper;
+mod effect_granular;
+mod filter_biquad;
+mod filter_dc_block;
+mod filter_fir;
+mod filter_variable;
 mod analyze_peak;
 mod analyze_rms;
+mod analyze_fft256;
+mod analyze_tone_detect;
+mod analyze_note_frequency;
+mod analyze_scope;
+mod analyze_trigger;
 
 pub use mixer::AudioMixer;
 pub use amplifier::AudioAmplifier;
+pub use amplifier_stereo::AudioAmplifierStereo;
+pub use panner::AudioPanner;
 pub use synth_sine::AudioSynthSine;
-pub use synth_dc::AudioSynthWaveformDc;
-pub use effect_fade::AudioEffectFade;
-pub use effect_envelope::{AudioEffectEnvelope, EnvelopeState};
+pub use synth_sine_modulated::AudioSynthSineModulated;
+pub use synth_waveform::{AudioSynthWaveform, WaveformShape};
+pub use synth_dc::{AudioSynthWaveformDc, AudioSynthWaveformDcMulti};
+pub use synth_silence::AudioSynthSilence;
+pub use synth_tonesweep::AudioSynthToneSweep;
+pub use synth_karplus_strong::AudioSynthKarplusStrong;
+pub use synth_simple_drum::AudioSynthSimpleDrum;
+pub use synth_wavetable::{AudioSynthWavetable, WAVETABLE_MAX_LEN};
+pub use effect_fade::{AudioEffectFade, FadeCurve};
+pub use effect_mute::AudioEffectMute;
+pub use effect_envelope::{AudioEffectEnvelope, EnvelopeCurve, EnvelopeState};
+pub use effect_gain_mix::AudioEffectGainMix;
+pub use effect_crossfade::AudioEffectCrossfade;
+pub use effect_multiply::AudioEffectMultiply;
+pub use effect_digital_combine::{AudioEffectDigitalCombine, CombineMode};
+pub use effect_delay::{AudioEffectDelay, DelayOutputMode, DELAY_TAPS};
+pub use effect_chorus_stereo::{AudioEffectChorusStereo, CHORUS_BUFFER_SAMPLES};
+pub use effect_waveshaper::{AudioEffectWaveshaper, WAVESHAPER_MAX_POINTS};
+pub use effect_granular::AudioEffectGranular;
+pub use filter_biquad::{AudioFilterBiquad, BIQUAD_MAX_STAGES};
+pub use filter_dc_block::AudioFilterDcBlock;
+pub use filter_fir::AudioFilterFIR;
+pub use filter_variable::AudioFilterStateVariable;
 pub use analyze_peak::AudioAnalyzePeak;
 pub use analyze_rms::AudioAnalyzeRms;
+pub use analyze_fft256::{AudioAnalyzeFFT256, FFT256_BINS};
+pub use analyze_tone_detect::AudioAnalyzeToneDetect;
+pub use analyze_note_frequency::AudioAnalyzeNoteFrequency;
+pub use analyze_scope::AudioAnalyzeScope;
+pub use analyze_trigger::AudioAnalyzeTrigger;
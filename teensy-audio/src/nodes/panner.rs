@@ -0,0 +1,176 @@
+//! Equal-power stereo panner.
+//!
+//! Places a mono signal in the stereo field with a sin/cos pan law, so the
+//! total power stays constant as the position moves.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::dsp::wavetables::SINE_TABLE;
+use crate::node::AudioNode;
+
+/// Stereo panner. Effect node: 1 input, 2 outputs (left, right).
+///
+/// Pan position `p` in -1.0 … +1.0 maps to the angle
+/// `θ = (p + 1) · π/4`; the left gain is `cos θ` and the right `sin θ`, both
+/// read from [`SINE_TABLE`]. At centre each side is -3 dB.
+///
+/// # Example
+/// ```ignore
+/// audio_graph! {
+///     pub struct Graph {
+///         synth: AudioSynthWaveform {},
+///         pan: AudioPanner { (synth, 0) },
+///         left: AudioRecordQueue { (pan.left) },
+///         right: AudioRecordQueue { (pan.right) },
+///     }
+/// }
+/// ```
+pub struct AudioPanner {
+    /// Left and right gains in Q15.
+    gain_left: i32,
+    gain_right: i32,
+}
+
+impl AudioPanner {
+    /// Create a new panner at centre.
+    pub fn new() -> Self {
+        let mut panner = AudioPanner {
+            gain_left: 0,
+            gain_right: 0,
+        };
+        panner.pan(0.0);
+        panner
+    }
+
+    /// Set the pan position: -1.0 = hard left, 0.0 = centre, 1.0 = hard
+    /// right. Values outside that range are clamped.
+    pub fn pan(&mut self, position: f32) {
+        // Quarter of the table (64 steps) spans 0 … π/2
+        let steps = (position.clamp(-1.0, 1.0) + 1.0) * 32.0;
+        self.gain_right = quarter_sine(steps);
+        self.gain_left = quarter_sine(64.0 - steps);
+    }
+}
+
+impl Default for AudioPanner {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// `sin(steps · 2π/256)` in Q15 for `steps` in 0 … 64, interpolated.
+fn quarter_sine(steps: f32) -> i32 {
+    let index = (steps as usize).min(63);
+    let frac = steps - index as f32;
+    let val1 = SINE_TABLE[index] as f32;
+    let val2 = SINE_TABLE[index + 1] as f32;
+    libm::roundf(val1 + (val2 - val1) * frac) as i32
+}
+
+impl AudioNode for AudioPanner {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = 2;
+    const NAME: &'static str = "AudioPanner";
+    const OUTPUT_NAMES: &'static [&'static str] = &["left", "right"];
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        let (mut left, mut right) = match (outputs[0].take(), outputs[1].take()) {
+            (Some(l), Some(r)) => (l, r),
+            _ => return,
+        };
+
+        let input = match inputs[0] {
+            Some(ref b) => b,
+            // No input: both outputs drop back to the pool
+            None => return,
+        };
+
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            let sample = input[i] as i32;
+            // Gains are at most 32767, so the products stay in range
+            left[i] = ((sample * self.gain_left) >> 15) as i16;
+            right[i] = ((sample * self.gain_right) >> 15) as i16;
+        }
+
+        outputs[0] = Some(left);
+        outputs[1] = Some(right);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    fn run(panner: &mut AudioPanner, value: i16) -> (i16, i16) {
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block.fill(value);
+        let inputs = [Some(block.into_shared())];
+        let mut outputs = [AudioBlockMut::alloc(), AudioBlockMut::alloc()];
+        panner.update(&inputs, &mut outputs);
+        (
+            outputs[0].as_ref().unwrap()[0],
+            outputs[1].as_ref().unwrap()[0],
+        )
+    }
+
+    #[test]
+    fn pan_centre_is_equal_minus_3db() {
+        reset_pool();
+        let mut panner = AudioPanner::new();
+        let (l, r) = run(&mut panner, 20000);
+        assert_eq!(l, r);
+        // 20000 × 0.7071 ≈ 14142
+        assert!((l as i32 - 14142).abs() <= 2, "centre level {}", l);
+    }
+
+    #[test]
+    fn pan_hard_left() {
+        reset_pool();
+        let mut panner = AudioPanner::new();
+        panner.pan(-1.0);
+        let (l, r) = run(&mut panner, 20000);
+        assert!((l as i32 - 20000).abs() <= 1, "left {}", l);
+        assert_eq!(r, 0);
+    }
+
+    #[test]
+    fn pan_hard_right() {
+        reset_pool();
+        let mut panner = AudioPanner::new();
+        panner.pan(1.0);
+        let (l, r) = run(&mut panner, -20000);
+        assert_eq!(l, 0);
+        assert!((r as i32 + 20000).abs() <= 1, "right {}", r);
+    }
+
+    #[test]
+    fn pan_is_equal_power() {
+        for position in [-0.8f32, -0.3, 0.2, 0.65] {
+            let mut panner = AudioPanner::new();
+            panner.pan(position);
+            let power = panner.gain_left.pow(2) + panner.gain_right.pow(2);
+            let unity = 32767i32.pow(2);
+            assert!(
+                (power - unity).abs() < unity / 500,
+                "pan {} power {}",
+                position,
+                power
+            );
+        }
+    }
+
+    #[test]
+    fn pan_no_input_frees_outputs() {
+        reset_pool();
+        let mut panner = AudioPanner::new();
+        let mut outputs = [AudioBlockMut::alloc(), AudioBlockMut::alloc()];
+        panner.update(&[None], &mut outputs);
+        assert!(outputs[0].is_none() && outputs[1].is_none());
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+}
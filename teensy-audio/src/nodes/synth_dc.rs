@@ -4,7 +4,7 @@
 //! and smooth ramping over a specified duration.
 
 use crate::block::{AudioBlockMut, AudioBlockRef};
-use crate::constants::{AUDIO_BLOCK_SAMPLES, AUDIO_SAMPLE_RATE_EXACT};
+use crate::constants::{ms_to_samples_truncated, AUDIO_BLOCK_SAMPLES};
 use crate::node::AudioNode;
 
 /// DC level source. Outputs a constant value every block.
@@ -25,6 +25,9 @@ pub struct AudioSynthWaveformDc {
     increment: i32,
     /// true = currently ramping toward `target`.
     transitioning: bool,
+    /// Ramp waiting for [`start_ramp()`](Self::start_ramp): target magnitude
+    /// and duration in samples.
+    armed: Option<(i32, i32)>,
 }
 
 impl AudioSynthWaveformDc {
@@ -35,6 +38,7 @@ impl AudioSynthWaveformDc {
             target: 0,
             increment: 0,
             transitioning: false,
+            armed: None,
         }
     }
 
@@ -50,34 +54,62 @@ impl AudioSynthWaveformDc {
         // Scale to match C++ behavior: magnitude uses upper 16 bits as sample value
         // C++ uses 2147418112.0 ≈ 0x7FFF0000 for 1.0
         self.magnitude = (clamped * 2_147_418_112.0) as i32;
+        self.target = self.magnitude;
         self.transitioning = false;
     }
 
     /// Set DC level with a smooth ramp over the specified duration.
     pub fn amplitude_ramp(&mut self, level: f32, milliseconds: f32) {
-        let clamped = if level > 1.0 {
-            1.0
-        } else if level < -1.0 {
-            -1.0
-        } else {
-            level
-        };
-        let new_target = (clamped * 2_147_418_112.0) as i32;
+        let (target, samples) = ramp_params(level, milliseconds);
+        self.begin_ramp(target, samples);
+    }
 
-        if milliseconds <= 0.0 {
-            self.magnitude = new_target;
-            self.transitioning = false;
-            return;
+    /// Prepare a ramp to `level` over `milliseconds` without starting it.
+    ///
+    /// The output holds its current level until [`start_ramp()`](Self::start_ramp)
+    /// is called, so several DC sources can be armed ahead of time and then
+    /// started together from the same event. Arming again replaces the
+    /// pending ramp.
+    pub fn arm_ramp(&mut self, level: f32, milliseconds: f32) {
+        self.armed = Some(ramp_params(level, milliseconds));
+    }
+
+    /// Start the armed ramp from the current level.
+    ///
+    /// With nothing armed, resumes a ramp halted by
+    /// [`stop_ramp()`](Self::stop_ramp) at its original rate.
+    pub fn start_ramp(&mut self) {
+        if let Some((target, samples)) = self.armed.take() {
+            self.begin_ramp(target, samples);
+        } else if self.magnitude != self.target && self.increment != 0 {
+            self.transitioning = true;
         }
+    }
+
+    /// Halt any ramp in progress, holding the current level.
+    pub fn stop_ramp(&mut self) {
+        self.transitioning = false;
+    }
 
-        let samples = (milliseconds * AUDIO_SAMPLE_RATE_EXACT / 1000.0) as i32;
+    /// Whether a ramp is currently running.
+    pub fn is_ramping(&self) -> bool {
+        self.transitioning
+    }
+
+    /// Whether a ramp is armed and waiting for [`start_ramp()`](Self::start_ramp).
+    pub fn is_armed(&self) -> bool {
+        self.armed.is_some()
+    }
+
+    /// Ramp from the current magnitude to `new_target` over `samples`.
+    fn begin_ramp(&mut self, new_target: i32, samples: i32) {
+        self.target = new_target;
         if samples <= 0 {
             self.magnitude = new_target;
             self.transitioning = false;
             return;
         }
 
-        self.target = new_target;
         let diff = (new_target as i64) - (self.magnitude as i64);
         self.increment = (diff / samples as i64) as i32;
         if self.increment == 0 {
@@ -90,6 +122,23 @@ impl AudioSynthWaveformDc {
     }
 }
 
+/// Convert a ramp request to a target magnitude and a duration in samples
+/// (0 = jump immediately).
+fn ramp_params(level: f32, milliseconds: f32) -> (i32, i32) {
+    let clamped = if level > 1.0 {
+        1.0
+    } else if level < -1.0 {
+        -1.0
+    } else {
+        level
+    };
+    let target = (clamped * 2_147_418_112.0) as i32;
+    if milliseconds <= 0.0 {
+        return (target, 0);
+    }
+    (target, ms_to_samples_truncated(milliseconds).min(i32::MAX as u32) as i32)
+}
+
 /// Extract the upper 16 bits of a Q16.16 value as an i16 sample.
 #[inline(always)]
 fn magnitude_to_sample(mag: i32) -> i16 {
@@ -99,6 +148,7 @@ fn magnitude_to_sample(mag: i32) -> i16 {
 impl AudioNode for AudioSynthWaveformDc {
     const NUM_INPUTS: usize = 0;
     const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioSynthWaveformDc";
 
     fn update(
         &mut self,
@@ -117,7 +167,7 @@ impl AudioNode for AudioSynthWaveformDc {
         } else {
             // Ramping toward target
             for i in 0..AUDIO_BLOCK_SAMPLES {
-                self.magnitude = self.magnitude.wrapping_add(self.increment);
+                self.magnitude = self.magnitude.saturating_add(self.increment);
 
                 // Check if we've reached or passed the target
                 if (self.increment > 0 && self.magnitude >= self.target)
@@ -141,6 +191,79 @@ impl AudioNode for AudioSynthWaveformDc {
     }
 }
 
+/// Multi-channel DC source. Outputs `N` independent constant levels.
+///
+/// Source node: 0 inputs, `N` outputs. Each output behaves exactly like an
+/// [`AudioSynthWaveformDc`], including ramping, so one node can bias
+/// several control inputs (e.g. filter frequency and resonance).
+/// Out-of-range channels are ignored.
+///
+/// # Example
+/// ```ignore
+/// let mut bias = AudioSynthWaveformDcMulti::<3>::new();
+/// bias.amplitude(0, 0.5);
+/// bias.amplitude_ramp(1, -0.25, 200.0);
+/// ```
+pub struct AudioSynthWaveformDcMulti<const N: usize> {
+    channels: [AudioSynthWaveformDc; N],
+}
+
+impl<const N: usize> AudioSynthWaveformDcMulti<N> {
+    /// Create a new multi-channel DC source with every output at zero.
+    pub const fn new() -> Self {
+        AudioSynthWaveformDcMulti {
+            channels: [const { AudioSynthWaveformDc::new() }; N],
+        }
+    }
+
+    /// Set the level of output `channel` immediately (-1.0 to 1.0).
+    pub fn amplitude(&mut self, channel: usize, level: f32) {
+        if let Some(dc) = self.channels.get_mut(channel) {
+            dc.amplitude(level);
+        }
+    }
+
+    /// Ramp output `channel` to `level` over `milliseconds`.
+    pub fn amplitude_ramp(&mut self, channel: usize, level: f32, milliseconds: f32) {
+        if let Some(dc) = self.channels.get_mut(channel) {
+            dc.amplitude_ramp(level, milliseconds);
+        }
+    }
+
+    /// Whether output `channel` is currently ramping.
+    pub fn is_ramping(&self, channel: usize) -> bool {
+        self.channels.get(channel).is_some_and(|dc| dc.is_ramping())
+    }
+
+    /// One output's source, for the arming and stop/resume controls of
+    /// [`AudioSynthWaveformDc`].
+    pub fn channel(&mut self, channel: usize) -> Option<&mut AudioSynthWaveformDc> {
+        self.channels.get_mut(channel)
+    }
+}
+
+impl<const N: usize> Default for AudioSynthWaveformDcMulti<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> AudioNode for AudioSynthWaveformDcMulti<N> {
+    const NUM_INPUTS: usize = 0;
+    const NUM_OUTPUTS: usize = N;
+    const NAME: &'static str = "AudioSynthWaveformDcMulti";
+
+    fn update(
+        &mut self,
+        inputs: &[Option<AudioBlockRef>],
+        outputs: &mut [Option<AudioBlockMut>],
+    ) {
+        for (dc, slot) in self.channels.iter_mut().zip(outputs.chunks_mut(1)) {
+            dc.update(inputs, slot);
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -245,4 +368,103 @@ mod tests {
             assert!(out[i] >= out[i - 1], "not monotonic at {}: {} < {}", i, out[i], out[i - 1]);
         }
     }
+
+    fn render(dc: &mut AudioSynthWaveformDc) -> [i16; AUDIO_BLOCK_SAMPLES] {
+        let mut outputs = [AudioBlockMut::alloc()];
+        dc.update(&[], &mut outputs);
+        let mut samples = [0; AUDIO_BLOCK_SAMPLES];
+        samples.copy_from_slice(&outputs[0].as_ref().unwrap()[..]);
+        samples
+    }
+
+    #[test]
+    fn dc_armed_ramp_waits_for_start() {
+        reset_pool();
+        let mut dc = AudioSynthWaveformDc::new();
+        dc.amplitude(0.25);
+        dc.arm_ramp(1.0, 20.0);
+        assert!(dc.is_armed());
+
+        // Output stays flat while armed
+        for _ in 0..4 {
+            let out = render(&mut dc);
+            assert!(out.iter().all(|&s| s == out[0]), "armed ramp moved early");
+            assert!((out[0] - 8191).abs() <= 1, "got {}", out[0]);
+        }
+
+        dc.start_ramp();
+        assert!(!dc.is_armed());
+        assert!(dc.is_ramping());
+        let out = render(&mut dc);
+        assert!(out[127] > out[0] && out[0] > 8191, "not ramping: {} .. {}", out[0], out[127]);
+
+        // 20 ms ≈ 882 samples; well finished after 10 blocks
+        for _ in 0..10 {
+            render(&mut dc);
+        }
+        assert!(!dc.is_ramping());
+        assert!(render(&mut dc)[0] >= 32766);
+    }
+
+    #[test]
+    fn dc_stop_ramp_holds_and_resumes() {
+        reset_pool();
+        let mut dc = AudioSynthWaveformDc::new();
+        dc.amplitude_ramp(1.0, 50.0);
+        render(&mut dc);
+
+        dc.stop_ramp();
+        let held = render(&mut dc);
+        assert!(held.iter().all(|&s| s == held[0]));
+        assert!(held[0] > 0 && held[0] < 32000, "held at {}", held[0]);
+        assert_eq!(render(&mut dc)[0], held[0]);
+
+        // With nothing armed, start_ramp resumes toward the original target
+        dc.start_ramp();
+        let resumed = render(&mut dc);
+        assert!(resumed[0] > held[0] && resumed[127] > resumed[0]);
+    }
+
+    #[test]
+    fn dc_armed_ramps_start_in_sync() {
+        reset_pool();
+        let mut a = AudioSynthWaveformDc::new();
+        let mut b = AudioSynthWaveformDc::new();
+        a.arm_ramp(0.5, 10.0);
+        b.arm_ramp(-0.5, 10.0);
+        render(&mut a);
+        render(&mut b);
+
+        a.start_ramp();
+        b.start_ramp();
+        let (oa, ob) = (render(&mut a), render(&mut b));
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            assert!((oa[i] as i32 + ob[i] as i32).abs() <= 1, "out of step at {}", i);
+        }
+    }
+
+    #[test]
+    fn dc_multi_outputs_independent_levels() {
+        reset_pool();
+        let mut dc = AudioSynthWaveformDcMulti::<3>::new();
+        dc.amplitude(0, 0.5);
+        dc.amplitude(1, -0.25);
+        dc.amplitude_ramp(2, 1.0, 10.0);
+        dc.amplitude(3, 1.0); // out of range: ignored
+        assert!(dc.is_ramping(2) && !dc.is_ramping(0) && !dc.is_ramping(3));
+
+        // 10 ms ≈ 441 samples: the ramp lands during the fourth block
+        for _ in 0..4 {
+            let mut outputs = [(); 3].map(|_| AudioBlockMut::alloc());
+            dc.update(&[], &mut outputs);
+        }
+        let mut outputs = [(); 3].map(|_| AudioBlockMut::alloc());
+        dc.update(&[], &mut outputs);
+        for (out, expected) in outputs.iter().zip([16383, -8192, 32767]) {
+            let out = out.as_ref().unwrap();
+            assert!(out.iter().all(|&s| s == out[0]), "not constant");
+            assert!((out[0] - expected).abs() <= 1, "expected ~{}, got {}", expected, out[0]);
+        }
+        assert!(!dc.is_ramping(2));
+    }
 }
@@ -0,0 +1,248 @@
+//! Karplus-Strong plucked string.
+//!
+//! Port of `TeensyAudio/synth_karplusstrong.cpp`. A delay line one period
+//! long is filled with noise, then recirculated through a two-point
+//! averaging lowpass so the high harmonics die away first.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_SAMPLE_RATE_EXACT;
+use crate::node::AudioNode;
+
+/// Loop gain applied with the averaging filter: `2 * 32604 / 65536` ≈ 0.995.
+const LOOP_GAIN: i32 = 32604;
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum StringState {
+    /// Not playing; no output.
+    Idle,
+    /// `note_on()` was called; excite the string on the next update.
+    Pluck,
+    /// Ringing.
+    Ringing,
+}
+
+/// Plucked-string synth. Source node: 0 inputs, 1 output.
+///
+/// `N` is the delay-line length in samples and sets the lowest note,
+/// `AUDIO_SAMPLE_RATE_EXACT / N` (the C++ library uses 536, about 82 Hz).
+/// Lower notes are played at that frequency. `N` must be at least 2.
+///
+/// Each pass round the loop loses a fixed fraction of energy, so higher
+/// notes, which loop more often, decay faster, as on a real string.
+///
+/// # Example
+/// ```ignore
+/// let mut string: AudioSynthKarplusStrong<536> = AudioSynthKarplusStrong::new();
+/// string.note_on(196.0, 0.8);
+/// ```
+pub struct AudioSynthKarplusStrong<const N: usize> {
+    buffer: [i16; N],
+    /// Active delay-line length (one period) in samples.
+    buffer_len: usize,
+    buffer_index: usize,
+    /// Excitation level in Q16: 65535 = full scale.
+    magnitude: i32,
+    /// LCG state for the excitation noise.
+    seed: u32,
+    /// Consecutive zero samples output; a full period of them ends the note.
+    zero_run: usize,
+    state: StringState,
+}
+
+impl<const N: usize> AudioSynthKarplusStrong<N> {
+    /// The shortest period the loop can run is 2 samples.
+    const CHECK_SIZE: () = assert!(N >= 2, "AudioSynthKarplusStrong: N must be at least 2");
+
+    /// Create a new, silent string.
+    pub const fn new() -> Self {
+        #[allow(clippy::let_unit_value)]
+        let _ = Self::CHECK_SIZE;
+        AudioSynthKarplusStrong {
+            buffer: [0; N],
+            buffer_len: 0,
+            buffer_index: 0,
+            magnitude: 0,
+            seed: 0,
+            zero_run: 0,
+            state: StringState::Idle,
+        }
+    }
+
+    /// Pluck the string at `frequency` Hz with `velocity` (0.0–1.0).
+    ///
+    /// The excitation happens at the start of the next update.
+    pub fn note_on(&mut self, frequency: f32, velocity: f32) {
+        self.magnitude = (velocity.clamp(0.0, 1.0) * 65535.0) as i32;
+        let len = (AUDIO_SAMPLE_RATE_EXACT / frequency.max(1.0) + 0.5) as usize;
+        self.buffer_len = len.clamp(2, N);
+        self.state = StringState::Pluck;
+    }
+
+    /// Stop the string immediately.
+    pub fn note_off(&mut self) {
+        self.state = StringState::Idle;
+    }
+
+    /// Returns `true` while the string is sounding: from `note_on()` until
+    /// it has decayed to silence or `note_off()` is called.
+    pub fn is_playing(&self) -> bool {
+        self.state != StringState::Idle
+    }
+}
+
+impl<const N: usize> Default for AudioSynthKarplusStrong<N> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<const N: usize> AudioNode for AudioSynthKarplusStrong<N> {
+    const NUM_INPUTS: usize = 0;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioSynthKarplusStrong";
+
+    fn update(&mut self, _inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        if self.state == StringState::Idle {
+            return;
+        }
+        let Some(mut out) = outputs[0].take() else {
+            return;
+        };
+
+        let len = self.buffer_len;
+        if self.state == StringState::Pluck {
+            let mut seed = self.seed;
+            for s in self.buffer[..len].iter_mut() {
+                seed = seed.wrapping_mul(1_664_525).wrapping_add(1_013_904_223);
+                *s = (((seed >> 16) as i16 as i32 * self.magnitude) >> 16) as i16;
+            }
+            self.seed = seed;
+            self.buffer_index = 0;
+            self.zero_run = 0;
+            self.state = StringState::Ringing;
+        }
+
+        let mut index = self.buffer_index;
+        let mut prior = self.buffer[if index > 0 { index - 1 } else { len - 1 }] as i32;
+        for dst in out.iter_mut() {
+            let input = self.buffer[index] as i32;
+            // Round toward zero: flooring would leave the loop stuck at -1
+            let sample = ((input * LOOP_GAIN + prior * LOOP_GAIN) / 65536) as i16;
+            *dst = sample;
+            self.zero_run = if sample == 0 { self.zero_run + 1 } else { 0 };
+            self.buffer[index] = sample;
+            prior = input;
+            index += 1;
+            if index >= len {
+                index = 0;
+            }
+        }
+        self.buffer_index = index;
+        if self.zero_run >= len {
+            // A whole period of silence can only recirculate as silence
+            self.state = StringState::Idle;
+        }
+
+        outputs[0] = Some(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::constants::AUDIO_BLOCK_SAMPLES;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Render one block per entry of `energies`, storing its sum of squares.
+    fn block_energies<const N: usize>(
+        string: &mut AudioSynthKarplusStrong<N>,
+        energies: &mut [u64],
+    ) {
+        for e in energies.iter_mut() {
+            let mut outputs = [AudioBlockMut::alloc()];
+            string.update(&[], &mut outputs);
+            let out = outputs[0].take().unwrap();
+            *e = out.iter().map(|&s| (s as i64 * s as i64) as u64).sum();
+        }
+    }
+
+    #[test]
+    fn idle_string_produces_nothing() {
+        reset_pool();
+        let mut string: AudioSynthKarplusStrong<536> = AudioSynthKarplusStrong::new();
+        assert!(!string.is_playing());
+        let mut outputs = [AudioBlockMut::alloc()];
+        string.update(&[], &mut outputs);
+        assert!(outputs[0].as_ref().unwrap().iter().all(|&s| s == 0));
+    }
+
+    #[test]
+    fn pluck_decays() {
+        reset_pool();
+        let mut string: AudioSynthKarplusStrong<536> = AudioSynthKarplusStrong::new();
+        string.note_on(220.0, 1.0);
+        assert!(string.is_playing());
+
+        let mut energies = [0u64; 200];
+        block_energies(&mut string, &mut energies);
+        assert!(energies[0] > 0, "pluck must excite the string");
+
+        // Energy over successive 20-block windows keeps falling
+        let mut prev = u64::MAX;
+        for window in energies.chunks(20) {
+            let e: u64 = window.iter().sum();
+            assert!(e < prev, "energy rose: {} after {}", e, prev);
+            prev = e;
+        }
+        let start: u64 = energies[..20].iter().sum();
+        assert!(prev < start / 10, "expected decay, {} -> {}", start, prev);
+
+        string.note_off();
+        assert!(!string.is_playing());
+    }
+
+    #[test]
+    fn pluck_decays_to_idle() {
+        reset_pool();
+        let mut string: AudioSynthKarplusStrong<64> = AudioSynthKarplusStrong::new();
+        string.note_on(2000.0, 1.0);
+        let mut blocks = 0;
+        while string.is_playing() {
+            let mut outputs = [AudioBlockMut::alloc()];
+            string.update(&[], &mut outputs);
+            blocks += 1;
+            assert!(blocks < 2000, "string never fell silent");
+            if !string.is_playing() {
+                // Ended by a silent period, not cut off mid-decay
+                let out = outputs[0].take().unwrap();
+                assert!(out[AUDIO_BLOCK_SAMPLES - 32..].iter().all(|&s| s == 0));
+            }
+        }
+        let mut energies = [0u64; 1];
+        string.note_on(2000.0, 1.0);
+        block_energies(&mut string, &mut energies);
+        assert!(energies[0] > 0, "a new pluck sounds again");
+    }
+
+    #[test]
+    fn higher_notes_decay_faster() {
+        reset_pool();
+        let remaining = |hz: f32| {
+            let mut string: AudioSynthKarplusStrong<536> = AudioSynthKarplusStrong::new();
+            string.note_on(hz, 1.0);
+            let mut energies = [0u64; 100];
+            block_energies(&mut string, &mut energies);
+            let start: u64 = energies[..10].iter().sum();
+            let end: u64 = energies[90..].iter().sum();
+            end as f64 / start as f64
+        };
+
+        let low = remaining(110.0);
+        let high = remaining(880.0);
+        assert!(high < low, "880 Hz kept {} vs 110 Hz {}", high, low);
+    }
+}
@@ -0,0 +1,90 @@
+//! Silence source — a placeholder that never produces audio.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::node::AudioNode;
+
+/// Silence source. Source node: 0 inputs, 1 output.
+///
+/// Always drops its output block, so downstream nodes see `None` (silence)
+/// and no pool block is held. Useful as a stand-in while a graph is under
+/// construction, or to feed explicit silence into a mixer channel.
+///
+/// # Example
+/// ```ignore
+/// audio_graph! {
+///     pub struct Graph {
+///         todo: AudioSynthSilence {},
+///         mix: AudioMixer<2> { (synth, 0), (todo, 0) },
+///         // ...
+///     }
+/// }
+/// ```
+pub struct AudioSynthSilence;
+
+impl AudioSynthSilence {
+    /// Create a new silence source.
+    pub const fn new() -> Self {
+        AudioSynthSilence
+    }
+}
+
+impl Default for AudioSynthSilence {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioSynthSilence {
+    const NUM_INPUTS: usize = 0;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioSynthSilence";
+
+    fn update(
+        &mut self,
+        _inputs: &[Option<AudioBlockRef>],
+        outputs: &mut [Option<AudioBlockMut>],
+    ) {
+        // Release the preallocated block back to the pool
+        outputs[0] = None;
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    #[test]
+    fn silence_never_produces_a_block() {
+        reset_pool();
+        let mut silence = AudioSynthSilence::new();
+        for _ in 0..4 {
+            let mut outputs = [AudioBlockMut::alloc()];
+            silence.update(&[], &mut outputs);
+            assert!(outputs[0].is_none());
+            assert_eq!(POOL.allocated_count(), 0);
+        }
+    }
+
+    crate::audio_graph! {
+        struct SilenceGraph {
+            silence: AudioSynthSilence {},
+            peak: crate::nodes::AudioAnalyzePeak { (silence, 0) },
+        }
+    }
+
+    #[test]
+    fn silence_feeds_none_downstream() {
+        reset_pool();
+        let mut graph = SilenceGraph::new();
+        graph.update_all();
+
+        // The peak analyzer received no block at all, not a zeroed one
+        assert!(!graph.peak.available());
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+}
@@ -0,0 +1,234 @@
+//! Simple pitched drum voice.
+//!
+//! Port of `TeensyAudio/synth_simple_drum.cpp`: two sines a fifth apart
+//! under a squared (roughly exponential) decay envelope, with an optional
+//! pitch sweep that follows the envelope.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::{ms_to_samples_truncated, AUDIO_SAMPLE_RATE_EXACT};
+use crate::dsp::freq_to_increment;
+use crate::dsp::wavetables::sine_lookup;
+use crate::node::AudioNode;
+
+/// Linear envelope start value (Q31, just under 1.0).
+const ENV_START: i32 = 0x7FFF_0000;
+
+/// Simple drum. Source node: 1 input (optional pitch modulation), 1 output.
+///
+/// [`note_on()`](Self::note_on) restarts both oscillators at phase zero
+/// with the envelope at full level, so every hit starts with the same
+/// sharp attack. The envelope falls linearly to zero over
+/// [`length()`](Self::length) and is squared before use, giving a fast
+/// exponential-like decay. The second oscillator runs at 1.5× the
+/// frequency, adding an inharmonic partial that suits toms and bongos.
+///
+/// [`pitch_mod()`](Self::pitch_mod) bends the pitch in proportion to the
+/// envelope: above 0.5 the hit starts high and falls, below 0.5 it starts
+/// low and rises. An input block, if connected, adds FM on top, where −1.0
+/// stops the oscillators and +1.0 doubles their frequency.
+///
+/// # Example
+/// ```ignore
+/// let mut drum = AudioSynthSimpleDrum::new();
+/// drum.frequency(60.0);
+/// drum.length(300.0);
+/// drum.second_mix(0.0);
+/// drum.pitch_mod(0.55);
+/// drum.note_on();
+/// ```
+pub struct AudioSynthSimpleDrum {
+    phase1: u32,
+    phase2: u32,
+    /// Base phase increment of the first oscillator.
+    increment: u32,
+    /// First oscillator level (Q15).
+    amplitude1: i32,
+    /// Second oscillator level (Q15).
+    amplitude2: i32,
+    /// Pitch-mod depth in Q15, −32767 to +32767; 0 = none.
+    pitch_depth: i32,
+    /// Linear envelope (Q31); 0 = finished.
+    env_lin: i32,
+    /// Per-sample envelope decrement (Q31).
+    env_decrement: i32,
+}
+
+impl AudioSynthSimpleDrum {
+    /// Create a new drum voice: 60 Hz, 1 s long, no second oscillator or
+    /// pitch mod, not playing.
+    pub fn new() -> Self {
+        let mut drum = AudioSynthSimpleDrum {
+            phase1: 0,
+            phase2: 0,
+            increment: 0,
+            amplitude1: 0x7FFF,
+            amplitude2: 0,
+            pitch_depth: 0,
+            env_lin: 0,
+            env_decrement: 0,
+        };
+        drum.frequency(60.0);
+        drum.length(1000.0);
+        drum
+    }
+
+    /// Set the fundamental frequency in Hz.
+    pub fn frequency(&mut self, hz: f32) {
+        self.increment = freq_to_increment(hz.clamp(0.0, AUDIO_SAMPLE_RATE_EXACT / 2.0));
+    }
+
+    /// Set the decay length in milliseconds (clamped to 1–5000 ms).
+    pub fn length(&mut self, milliseconds: f32) {
+        let ms = milliseconds.clamp(1.0, 5000.0);
+        let samples = ms_to_samples_truncated(ms) as i32;
+        self.env_decrement = ENV_START / samples.max(1);
+    }
+
+    /// Set the level of the second oscillator (0.0–1.0).
+    ///
+    /// At 1.0 the two oscillators are mixed at half scale each.
+    pub fn second_mix(&mut self, level: f32) {
+        self.amplitude2 = (level.clamp(0.0, 1.0) * 16383.0) as i32;
+        self.amplitude1 = 0x7FFF - self.amplitude2;
+    }
+
+    /// Set the envelope-following pitch bend (0.0–1.0, 0.5 = none).
+    ///
+    /// At 1.0 the hit starts an octave up; at 0.0 it starts at 0 Hz.
+    pub fn pitch_mod(&mut self, depth: f32) {
+        self.pitch_depth = ((depth.clamp(0.0, 1.0) - 0.5) * 2.0 * 32767.0) as i32;
+    }
+
+    /// Trigger the drum.
+    pub fn note_on(&mut self) {
+        self.phase1 = 0;
+        self.phase2 = 0;
+        self.env_lin = ENV_START;
+    }
+
+    /// Returns `true` while the envelope is still decaying.
+    pub fn is_playing(&self) -> bool {
+        self.env_lin > 0
+    }
+}
+
+impl Default for AudioSynthSimpleDrum {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioSynthSimpleDrum {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioSynthSimpleDrum";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        if self.env_lin <= 0 {
+            return;
+        }
+        let Some(mut out) = outputs[0].take() else {
+            return;
+        };
+
+        let modulator = inputs[0].as_ref();
+        let inc = self.increment as i64;
+
+        for (i, dst) in out.iter_mut().enumerate() {
+            self.env_lin = (self.env_lin - self.env_decrement).max(0);
+            let env = self.env_lin >> 16;
+            let env_sqr = (env * env) >> 15;
+
+            let s1 = sine_lookup(self.phase1) >> 16;
+            let s2 = sine_lookup(self.phase2) >> 16;
+            let mix = (s1 * self.amplitude1 + s2 * self.amplitude2) >> 15;
+            *dst = ((mix * env_sqr) >> 15) as i16;
+
+            // Frequency scale 1 + m, m in Q15, never running backwards
+            let mut m = (self.pitch_depth * env_sqr) >> 15;
+            if let Some(block) = modulator {
+                m += block[i] as i32;
+            }
+            let step = (inc + ((inc * m.max(-32768) as i64) >> 15)) as u32;
+            self.phase1 = self.phase1.wrapping_add(step);
+            self.phase2 = self.phase2.wrapping_add(step.wrapping_add(step >> 1));
+        }
+
+        outputs[0] = Some(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::constants::AUDIO_BLOCK_SAMPLES;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    fn render(drum: &mut AudioSynthSimpleDrum) -> AudioBlockMut {
+        let mut outputs = [AudioBlockMut::alloc()];
+        drum.update(&[None], &mut outputs);
+        outputs[0].take().unwrap()
+    }
+
+    fn peak(block: &[i16]) -> i16 {
+        block.iter().map(|s| s.saturating_abs()).max().unwrap()
+    }
+
+    #[test]
+    fn drum_is_silent_until_triggered() {
+        reset_pool();
+        let mut drum = AudioSynthSimpleDrum::new();
+        assert!(!drum.is_playing());
+        assert_eq!(peak(&render(&mut drum)[..]), 0);
+    }
+
+    #[test]
+    fn drum_attacks_then_decays_within_length() {
+        reset_pool();
+        let mut drum = AudioSynthSimpleDrum::new();
+        // Several cycles per block, so each block's peak tracks the envelope
+        drum.frequency(1000.0);
+        drum.length(50.0);
+        drum.note_on();
+        assert!(drum.is_playing());
+
+        // Sharp attack: near full scale within the first block
+        let first = peak(&render(&mut drum)[..]);
+        assert!(first > 20000, "attack peak {}", first);
+
+        let blocks = (0.050 * AUDIO_SAMPLE_RATE_EXACT) as usize / AUDIO_BLOCK_SAMPLES + 1;
+        let mut prev = first;
+        for _ in 1..blocks {
+            let p = peak(&render(&mut drum)[..]);
+            assert!(p <= prev, "envelope rose: {} after {}", p, prev);
+            prev = p;
+        }
+        assert!(prev < 100, "tail {}", prev);
+        assert!(!drum.is_playing(), "finished after {} blocks", blocks);
+        assert_eq!(peak(&render(&mut drum)[..]), 0);
+
+        // Retrigger starts over
+        drum.note_on();
+        assert_eq!(peak(&render(&mut drum)[..]), first);
+    }
+
+    #[test]
+    fn pitch_mod_bends_attack() {
+        reset_pool();
+        let crossings = |depth: f32| {
+            let mut drum = AudioSynthSimpleDrum::new();
+            drum.frequency(1000.0);
+            drum.pitch_mod(depth);
+            drum.note_on();
+            let out = render(&mut drum);
+            out.windows(2).filter(|w| (w[0] < 0) != (w[1] < 0)).count()
+        };
+        let flat = crossings(0.5);
+        assert!(crossings(1.0) > flat + flat / 2, "high start");
+        assert!(crossings(0.0) < flat / 2, "low start");
+    }
+}
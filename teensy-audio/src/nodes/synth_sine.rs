@@ -4,8 +4,10 @@
 //! with linear interpolation between adjacent entries.
 
 use crate::block::{AudioBlockMut, AudioBlockRef};
-use crate::constants::{AUDIO_BLOCK_SAMPLES, AUDIO_SAMPLE_RATE_EXACT};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::dsp::freq_to_increment;
 use crate::dsp::intrinsics::mul_32x32_rshift32;
+use crate::dsp::music::midi_note_to_frequency;
 use crate::dsp::wavetables::SINE_TABLE;
 use crate::node::AudioNode;
 
@@ -41,10 +43,28 @@ impl AudioSynthSine {
 
     /// Set the oscillator frequency in Hz.
     ///
-    /// Phase increment is computed as `freq / AUDIO_SAMPLE_RATE_EXACT * 2^32`.
+    /// Phase increment is computed as `freq / AUDIO_SAMPLE_RATE_EXACT * 2^32`
+    /// (see [`freq_to_increment()`]).
     pub fn frequency(&mut self, hz: f32) {
-        let inc = hz * (4_294_967_296.0 / AUDIO_SAMPLE_RATE_EXACT);
-        self.phase_increment = inc as u32;
+        self.phase_increment = freq_to_increment(hz);
+    }
+
+    /// Set the phase increment directly, without float math.
+    ///
+    /// One cycle is 2^32, so the increment is
+    /// `hz / AUDIO_SAMPLE_RATE_EXACT * 2^32`; compute it off the ISR with
+    /// [`freq_to_increment()`].
+    /// Increments of 2^31 and above are beyond Nyquist and alias.
+    pub fn phase_increment_raw(&mut self, increment: u32) {
+        self.phase_increment = increment;
+    }
+
+    /// Set the frequency from a MIDI note number (12-TET, A4 = 69 = 440 Hz).
+    ///
+    /// Only the phase increment changes, so switching notes mid-tone is
+    /// phase-continuous (no click). Notes above 127 are clamped.
+    pub fn note(&mut self, midi_note: u8) {
+        self.frequency(midi_note_to_frequency(midi_note.min(127)));
     }
 
     /// Set the output amplitude (0.0 = silent, 1.0 = full scale).
@@ -64,6 +84,7 @@ impl AudioSynthSine {
 impl AudioNode for AudioSynthSine {
     const NUM_INPUTS: usize = 0;
     const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioSynthSine";
 
     fn update(
         &mut self,
@@ -115,6 +136,7 @@ impl AudioNode for AudioSynthSine {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::constants::AUDIO_SAMPLE_RATE_EXACT;
     use crate::block::pool::POOL;
 
     fn reset_pool() {
@@ -241,4 +263,40 @@ mod tests {
             assert_eq!(out[i], first);
         }
     }
+
+    #[test]
+    fn sine_note_sets_frequency_keeping_phase() {
+        reset_pool();
+        let hz_of = |sine: &AudioSynthSine| {
+            sine.phase_increment as f32 * (AUDIO_SAMPLE_RATE_EXACT / 4_294_967_296.0)
+        };
+        let mut sine = AudioSynthSine::new();
+        sine.amplitude(1.0);
+        sine.note(69);
+        assert!((hz_of(&sine) - 440.0).abs() < 0.01, "note 69 = {}", hz_of(&sine));
+
+        let mut outputs = [AudioBlockMut::alloc()];
+        sine.update(&[], &mut outputs);
+        let phase = sine.phase_accumulator;
+        assert_ne!(phase, 0);
+
+        sine.note(81);
+        assert!((hz_of(&sine) - 880.0).abs() < 0.01, "note 81 = {}", hz_of(&sine));
+        assert_eq!(sine.phase_accumulator, phase, "note change must not reset phase");
+    }
+
+    #[test]
+    fn sine_raw_increment_matches_frequency() {
+        for hz in [0.0, 27.5, 440.0, 1000.0, 12345.6, 20000.0] {
+            let mut float = AudioSynthSine::new();
+            float.frequency(hz);
+            let mut raw = AudioSynthSine::new();
+            raw.phase_increment_raw(freq_to_increment(hz));
+            assert_eq!(raw.phase_increment, float.phase_increment, "{} Hz", hz);
+        }
+
+        // 2^32 / 44117.647 ≈ 97,352.6 per Hz
+        const A4: u32 = freq_to_increment(440.0);
+        assert!((A4 as i64 - 42_835_160).abs() < 100, "A4 increment {}", A4);
+    }
 }
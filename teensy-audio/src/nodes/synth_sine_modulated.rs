@@ -0,0 +1,268 @@
+//! Sine oscillator with a frequency or phase modulation input.
+//!
+//! Port of `AudioSynthWaveformSineModulated` from `TeensyAudio/synth_sine.cpp`,
+//! with an added phase-modulation mode. Same 257-entry wavetable and
+//! linear interpolation as [`AudioSynthSine`](super::AudioSynthSine).
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::dsp::freq_to_increment;
+use crate::dsp::intrinsics::mul_32x32_rshift32;
+use crate::dsp::wavetables::sine_lookup;
+use crate::node::AudioNode;
+
+/// Sine oscillator driven by a modulation input. 1 input, 1 output.
+///
+/// In FM mode (the default, as in the C++ library) each input sample
+/// scales that sample's phase increment by `1 + x`: −1.0 stops the
+/// oscillator, 0.0 plays the set frequency and +1.0 doubles it. Keep the
+/// center frequency below a quarter of the sample rate so full positive
+/// modulation stays below Nyquist.
+///
+/// In PM mode each input sample offsets the phase instead, full scale
+/// being ±180°.
+///
+/// With no input block it plays an unmodulated sine.
+///
+/// # Example
+/// ```ignore
+/// let mut osc = AudioSynthSineModulated::new();
+/// osc.frequency(440.0);
+/// osc.amplitude(0.8);
+/// osc.fm_mode(false); // phase modulation
+/// ```
+pub struct AudioSynthSineModulated {
+    /// Phase accumulator (wraps naturally at 32 bits = 360°).
+    phase_accumulator: u32,
+    /// Center phase increment per sample: `freq / SAMPLE_RATE * 2^32`.
+    phase_increment: u32,
+    /// Output magnitude in Q16.16 format. 0 = silent, 65536 = full scale.
+    magnitude: i32,
+    /// `true` for frequency modulation, `false` for phase modulation.
+    fm: bool,
+}
+
+impl AudioSynthSineModulated {
+    /// Create a new modulated sine oscillator, initially silent, in FM mode.
+    pub const fn new() -> Self {
+        AudioSynthSineModulated {
+            phase_accumulator: 0,
+            phase_increment: 0,
+            magnitude: 0,
+            fm: true,
+        }
+    }
+
+    /// Set the center frequency in Hz.
+    pub fn frequency(&mut self, hz: f32) {
+        self.phase_increment = freq_to_increment(hz);
+    }
+
+    /// Set the output amplitude (0.0 = silent, 1.0 = full scale).
+    pub fn amplitude(&mut self, level: f32) {
+        self.magnitude = (level.clamp(0.0, 1.0) * 65536.0) as i32;
+    }
+
+    /// Set the phase offset in degrees (0–360).
+    pub fn phase(&mut self, angle: f32) {
+        self.phase_accumulator = (angle * (4_294_967_296.0 / 360.0)) as u32;
+    }
+
+    /// Select frequency (`true`) or phase (`false`) modulation.
+    pub fn fm_mode(&mut self, enable: bool) {
+        self.fm = enable;
+    }
+}
+
+impl Default for AudioSynthSineModulated {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Sine at phase `ph`, scaled by `mag` (Q16.16).
+#[inline(always)]
+fn sine_at(ph: u32, mag: i32) -> i16 {
+    mul_32x32_rshift32(sine_lookup(ph), mag) as i16
+}
+
+impl AudioNode for AudioSynthSineModulated {
+    const NUM_INPUTS: usize = 1;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioSynthSineModulated";
+
+    fn update(&mut self, inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        let out = if self.magnitude == 0 {
+            None
+        } else {
+            outputs[0].take()
+        };
+        let Some(mut out) = out else {
+            // Silent or no output block: keep time at the center frequency
+            self.phase_accumulator = self.phase_accumulator.wrapping_add(
+                self.phase_increment
+                    .wrapping_mul(AUDIO_BLOCK_SAMPLES as u32),
+            );
+            return;
+        };
+
+        let mut ph = self.phase_accumulator;
+        let inc = self.phase_increment;
+        let mag = self.magnitude;
+
+        match inputs[0] {
+            Some(ref modulator) if self.fm => {
+                for (dst, &m) in out.iter_mut().zip(modulator.iter()) {
+                    *dst = sine_at(ph, mag);
+                    // inc * (1 + m / 32768): 0 at -1.0, double at +1.0
+                    let delta = (inc as i64 * m as i64) >> 15;
+                    ph = ph.wrapping_add((inc as i64 + delta) as u32);
+                }
+            }
+            Some(ref modulator) => {
+                for (dst, &m) in out.iter_mut().zip(modulator.iter()) {
+                    // Full scale input = ±half a cycle
+                    let offset = ((m as i32) << 16) as u32;
+                    *dst = sine_at(ph.wrapping_add(offset), mag);
+                    ph = ph.wrapping_add(inc);
+                }
+            }
+            None => {
+                for dst in out.iter_mut() {
+                    *dst = sine_at(ph, mag);
+                    ph = ph.wrapping_add(inc);
+                }
+            }
+        }
+
+        self.phase_accumulator = ph;
+        outputs[0] = Some(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::constants::AUDIO_SAMPLE_RATE_EXACT;
+    use crate::nodes::{AudioSynthSine, AudioSynthWaveformDc};
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Carrier at exactly four cycles per block.
+    const CARRIER_HZ: f32 = AUDIO_SAMPLE_RATE_EXACT * 4.0 / AUDIO_BLOCK_SAMPLES as f32;
+
+    fn render(
+        osc: &mut AudioSynthSineModulated,
+        modulator: Option<AudioBlockRef>,
+    ) -> AudioBlockMut {
+        let mut outputs = [AudioBlockMut::alloc()];
+        osc.update(&[modulator], &mut outputs);
+        outputs[0].take().unwrap()
+    }
+
+    fn argmax(block: &[i16]) -> usize {
+        (0..block.len()).max_by_key(|&i| block[i]).unwrap()
+    }
+
+    fn zero_crossings(block: &[i16]) -> usize {
+        block
+            .windows(2)
+            .filter(|w| (w[0] < 0) != (w[1] < 0))
+            .count()
+    }
+
+    #[test]
+    fn unmodulated_matches_plain_sine() {
+        reset_pool();
+        let mut plain = AudioSynthSine::new();
+        plain.frequency(CARRIER_HZ);
+        plain.amplitude(0.9);
+        let mut osc = AudioSynthSineModulated::new();
+        osc.frequency(CARRIER_HZ);
+        osc.amplitude(0.9);
+
+        for _ in 0..3 {
+            let mut outputs = [AudioBlockMut::alloc()];
+            plain.update(&[], &mut outputs);
+            let expected = outputs[0].take().unwrap();
+            assert_eq!(&render(&mut osc, None)[..], &expected[..]);
+        }
+    }
+
+    #[test]
+    fn slow_modulator_produces_vibrato() {
+        reset_pool();
+        let mut lfo = AudioSynthSine::new();
+        lfo.frequency(20.0);
+        lfo.amplitude(0.05);
+        let mut osc = AudioSynthSineModulated::new();
+        osc.frequency(CARRIER_HZ);
+        osc.amplitude(0.9);
+
+        // Unmodulated, the peak lands on the same sample every block
+        let steady = argmax(&render(&mut osc, None)[..]);
+        assert_eq!(argmax(&render(&mut osc, None)[..]), steady);
+
+        let (mut min_peak, mut max_peak) = (usize::MAX, 0);
+        for _ in 0..20 {
+            let mut lfo_out = [AudioBlockMut::alloc()];
+            lfo.update(&[], &mut lfo_out);
+            let modulator = lfo_out[0].take().unwrap().into_shared();
+            let peak = argmax(&render(&mut osc, Some(modulator))[..]);
+            min_peak = min_peak.min(peak);
+            max_peak = max_peak.max(peak);
+        }
+        assert!(max_peak > min_peak, "peak index never moved: {}", min_peak);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn fm_dc_offset_scales_frequency() {
+        reset_pool();
+        let mut dc = AudioSynthWaveformDc::new();
+        dc.amplitude(0.5);
+        let mut osc = AudioSynthSineModulated::new();
+        osc.frequency(CARRIER_HZ);
+        osc.amplitude(0.9);
+
+        let mut dc_out = [AudioBlockMut::alloc()];
+        dc.update(&[], &mut dc_out);
+        let modulator = dc_out[0].take().unwrap().into_shared();
+
+        // 4 cycles per block at rest, 6 at +0.5: ~12 crossings per block
+        let out = render(&mut osc, Some(modulator.clone()));
+        assert!((11..=12).contains(&zero_crossings(&out[..])));
+
+        // Phase modulation with a constant offset leaves the frequency alone
+        osc.fm_mode(false);
+        let out = render(&mut osc, Some(modulator));
+        assert!((7..=8).contains(&zero_crossings(&out[..])));
+    }
+
+    #[test]
+    fn pm_half_scale_shifts_quarter_cycle() {
+        reset_pool();
+        // Exactly +0.5: a quarter cycle ahead
+        let mut block = AudioBlockMut::alloc().unwrap();
+        block.fill(16384);
+        let modulator = block.into_shared();
+
+        let mut shifted = AudioSynthSineModulated::new();
+        shifted.frequency(CARRIER_HZ);
+        shifted.amplitude(0.9);
+        shifted.fm_mode(false);
+        let mut reference = AudioSynthSineModulated::new();
+        reference.frequency(CARRIER_HZ);
+        reference.amplitude(0.9);
+        reference.phase(90.0);
+
+        let a = render(&mut shifted, Some(modulator));
+        let b = render(&mut reference, None);
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            assert!((a[i] as i32 - b[i] as i32).abs() <= 2, "sample {}", i);
+        }
+    }
+}
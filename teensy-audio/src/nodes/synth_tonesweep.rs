@@ -0,0 +1,279 @@
+//! Logarithmic sine sweep (chirp) source.
+//!
+//! Port of `TeensyAudio/synth_tonesweep.cpp`. The C++ sweep is linear in
+//! Hz; this one is exponential, so every octave takes the same time,
+//! which suits frequency-response measurements.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::{AUDIO_BLOCK_SAMPLES, AUDIO_SAMPLE_RATE_EXACT};
+use crate::dsp::freq_to_increment;
+use crate::dsp::intrinsics::mul_32x32_rshift32;
+use crate::dsp::wavetables::sine_lookup;
+use crate::node::AudioNode;
+
+/// Tone sweep. Source node: 0 inputs, 1 output.
+///
+/// [`play()`](Self::play) starts a sine at `low_hz` whose frequency is
+/// multiplied by a constant factor every sample, reaching `high_hz` after
+/// `duration_s`. Then the output goes silent until the next `play()`.
+/// `high_hz` may be below `low_hz` for a downward sweep.
+///
+/// The increment is recomputed from the start frequency at every block
+/// boundary and ramped linearly across the block, so rounding does not
+/// accumulate over long sweeps.
+///
+/// # Example
+/// ```ignore
+/// let mut sweep = AudioSynthToneSweep::new();
+/// sweep.play(0.5, 20.0, 20_000.0, 10.0);
+/// // ... later ...
+/// if !sweep.is_playing() { /* measurement done */ }
+/// ```
+pub struct AudioSynthToneSweep {
+    /// Phase accumulator (wraps naturally at 32 bits = 360°).
+    phase_accumulator: u32,
+    /// Phase increment at the current position.
+    phase_increment: u32,
+    /// Phase increment at `low_hz`.
+    start_increment: f32,
+    /// `ln(high_hz / low_hz)`; negative sweeps down.
+    log_ratio: f32,
+    /// Sweep length in samples.
+    length: u32,
+    /// Samples played so far; the sweep stops at `length`.
+    position: u32,
+    /// Output magnitude in Q16.16 format.
+    magnitude: i32,
+}
+
+impl AudioSynthToneSweep {
+    /// Create a new, stopped tone sweep.
+    pub const fn new() -> Self {
+        AudioSynthToneSweep {
+            phase_accumulator: 0,
+            phase_increment: 0,
+            start_increment: 0.0,
+            log_ratio: 0.0,
+            length: 0,
+            position: 0,
+            magnitude: 0,
+        }
+    }
+
+    /// Start a sweep from `low_hz` to `high_hz` lasting `duration_s` seconds.
+    ///
+    /// Returns `false` (and leaves the current state alone) if either
+    /// frequency is not between 0 and Nyquist, or the duration is shorter
+    /// than one sample.
+    pub fn play(&mut self, amplitude: f32, low_hz: f32, high_hz: f32, duration_s: f32) -> bool {
+        let nyquist = AUDIO_SAMPLE_RATE_EXACT / 2.0;
+        let audible = |hz: f32| hz > 0.0 && hz < nyquist;
+        if !audible(low_hz) || !audible(high_hz) {
+            return false;
+        }
+        let samples = duration_s * AUDIO_SAMPLE_RATE_EXACT;
+        if !(1.0..).contains(&samples) {
+            return false;
+        }
+
+        self.phase_increment = freq_to_increment(low_hz);
+        self.start_increment = self.phase_increment as f32;
+        self.log_ratio = libm::logf(high_hz / low_hz);
+        self.phase_accumulator = 0;
+        self.length = samples as u32;
+        self.position = 0;
+        self.magnitude = (amplitude.clamp(0.0, 1.0) * 65536.0) as i32;
+        true
+    }
+
+    /// Returns `true` while a sweep is in progress.
+    pub fn is_playing(&self) -> bool {
+        self.position < self.length
+    }
+
+    /// Current sweep frequency in Hz (the end frequency once stopped).
+    pub fn read(&self) -> f32 {
+        self.phase_increment as f32 * (AUDIO_SAMPLE_RATE_EXACT / 4_294_967_296.0)
+    }
+
+    /// Phase increment `position` samples into the sweep:
+    /// `start · (high / low)^(position / length)`.
+    fn increment_at(&self, position: u32) -> u32 {
+        let t = position as f32 / self.length as f32;
+        (self.start_increment * libm::expf(self.log_ratio * t)) as u32
+    }
+}
+
+impl Default for AudioSynthToneSweep {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioSynthToneSweep {
+    const NUM_INPUTS: usize = 0;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioSynthToneSweep";
+
+    fn update(&mut self, _inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        if !self.is_playing() {
+            return;
+        }
+        let Some(mut out) = outputs[0].take() else {
+            return;
+        };
+
+        let count = ((self.length - self.position) as usize).min(AUDIO_BLOCK_SAMPLES);
+        let end = self.position + count as u32;
+        let end_increment = self.increment_at(end);
+
+        // Increment in Q32.16 so the per-sample step keeps its fraction
+        let mut inc = (self.phase_increment as i64) << 16;
+        let step = ((end_increment as i64 - self.phase_increment as i64) << 16) / count as i64;
+        let mut ph = self.phase_accumulator;
+
+        for dst in out[..count].iter_mut() {
+            *dst = mul_32x32_rshift32(sine_lookup(ph), self.magnitude) as i16;
+            ph = ph.wrapping_add((inc >> 16) as u32);
+            inc += step;
+        }
+        // Tail of the final block
+        out[count..].fill(0);
+
+        self.phase_accumulator = ph;
+        self.phase_increment = end_increment;
+        self.position = end;
+        outputs[0] = Some(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    fn render(sweep: &mut AudioSynthToneSweep) -> Option<AudioBlockMut> {
+        let mut outputs = [AudioBlockMut::alloc()];
+        sweep.update(&[], &mut outputs);
+        outputs[0].take()
+    }
+
+    fn zero_crossings(block: &[i16]) -> usize {
+        block
+            .windows(2)
+            .filter(|w| (w[0] < 0) != (w[1] < 0))
+            .count()
+    }
+
+    #[test]
+    fn sweep_rises_then_stops() {
+        reset_pool();
+        let mut sweep = AudioSynthToneSweep::new();
+        assert!(!sweep.is_playing());
+        assert!(sweep.play(0.8, 200.0, 5000.0, 0.5));
+        assert!(sweep.is_playing());
+
+        // Crossings per 16-block window: ~2 per cycle
+        const WINDOW: usize = 16;
+        let mut windows = [0usize; 16];
+        let mut filled = 0;
+        let (mut crossings, mut blocks) = (0, 0);
+        while sweep.is_playing() {
+            let out = render(&mut sweep).unwrap();
+            crossings += zero_crossings(&out[..]);
+            blocks += 1;
+            if blocks % WINDOW == 0 {
+                windows[filled] = crossings;
+                filled += 1;
+                crossings = 0;
+            }
+        }
+        assert_eq!(
+            blocks,
+            (0.5 * AUDIO_SAMPLE_RATE_EXACT) as usize / AUDIO_BLOCK_SAMPLES + 1
+        );
+
+        let windows = &windows[..filled];
+        for pair in windows.windows(2) {
+            assert!(pair[1] > pair[0], "frequency must rise: {:?}", windows);
+        }
+        let hz = |c: usize| {
+            c as f32 / 2.0 * AUDIO_SAMPLE_RATE_EXACT / (WINDOW * AUDIO_BLOCK_SAMPLES) as f32
+        };
+        let first = hz(windows[0]);
+        // Window averages: the last full one is centered ~88% of the way in
+        let last = hz(windows[filled - 1]);
+        assert!(
+            (200.0..300.0).contains(&first),
+            "starts near low: {}",
+            first
+        );
+        assert!((3000.0..4000.0).contains(&last), "ends near high: {}", last);
+        assert!(
+            (sweep.read() - 5000.0).abs() < 50.0,
+            "ended at {}",
+            sweep.read()
+        );
+
+        // Finished: the output block is left untouched
+        let out = render(&mut sweep).unwrap();
+        assert!(out.iter().all(|&s| s == 0), "silent once finished");
+        drop(out);
+        assert_eq!(POOL.allocated_count(), 0);
+    }
+
+    #[test]
+    fn long_full_range_sweep_reaches_end_frequency() {
+        reset_pool();
+        let mut sweep = AudioSynthToneSweep::new();
+        assert!(sweep.play(0.8, 20.0, 20_000.0, 30.0));
+
+        // Measure a 16-block window centred on the midpoint, where a log
+        // sweep is at the geometric mean: sqrt(20 · 20000) ≈ 632 Hz
+        const WINDOW: usize = 16;
+        let total = (30.0 * AUDIO_SAMPLE_RATE_EXACT) as usize / AUDIO_BLOCK_SAMPLES + 1;
+        let start = total / 2 - WINDOW / 2;
+        let (mut crossings, mut blocks) = (0, 0);
+        while sweep.is_playing() {
+            let out = render(&mut sweep).unwrap();
+            if (start..start + WINDOW).contains(&blocks) {
+                crossings += zero_crossings(&out[..]);
+            }
+            blocks += 1;
+        }
+        assert_eq!(blocks, total);
+
+        let mid = crossings as f32 / 2.0 * AUDIO_SAMPLE_RATE_EXACT
+            / (WINDOW * AUDIO_BLOCK_SAMPLES) as f32;
+        assert!((mid - 632.5).abs() < 20.0, "midpoint at {} Hz", mid);
+        assert!(
+            (sweep.read() - 20_000.0).abs() < 20.0,
+            "ended at {}",
+            sweep.read()
+        );
+    }
+
+    #[test]
+    fn sweep_downward_and_rejects_bad_args() {
+        reset_pool();
+        let mut sweep = AudioSynthToneSweep::new();
+        assert!(!sweep.play(0.5, 0.0, 1000.0, 1.0));
+        assert!(!sweep.play(0.5, 100.0, 30_000.0, 1.0));
+        assert!(!sweep.play(0.5, 100.0, 1000.0, 0.0));
+        assert!(!sweep.is_playing());
+
+        assert!(sweep.play(0.5, 4000.0, 1000.0, 0.1));
+        while sweep.is_playing() {
+            render(&mut sweep);
+        }
+        assert!(
+            (sweep.read() - 1000.0).abs() < 10.0,
+            "ended at {}",
+            sweep.read()
+        );
+    }
+}
@@ -0,0 +1,455 @@
+//! Multi-shape waveform oscillator.
+//!
+//! Port of `TeensyAudio/synth_waveform.cpp` (`AudioSynthWaveform`). Shares the
+//! phase-accumulator design of [`AudioSynthSine`](super::AudioSynthSine): the
+//! sine shape uses the 257-entry wavetable, while the other shapes are
+//! computed arithmetically from the top bits of the accumulator.
+//!
+//! An optional band-limited mode applies a PolyBLEP correction at the
+//! discontinuities of the sawtooth, square and pulse shapes to reduce aliasing.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::{AUDIO_BLOCK_SAMPLES, AUDIO_SAMPLE_RATE_EXACT};
+use crate::dsp::intrinsics::{mul_32x32_rshift32, saturate16};
+use crate::dsp::wavetables::sine_lookup;
+use crate::node::AudioNode;
+
+/// Waveform shapes supported by [`AudioSynthWaveform`].
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum WaveformShape {
+    /// Sine, from the wavetable with linear interpolation.
+    Sine,
+    /// Rising ramp: 0 → +max, jump to −max, rising back to 0.
+    Sawtooth,
+    /// Falling ramp (mirror image of [`Sawtooth`](Self::Sawtooth)).
+    SawtoothReverse,
+    /// 50% duty square: high for the first half-cycle, low for the second.
+    Square,
+    /// Triangle: 0 → +max at 90°, −max at 270°, back to 0.
+    Triangle,
+    /// Variable-duty square; see [`AudioSynthWaveform::pulse_width`].
+    Pulse,
+    /// Random level, re-sampled once per cycle and held.
+    SampleHold,
+}
+
+/// Multi-shape oscillator. Source node: 0 inputs, 1 output.
+///
+/// # Example
+/// ```ignore
+/// let mut osc = AudioSynthWaveform::new();
+/// osc.begin(WaveformShape::Sawtooth);
+/// osc.frequency(110.0);
+/// osc.amplitude(0.5);
+/// ```
+pub struct AudioSynthWaveform {
+    /// Phase accumulator (wraps naturally at 32 bits = 360°).
+    phase_accumulator: u32,
+    /// Phase increment per sample: `freq / SAMPLE_RATE * 2^32`.
+    phase_increment: u32,
+    /// Output magnitude in Q16.16 format. 0 = silent, 65536 = full scale.
+    magnitude: i32,
+    /// Selected waveform shape.
+    shape: WaveformShape,
+    /// Pulse shape: output is high while `phase_accumulator < pulse_threshold`.
+    pulse_threshold: u32,
+    /// Sample-and-hold: current held level (Q31).
+    held: i32,
+    /// Sample-and-hold: xorshift32 state (never zero).
+    seed: u32,
+    /// Apply PolyBLEP correction to saw/square/pulse edges.
+    bandlimited: bool,
+}
+
+impl AudioSynthWaveform {
+    /// Create a new oscillator: sine shape, silent, 50% pulse width.
+    pub const fn new() -> Self {
+        AudioSynthWaveform {
+            phase_accumulator: 0,
+            phase_increment: 0,
+            magnitude: 0,
+            shape: WaveformShape::Sine,
+            pulse_threshold: 0x8000_0000,
+            held: 0,
+            seed: 0x1234_5678,
+            bandlimited: false,
+        }
+    }
+
+    /// Select the waveform shape.
+    pub fn begin(&mut self, shape: WaveformShape) {
+        self.shape = shape;
+    }
+
+    /// Get the current waveform shape.
+    pub fn shape(&self) -> WaveformShape {
+        self.shape
+    }
+
+    /// Set the oscillator frequency in Hz.
+    ///
+    /// Phase increment is computed as `freq / AUDIO_SAMPLE_RATE_EXACT * 2^32`.
+    pub fn frequency(&mut self, hz: f32) {
+        let inc = hz * (4_294_967_296.0 / AUDIO_SAMPLE_RATE_EXACT);
+        self.phase_increment = inc as u32;
+    }
+
+    /// Set the output amplitude (0.0 = silent, 1.0 = full scale).
+    ///
+    /// The magnitude is stored as Q16.16: `level * 65536`.
+    pub fn amplitude(&mut self, level: f32) {
+        let clamped = level.clamp(0.0, 1.0);
+        self.magnitude = (clamped * 65536.0) as i32;
+    }
+
+    /// Set the phase offset in degrees (0–360).
+    pub fn phase(&mut self, angle: f32) {
+        self.phase_accumulator = (angle * (4_294_967_296.0 / 360.0)) as u32;
+    }
+
+    /// Set the duty cycle for [`WaveformShape::Pulse`] (0.0–1.0).
+    ///
+    /// This is the fraction of each cycle spent high; 0.5 is a square wave.
+    pub fn pulse_width(&mut self, width: f32) {
+        let clamped = width.clamp(0.0, 1.0);
+        self.pulse_threshold = (clamped as f64 * 4_294_967_295.0) as u32;
+    }
+
+    /// Enable or disable band-limited (PolyBLEP) edges.
+    ///
+    /// Smooths the discontinuities of the sawtooth, square and pulse shapes
+    /// over one sample either side, greatly reducing the aliasing of the naive
+    /// shapes at high frequencies. Sine, triangle and sample-and-hold are
+    /// unaffected.
+    pub fn bandlimited(&mut self, enable: bool) {
+        self.bandlimited = enable;
+    }
+
+    /// Advance the phase by one block without producing output.
+    fn advance_block(&mut self) {
+        self.phase_accumulator = self.phase_accumulator
+            .wrapping_add(self.phase_increment.wrapping_mul(AUDIO_BLOCK_SAMPLES as u32));
+    }
+
+    /// Next pseudo-random Q31 value (xorshift32).
+    fn next_random(&mut self) -> i32 {
+        let mut x = self.seed;
+        x ^= x << 13;
+        x ^= x >> 17;
+        x ^= x << 5;
+        self.seed = x;
+        x as i32
+    }
+}
+
+impl Default for AudioSynthWaveform {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+/// Triangle as Q31: rises 0 → max over the first quarter, falls to min at
+/// three quarters, and rises back to 0.
+#[inline(always)]
+fn triangle_q31(ph: u32) -> i32 {
+    let ph = ph as i64;
+    let v = if ph < 0x4000_0000 {
+        ph << 1
+    } else if ph < 0xC000_0000 {
+        (1i64 << 31) - ((ph - 0x4000_0000) << 1)
+    } else {
+        ((ph - 0xC000_0000) << 1) - (1i64 << 31)
+    };
+    v.clamp(i32::MIN as i64, i32::MAX as i64) as i32
+}
+
+/// PolyBLEP residual in Q16 (−1.0 to 1.0, as `i64`) for a unit rising
+/// step, `t` being the phase since the discontinuity and `dt` the phase
+/// increment per sample. Zero outside one sample of the edge, and when
+/// `dt` is zero (DC, no edges to correct).
+#[inline(always)]
+fn poly_blep(t: u32, dt: u32) -> i64 {
+    if dt == 0 {
+        return 0;
+    }
+    if t < dt {
+        // Just after the edge: x = t / dt, residual = 2x - x² - 1
+        let x = (((t as u64) << 16) / dt as u64) as i64;
+        2 * x - ((x * x) >> 16) - 65536
+    } else if t > 0u32.wrapping_sub(dt) {
+        // Just before the edge: y = (1 - t) / dt, residual = y² - 2y + 1
+        let y = (((0u32.wrapping_sub(t) as u64) << 16) / dt as u64) as i64;
+        ((y * y) >> 16) - 2 * y + 65536
+    } else {
+        0
+    }
+}
+
+impl AudioNode for AudioSynthWaveform {
+    const NUM_INPUTS: usize = 0;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioSynthWaveform";
+
+    fn update(
+        &mut self,
+        _inputs: &[Option<AudioBlockRef>],
+        outputs: &mut [Option<AudioBlockMut>],
+    ) {
+        if self.magnitude == 0 {
+            // Silent: advance phase but produce no output
+            self.advance_block();
+            return;
+        }
+
+        let mut out = match outputs[0].take() {
+            Some(b) => b,
+            None => {
+                self.advance_block();
+                return;
+            }
+        };
+
+        let mut ph = self.phase_accumulator;
+        let inc = self.phase_increment;
+        let mag = self.magnitude;
+
+        for i in 0..AUDIO_BLOCK_SAMPLES {
+            let val = match self.shape {
+                WaveformShape::Sine => sine_lookup(ph),
+                WaveformShape::Sawtooth => ph as i32,
+                WaveformShape::SawtoothReverse => !ph as i32,
+                WaveformShape::Square => {
+                    if ph < 0x8000_0000 { 0x7FFF_0000 } else { -0x7FFF_0000 }
+                }
+                WaveformShape::Triangle => triangle_q31(ph),
+                WaveformShape::Pulse => {
+                    if ph < self.pulse_threshold { 0x7FFF_0000 } else { -0x7FFF_0000 }
+                }
+                WaveformShape::SampleHold => self.held,
+            };
+
+            if self.bandlimited && inc != 0 {
+                // A residual spans ±1.0 (Q16) across an edge 2.0 high
+                // (−1 → +1), so 1.0 in Q16 becomes 1 << 31 in Q31: << 15.
+                let blep = match self.shape {
+                    WaveformShape::Sawtooth => -poly_blep(ph.wrapping_add(0x8000_0000), inc),
+                    WaveformShape::SawtoothReverse => poly_blep(ph.wrapping_add(0x8000_0000), inc),
+                    WaveformShape::Square => {
+                        poly_blep(ph, inc) - poly_blep(ph.wrapping_add(0x8000_0000), inc)
+                    }
+                    WaveformShape::Pulse => {
+                        poly_blep(ph, inc) - poly_blep(ph.wrapping_sub(self.pulse_threshold), inc)
+                    }
+                    _ => 0,
+                };
+                let corrected = val as i64 + (blep << 15);
+                // Q31 × Q16.16 >> 32 = Q15
+                out[i] = saturate16(((corrected * mag as i64) >> 32) as i32);
+            } else {
+                // Q31 × Q16.16 >> 32 = Q15
+                out[i] = mul_32x32_rshift32(val, mag) as i16;
+            }
+
+            let next = ph.wrapping_add(inc);
+            if self.shape == WaveformShape::SampleHold && next < ph {
+                // Phase wrapped: start of a new cycle
+                self.held = self.next_random();
+            }
+            ph = next;
+        }
+
+        self.phase_accumulator = ph;
+        outputs[0] = Some(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// Run one update and return a copy of the output block.
+    fn render(osc: &mut AudioSynthWaveform) -> [i16; AUDIO_BLOCK_SAMPLES] {
+        let output = AudioBlockMut::alloc().unwrap();
+        let mut outputs = [Some(output)];
+        let inputs: [Option<AudioBlockRef>; 0] = [];
+        osc.update(&inputs, &mut outputs);
+        let mut samples = [0i16; AUDIO_BLOCK_SAMPLES];
+        samples.copy_from_slice(&outputs[0].as_ref().unwrap()[..]);
+        samples
+    }
+
+    /// Full-amplitude oscillator with an exact period of `period` samples.
+    fn osc(shape: WaveformShape, period: u32) -> AudioSynthWaveform {
+        let mut osc = AudioSynthWaveform::new();
+        osc.begin(shape);
+        osc.phase_increment = (1u64 << 32).div_euclid(period as u64) as u32;
+        osc.amplitude(1.0);
+        osc
+    }
+
+    #[test]
+    fn sine_matches_sine_node() {
+        reset_pool();
+        let mut wave = osc(WaveformShape::Sine, 100);
+        wave.frequency(440.0);
+        let mut sine = crate::nodes::AudioSynthSine::new();
+        sine.frequency(440.0);
+        sine.amplitude(1.0);
+
+        let a = render(&mut wave);
+        let output = AudioBlockMut::alloc().unwrap();
+        let mut outputs = [Some(output)];
+        sine.update(&[], &mut outputs);
+        assert_eq!(&a[..], &outputs[0].as_ref().unwrap()[..]);
+    }
+
+    #[test]
+    fn sawtooth_ramps_and_wraps() {
+        reset_pool();
+        // 4 cycles per block: period of 32 samples
+        let mut saw = osc(WaveformShape::Sawtooth, 32);
+        let out = render(&mut saw);
+        assert!(out[0].abs() < 100);
+        for i in 1..15 {
+            assert!(out[i] > out[i - 1], "saw not rising at {}", i);
+        }
+        // Wrap from +max to -max halfway through the cycle
+        assert!(out[15] > 30000 && out[16] < -30000, "{} {}", out[15], out[16]);
+
+        let mut rev = osc(WaveformShape::SawtoothReverse, 32);
+        let out_rev = render(&mut rev);
+        for i in 1..15 {
+            assert!(out_rev[i] < out_rev[i - 1], "reverse saw not falling at {}", i);
+        }
+    }
+
+    #[test]
+    fn square_levels() {
+        reset_pool();
+        let mut sq = osc(WaveformShape::Square, 32);
+        let out = render(&mut sq);
+        assert!(out[..16].iter().all(|&s| s > 32000));
+        assert!(out[16..32].iter().all(|&s| s < -32000));
+    }
+
+    #[test]
+    fn triangle_peaks_at_quarter_cycle() {
+        reset_pool();
+        let mut tri = osc(WaveformShape::Triangle, 64);
+        let out = render(&mut tri);
+        assert!(out[0].abs() < 100);
+        assert!(out[16] > 32000, "peak {}", out[16]);
+        assert!(out[32].abs() < 1100, "zero crossing {}", out[32]);
+        assert!(out[48] < -32000, "trough {}", out[48]);
+        for i in 1..16 {
+            assert!(out[i] > out[i - 1]);
+        }
+    }
+
+    #[test]
+    fn pulse_width_sets_duty_cycle() {
+        reset_pool();
+        let mut pulse = osc(WaveformShape::Pulse, 32);
+        pulse.pulse_width(0.25);
+        let out = render(&mut pulse);
+        let high = out.iter().filter(|&&s| s > 0).count();
+        assert_eq!(high, AUDIO_BLOCK_SAMPLES / 4);
+    }
+
+    #[test]
+    fn sample_hold_changes_once_per_cycle() {
+        reset_pool();
+        let mut sh = osc(WaveformShape::SampleHold, 32);
+        let out = render(&mut sh);
+        let mut changes = 0;
+        for i in 1..AUDIO_BLOCK_SAMPLES {
+            if out[i] != out[i - 1] {
+                changes += 1;
+            }
+        }
+        // 4 cycles per block; the first held level is 0
+        assert!((3..=4).contains(&changes), "changes = {}", changes);
+    }
+
+    #[test]
+    fn silent_advances_phase() {
+        reset_pool();
+        let mut wave = osc(WaveformShape::Sawtooth, 100);
+        wave.amplitude(0.0);
+        let inc = wave.phase_increment;
+
+        let output = AudioBlockMut::alloc().unwrap();
+        let mut outputs = [Some(output)];
+        wave.update(&[], &mut outputs);
+
+        // Output block untouched, but phase moved on by one block
+        assert!(outputs[0].is_some());
+        assert_eq!(wave.phase_accumulator, inc.wrapping_mul(AUDIO_BLOCK_SAMPLES as u32));
+    }
+
+    /// Sum of squared successive differences: a cheap high-frequency
+    /// energy measure (a first-difference filter boosts the top octave).
+    fn diff_energy(osc: &mut AudioSynthWaveform, blocks: usize) -> u64 {
+        let mut energy = 0u64;
+        let mut prev: Option<i16> = None;
+        for _ in 0..blocks {
+            for &s in render(osc).iter() {
+                if let Some(p) = prev {
+                    let d = s as i64 - p as i64;
+                    energy += (d * d) as u64;
+                }
+                prev = Some(s);
+            }
+        }
+        energy
+    }
+
+    #[test]
+    fn bandlimited_saw_reduces_high_frequency_energy() {
+        reset_pool();
+        let mut naive = osc(WaveformShape::Sawtooth, 1);
+        naive.frequency(10_000.0);
+        let mut smooth = osc(WaveformShape::Sawtooth, 1);
+        smooth.frequency(10_000.0);
+        smooth.bandlimited(true);
+
+        let e_naive = diff_energy(&mut naive, 8);
+        let e_smooth = diff_energy(&mut smooth, 8);
+        // About 58% less; allow down to a 50% reduction
+        assert!(
+            e_smooth * 2 < e_naive,
+            "band-limited {} vs naive {}",
+            e_smooth,
+            e_naive
+        );
+    }
+
+    #[test]
+    fn bandlimited_square_keeps_levels_away_from_edges() {
+        reset_pool();
+        let mut sq = osc(WaveformShape::Square, 32);
+        sq.bandlimited(true);
+        let out = render(&mut sq);
+        // One sample either side of each edge is corrected; the rest is flat
+        assert!(out[2..15].iter().all(|&s| s > 32000));
+        assert!(out[18..31].iter().all(|&s| s < -32000));
+        assert!(out[16].abs() < 32000 || out[15].abs() < 32000);
+    }
+
+    #[test]
+    fn bandlimited_dc_skips_blep() {
+        reset_pool();
+        let mut saw = osc(WaveformShape::Sawtooth, 1);
+        saw.phase_increment = 0;
+        saw.phase(90.0);
+        saw.bandlimited(true);
+        let out = render(&mut saw);
+        assert!(out.iter().all(|&s| s == out[0]));
+        assert!((out[0] - 16383).abs() <= 1, "{}", out[0]);
+    }
+}
@@ -0,0 +1,230 @@
+//! Single-cycle wavetable oscillator.
+//!
+//! Generalizes [`AudioSynthSine`](super::AudioSynthSine) to a
+//! user-supplied table of any power-of-two length. Unlike `SINE_TABLE`,
+//! the table has no guard entry: interpolation wraps from the last entry
+//! back to the first.
+
+use crate::block::{AudioBlockMut, AudioBlockRef};
+use crate::constants::AUDIO_BLOCK_SAMPLES;
+use crate::dsp::freq_to_increment;
+use crate::dsp::intrinsics::mul_32x32_rshift32;
+use crate::node::AudioNode;
+
+/// Largest supported table, so at least 16 phase bits remain for
+/// interpolation.
+pub const WAVETABLE_MAX_LEN: usize = 65536;
+
+/// Wavetable oscillator. Source node: 0 inputs, 1 output.
+///
+/// Plays one cycle of the table per period, interpolating linearly
+/// between entries. Silent until both a table and an amplitude are set.
+///
+/// # Example
+/// ```ignore
+/// static ORGAN: [i16; 512] = [/* one cycle */];
+/// let mut osc = AudioSynthWavetable::new();
+/// osc.set_table(&ORGAN);
+/// osc.frequency(220.0);
+/// osc.amplitude(0.7);
+/// ```
+pub struct AudioSynthWavetable {
+    table: &'static [i16],
+    /// `log2(table.len())`: the top `bits` of the phase select the entry.
+    bits: u32,
+    /// Phase accumulator (wraps naturally at 32 bits = one cycle).
+    phase_accumulator: u32,
+    /// Phase increment per sample: `freq / SAMPLE_RATE * 2^32`.
+    phase_increment: u32,
+    /// Output magnitude in Q16.16 format. 0 = silent, 65536 = full scale.
+    magnitude: i32,
+}
+
+impl AudioSynthWavetable {
+    /// Create a new wavetable oscillator with no table, initially silent.
+    pub const fn new() -> Self {
+        AudioSynthWavetable {
+            table: &[],
+            bits: 0,
+            phase_accumulator: 0,
+            phase_increment: 0,
+            magnitude: 0,
+        }
+    }
+
+    /// Set the single-cycle table.
+    ///
+    /// The length must be a power of two from 2 to
+    /// [`WAVETABLE_MAX_LEN`]; otherwise the call is ignored and `false` is
+    /// returned. The phase is kept, so switching tables does not restart
+    /// the cycle.
+    pub fn set_table(&mut self, table: &'static [i16]) -> bool {
+        if table.len() < 2 || table.len() > WAVETABLE_MAX_LEN || !table.len().is_power_of_two() {
+            return false;
+        }
+        self.table = table;
+        self.bits = table.len().trailing_zeros();
+        true
+    }
+
+    /// Set the oscillator frequency in Hz.
+    pub fn frequency(&mut self, hz: f32) {
+        self.phase_increment = freq_to_increment(hz);
+    }
+
+    /// Set the output amplitude (0.0 = silent, 1.0 = full scale).
+    pub fn amplitude(&mut self, level: f32) {
+        self.magnitude = (level.clamp(0.0, 1.0) * 65536.0) as i32;
+    }
+
+    /// Set the phase offset in degrees (0–360).
+    pub fn phase(&mut self, angle: f32) {
+        self.phase_accumulator = (angle * (4_294_967_296.0 / 360.0)) as u32;
+    }
+}
+
+impl Default for AudioSynthWavetable {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl AudioNode for AudioSynthWavetable {
+    const NUM_INPUTS: usize = 0;
+    const NUM_OUTPUTS: usize = 1;
+    const NAME: &'static str = "AudioSynthWavetable";
+
+    fn update(&mut self, _inputs: &[Option<AudioBlockRef>], outputs: &mut [Option<AudioBlockMut>]) {
+        let out = if self.magnitude == 0 || self.table.is_empty() {
+            None
+        } else {
+            outputs[0].take()
+        };
+        let Some(mut out) = out else {
+            self.phase_accumulator = self.phase_accumulator.wrapping_add(
+                self.phase_increment
+                    .wrapping_mul(AUDIO_BLOCK_SAMPLES as u32),
+            );
+            return;
+        };
+
+        let table = self.table;
+        let shift = 32 - self.bits;
+        let mask = table.len() - 1;
+        let mut ph = self.phase_accumulator;
+        let inc = self.phase_increment;
+
+        for dst in out.iter_mut() {
+            let index = (ph >> shift) as usize;
+            let val1 = table[index] as i32;
+            let val2 = table[(index + 1) & mask] as i32;
+            // 16 bits below the index bits as the interpolation weight
+            let scale = ((ph << self.bits) >> 16) as i32;
+            let interpolated = val1 * (0x10000 - scale) + val2 * scale;
+            *dst = mul_32x32_rshift32(interpolated, self.magnitude) as i16;
+            ph = ph.wrapping_add(inc);
+        }
+
+        self.phase_accumulator = ph;
+        outputs[0] = Some(out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::block::pool::POOL;
+    use crate::constants::AUDIO_SAMPLE_RATE_EXACT;
+
+    fn reset_pool() {
+        POOL.reset();
+    }
+
+    /// One cycle of a rising ramp from -32768 to just under +32768.
+    static RAMP_512: [i16; 512] = {
+        let mut t = [0i16; 512];
+        let mut i = 0;
+        while i < 512 {
+            t[i] = (i as i32 * 128 - 32768) as i16;
+            i += 1;
+        }
+        t
+    };
+
+    /// Count sign changes over `blocks` blocks, including across block edges.
+    fn zero_crossings(osc: &mut AudioSynthWavetable, blocks: usize) -> usize {
+        let mut count = 0;
+        let mut last: Option<i16> = None;
+        for _ in 0..blocks {
+            let mut outputs = [AudioBlockMut::alloc()];
+            osc.update(&[], &mut outputs);
+            for &s in outputs[0].as_ref().unwrap().iter() {
+                if last.is_some_and(|p| (p < 0) != (s < 0)) {
+                    count += 1;
+                }
+                last = Some(s);
+            }
+        }
+        count
+    }
+
+    #[test]
+    fn ramp_table_plays_requested_frequency() {
+        reset_pool();
+        let mut osc = AudioSynthWavetable::new();
+        assert!(osc.set_table(&RAMP_512));
+        osc.amplitude(1.0);
+
+        // A ramp crosses zero twice per cycle: mid-ramp and at the wrap
+        let blocks = 100;
+        let seconds = (blocks * AUDIO_BLOCK_SAMPLES) as f32 / AUDIO_SAMPLE_RATE_EXACT;
+        for hz in [100.0, 1000.0, 5000.0] {
+            osc.frequency(hz);
+            osc.phase(90.0);
+            let measured = zero_crossings(&mut osc, blocks) as f32 / 2.0 / seconds;
+            assert!(
+                (measured - hz).abs() < hz * 0.01 + 1.0 / seconds,
+                "requested {} Hz, measured {}",
+                hz,
+                measured
+            );
+        }
+    }
+
+    #[test]
+    fn ramp_table_interpolates_and_wraps() {
+        reset_pool();
+        let mut osc = AudioSynthWavetable::new();
+        osc.set_table(&RAMP_512);
+        osc.amplitude(1.0);
+        // Half an entry per sample
+        osc.phase_accumulator = 0;
+        osc.phase_increment = 1 << (32 - 9 - 1);
+
+        let mut outputs = [AudioBlockMut::alloc()];
+        osc.update(&[], &mut outputs);
+        let out = outputs[0].take().unwrap();
+        assert_eq!(out[0], -32768);
+        assert!((out[1] as i32 - -32704).abs() <= 1, "midpoint {}", out[1]);
+        assert!((out[2] as i32 - -32640).abs() <= 1, "next entry {}", out[2]);
+
+        // Between the last entry and the first, not off the end
+        osc.phase_accumulator = u32::MAX - (1 << 22) + 1;
+        let mut outputs = [AudioBlockMut::alloc()];
+        osc.update(&[], &mut outputs);
+        let out = outputs[0].take().unwrap();
+        assert!(out[0] < 0 && out[0] > -100, "wrap midpoint {}", out[0]);
+    }
+
+    #[test]
+    fn rejects_non_power_of_two_tables() {
+        static ODD: [i16; 257] = [0; 257];
+        static ONE: [i16; 1] = [0];
+        let mut osc = AudioSynthWavetable::new();
+        assert!(!osc.set_table(&ODD));
+        assert!(!osc.set_table(&ONE));
+        assert!(osc.set_table(&RAMP_512));
+        assert!(!osc.set_table(&ODD));
+        assert_eq!(osc.table.len(), 512, "rejected table leaves the old one");
+    }
+}